@@ -0,0 +1,87 @@
+use serde::{Deserialize, Deserializer};
+
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+
+#[cfg(feature = "decimal")]
+use crate::resp_structs::{QuoteResponse, TransactionInfo};
+
+/// An amount as sent by the API: a JSON number or a decimal string
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Number(f64),
+    String(String),
+}
+
+impl NumberOrString {
+    fn into_f64<E: serde::de::Error>(self) -> Result<f64, E> {
+        match self {
+            NumberOrString::Number(n) => Ok(n),
+            NumberOrString::String(s) => s.trim().parse().map_err(|_| E::custom(format!("invalid amount {:?}", s))),
+        }
+    }
+}
+
+/// `deserialize_with` of amounts that may arrive as numbers or strings
+pub(crate) fn number_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    NumberOrString::deserialize(deserializer)?.into_f64()
+}
+
+/// [`number_or_string`] of optional amounts; pair with `#[serde(default)]`
+pub(crate) fn opt_number_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    Option::<NumberOrString>::deserialize(deserializer)?.map(NumberOrString::into_f64).transpose()
+}
+
+/// Exact decimal value of an amount
+///
+/// Converts the shortest decimal representation of the float, so an amount the API
+/// sent as `0.3` becomes exactly `0.3` rather than `0.299999999999999988897...`.
+/// Amounts with more significant digits than an `f64` holds (about 15) were already
+/// rounded when parsed. `None` for NaN and infinities.
+/// # Examples
+/// ```
+/// use std::str::FromStr;
+/// use rust_decimal::Decimal;
+/// use fogswap_sdk_rust::amount::to_decimal;
+///
+/// assert_eq!(to_decimal(0.3), Some(Decimal::from_str("0.3").unwrap()));
+/// assert_eq!(to_decimal(0.1).unwrap() + to_decimal(0.2).unwrap(), Decimal::from_str("0.3").unwrap());
+/// ```
+#[cfg(feature = "decimal")]
+pub fn to_decimal(amount: f64) -> Option<Decimal> {
+    if !amount.is_finite() {
+        return None;
+    }
+    amount.to_string().parse().ok()
+}
+
+/// Amount to send to the API, through the shortest float with the same decimal representation
+#[cfg(feature = "decimal")]
+pub fn from_decimal(amount: Decimal) -> f64 {
+    amount.normalize().to_string().parse().unwrap_or_default()
+}
+
+#[cfg(feature = "decimal")]
+impl QuoteResponse {
+
+    pub fn amount_from_decimal(&self) -> Option<Decimal> {
+        to_decimal(self.amount_from)
+    }
+
+    pub fn amount_to_decimal(&self) -> Option<Decimal> {
+        to_decimal(self.amount_to)
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl TransactionInfo {
+
+    pub fn amount_from_decimal(&self) -> Option<Decimal> {
+        to_decimal(self.amount_from)
+    }
+
+    pub fn amount_to_decimal(&self) -> Option<Decimal> {
+        to_decimal(self.amount_to)
+    }
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+
+use crate::req_structs::{CreateTransactionRequest, Pair, QuoteRequest};
+use crate::resp_structs::{PairLimits, QuoteResponse, TokenList, TransactionInfo, TxType};
+use crate::FogswapSdk;
+
+/// Object-safe view of the Fogswap client, for dependency injection and mocking
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use fogswap_sdk_rust::{ArcFogswap, FogswapSdk};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let api: ArcFogswap = Arc::new(FogswapSdk::new());
+/// let tokens = api.get_token_list().await?;
+/// println!("Found {} networks", tokens.len());
+/// # Ok(())
+/// # }
+/// ```
+pub trait FogswapApi {
+
+    fn get_token_list(&self) -> BoxFuture<'_, Result<Vec<TokenList>>>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn get_quote<'a>(
+        &'a self,
+        amount_from: f64,
+        network_from: &'a str,
+        contract_address_from: &'a str,
+        network_to: &'a str,
+        contract_address_to: &'a str,
+        tx_type: Option<TxType>,
+        is_use_xmr: Option<bool>
+    ) -> BoxFuture<'a, Result<QuoteResponse>>;
+
+    /// Quote from a `QuoteRequest`; by default forwards to `get_quote`, dropping
+    /// `promo_code`, the partner fee and `extra_params`
+    fn get_quote_with<'a>(&'a self, req: &'a QuoteRequest) -> BoxFuture<'a, Result<QuoteResponse>> {
+        self.get_quote(
+            req.amount_from, &req.network_from, &req.contract_address_from,
+            &req.network_to, &req.contract_address_to, req.tx_type.clone(), req.is_use_xmr
+        )
+    }
+
+    fn create_transaction_with<'a>(&'a self, req: &'a CreateTransactionRequest) -> BoxFuture<'a, Result<TransactionInfo>>;
+
+    fn get_transaction_info<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<TransactionInfo>>;
+
+    fn get_pair_limits<'a>(&'a self, pair: &'a Pair, tx_type: Option<TxType>) -> BoxFuture<'a, Result<PairLimits>>;
+}
+
+/// Shared, type-erased Fogswap client
+pub type ArcFogswap = Arc<dyn FogswapApi + Send + Sync>;
+
+impl FogswapApi for FogswapSdk {
+
+    fn get_token_list(&self) -> BoxFuture<'_, Result<Vec<TokenList>>> {
+        Box::pin(FogswapSdk::get_token_list(self))
+    }
+
+    fn get_quote<'a>(
+        &'a self,
+        amount_from: f64,
+        network_from: &'a str,
+        contract_address_from: &'a str,
+        network_to: &'a str,
+        contract_address_to: &'a str,
+        tx_type: Option<TxType>,
+        is_use_xmr: Option<bool>
+    ) -> BoxFuture<'a, Result<QuoteResponse>> {
+        let req = QuoteRequest {
+            amount_from,
+            network_from: network_from.to_string(),
+            contract_address_from: contract_address_from.to_string(),
+            network_to: network_to.to_string(),
+            contract_address_to: contract_address_to.to_string(),
+            tx_type,
+            is_use_xmr,
+            ..Default::default()
+        };
+        Box::pin(async move { FogswapSdk::get_quote_with(self, &req).await })
+    }
+
+    fn get_quote_with<'a>(&'a self, req: &'a QuoteRequest) -> BoxFuture<'a, Result<QuoteResponse>> {
+        Box::pin(FogswapSdk::get_quote_with(self, req))
+    }
+
+    fn create_transaction_with<'a>(&'a self, req: &'a CreateTransactionRequest) -> BoxFuture<'a, Result<TransactionInfo>> {
+        Box::pin(FogswapSdk::create_transaction_with(self, req))
+    }
+
+    fn get_transaction_info<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<TransactionInfo>> {
+        Box::pin(FogswapSdk::get_transaction_info(self, id))
+    }
+
+    fn get_pair_limits<'a>(&'a self, pair: &'a Pair, tx_type: Option<TxType>) -> BoxFuture<'a, Result<PairLimits>> {
+        Box::pin(FogswapSdk::get_pair_limits(self, pair, tx_type))
+    }
+}
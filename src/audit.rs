@@ -0,0 +1,134 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::meta::ResponseMeta;
+use crate::rt::SystemTime;
+use crate::FogswapSdk;
+
+/// Structured record of one state-changing API call
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditRecord {
+    pub method: String,
+    pub endpoint: String,
+    /// Request payload, with the fields of the `AuditPolicy` replaced by `"***"`
+    pub payload: Option<Value>,
+    pub started_at: SystemTime,
+    pub finished_at: SystemTime,
+    /// Id of the transaction the call returned, if any
+    pub tx_id: Option<String>,
+    /// Server request id, when the API answered
+    pub request_id: Option<String>,
+    /// `None` if the call succeeded
+    pub error: Option<String>,
+}
+
+/// What an `AuditSink` is allowed to see
+#[derive(Debug, Clone, Default)]
+pub struct AuditPolicy {
+    /// Top-level payload fields to redact, e.g. `payout_address`
+    pub redact_fields: Vec<String>,
+}
+
+impl AuditPolicy {
+
+    fn redact(&self, payload: &Option<Value>) -> Option<Value> {
+        let mut payload = payload.clone()?;
+        if let Some(fields) = payload.as_object_mut() {
+            for name in &self.redact_fields {
+                if let Some(value) = fields.get_mut(name)
+                    && !value.is_null() {
+                    *value = Value::String("***".to_string());
+                }
+            }
+        }
+        Some(payload)
+    }
+}
+
+/// Archive of every state-changing call (every POST, such as transaction creation)
+///
+/// Called once per call, after its outcome is known and whether it succeeded or not.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{AuditRecord, AuditSink};
+///
+/// #[derive(Debug)]
+/// struct StdoutAudit;
+///
+/// impl AuditSink for StdoutAudit {
+///     fn record(&self, record: &AuditRecord) {
+///         println!("{}", serde_json::to_string(record).unwrap_or_default());
+///     }
+/// }
+/// ```
+pub trait AuditSink: Debug + Send + Sync {
+    fn record(&self, record: &AuditRecord);
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Audit {
+    sink: Arc<dyn AuditSink>,
+    policy: AuditPolicy,
+}
+
+impl FogswapSdk {
+
+    /// Report every state-changing call to `sink`, redacted according to `policy`
+    /// # Examples
+    /// ```
+    /// # use fogswap_sdk_rust::{AuditRecord, AuditSink};
+    /// # #[derive(Debug)]
+    /// # struct StdoutAudit;
+    /// # impl AuditSink for StdoutAudit {
+    /// #     fn record(&self, _record: &AuditRecord) {}
+    /// # }
+    /// use std::sync::Arc;
+    /// use fogswap_sdk_rust::{AuditPolicy, FogswapSdk};
+    ///
+    /// let sdk = FogswapSdk::new().with_audit_sink(Arc::new(StdoutAudit), AuditPolicy {
+    ///     redact_fields: vec!["payout_address".to_string(), "payout_extra_id".to_string()],
+    /// });
+    /// ```
+    pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>, policy: AuditPolicy) -> Self {
+        self.audit = Some(Audit { sink, policy });
+        self
+    }
+
+    /// Record the outcome of a call, if it changes state and auditing is enabled
+    pub(crate) fn audit(
+        &self,
+        req_method: &reqwest::Method,
+        endpoint: &str,
+        payload: &Option<Value>,
+        started_at: SystemTime,
+        result: &Result<(Value, ResponseMeta)>,
+    ) {
+        let Some(audit) = &self.audit else {
+            return;
+        };
+        if *req_method != reqwest::Method::POST {
+            return;
+        }
+        let (tx_id, request_id, error) = match result {
+            Ok((body, meta)) => {
+                let tx_id = body.pointer("/result/id").and_then(Value::as_str).map(str::to_string);
+                (tx_id, meta.request_id.clone(), None)
+            }
+            Err(e) => (None, None, Some(format!("{:#}", e))),
+        };
+        audit.sink.record(&AuditRecord {
+            method: req_method.to_string(),
+            endpoint: endpoint.to_string(),
+            payload: audit.policy.redact(payload),
+            started_at,
+            finished_at: SystemTime::now(),
+            tx_id,
+            request_id,
+            error,
+        });
+    }
+}
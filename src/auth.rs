@@ -0,0 +1,270 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use tokio::sync::Mutex;
+
+use crate::dedup::DuplicateGuard;
+use crate::FogswapSdk;
+
+/// Source of short-lived bearer tokens
+///
+/// The client asks for a token before the first request and again whenever the
+/// API answers `401 Unauthorized`, then replays the rejected request once.
+/// # Examples
+/// ```
+/// use anyhow::Result;
+/// use futures::future::BoxFuture;
+/// use fogswap_sdk_rust::TokenProvider;
+///
+/// #[derive(Debug)]
+/// struct StaticToken(String);
+///
+/// impl TokenProvider for StaticToken {
+///     fn fetch_token(&self) -> BoxFuture<'_, Result<String>> {
+///         Box::pin(async move { Ok(self.0.clone()) })
+///     }
+/// }
+/// ```
+pub trait TokenProvider: Debug + Send + Sync {
+    /// Obtain a fresh token
+    fn fetch_token(&self) -> BoxFuture<'_, Result<String>>;
+}
+
+/// Where the partner API key is sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiKeyPlacement {
+    /// Request header of this name
+    Header(String),
+    /// Query parameter of this name
+    Query(String),
+}
+
+impl Default for ApiKeyPlacement {
+    fn default() -> Self {
+        ApiKeyPlacement::Header("x-api-key".to_string())
+    }
+}
+
+/// Partner API key attached to every request
+#[derive(Clone)]
+pub(crate) struct ApiKey {
+    pub key: String,
+    pub placement: ApiKeyPlacement,
+}
+
+impl Debug for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKey").field("key", &"***").field("placement", &self.placement).finish()
+    }
+}
+
+impl ApiKey {
+
+    pub fn apply(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.placement {
+            ApiKeyPlacement::Header(name) => req.header(name.as_str(), &self.key),
+            ApiKeyPlacement::Query(name) => req.query(&[(name, &self.key)]),
+        }
+    }
+}
+
+/// Credentials of one partner account, for `FogswapSdk::for_account`
+#[derive(Clone, Default)]
+pub struct Account {
+    pub api_key: Option<String>,
+    /// Sent as the `x-partner-id` header, for affiliate attribution
+    pub partner_id: Option<String>,
+}
+
+impl Debug for Account {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Account")
+            .field("api_key", &self.api_key.as_ref().map(|_| "***"))
+            .field("partner_id", &self.partner_id)
+            .finish()
+    }
+}
+
+/// Current token of a client, refreshed at most once per expiry
+#[derive(Debug)]
+pub(crate) struct AuthState {
+    provider: Arc<dyn TokenProvider>,
+    token: Mutex<Option<String>>,
+}
+
+impl AuthState {
+
+    pub fn new(provider: Arc<dyn TokenProvider>) -> Self {
+        Self { provider, token: Mutex::new(None) }
+    }
+
+    pub async fn token(&self) -> Result<String> {
+        let mut token = self.token.lock().await;
+        match token.as_ref() {
+            Some(token) => Ok(token.clone()),
+            None => {
+                let fresh = self.provider.fetch_token().await?;
+                *token = Some(fresh.clone());
+                Ok(fresh)
+            }
+        }
+    }
+
+    /// Replace `stale` with a new token; concurrent callers holding the same stale
+    /// token wait for the first refresh instead of starting their own
+    pub async fn refresh(&self, stale: &str) -> Result<String> {
+        let mut token = self.token.lock().await;
+        if let Some(current) = token.as_ref()
+            && current != stale {
+            return Ok(current.clone());
+        }
+        let fresh = self.provider.fetch_token().await?;
+        *token = Some(fresh.clone());
+        Ok(fresh)
+    }
+}
+
+impl FogswapSdk {
+
+    /// Authenticate every request with a bearer token from `provider`
+    /// # Examples
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::future::BoxFuture;
+    /// # use fogswap_sdk_rust::TokenProvider;
+    /// # #[derive(Debug)]
+    /// # struct StaticToken(String);
+    /// # impl TokenProvider for StaticToken {
+    /// #     fn fetch_token(&self) -> BoxFuture<'_, Result<String>> {
+    /// #         Box::pin(async move { Ok(self.0.clone()) })
+    /// #     }
+    /// # }
+    /// use std::sync::Arc;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// let sdk = FogswapSdk::new().with_token_provider(Arc::new(StaticToken("token".to_string())));
+    /// ```
+    pub fn with_token_provider(mut self, provider: Arc<dyn TokenProvider>) -> Self {
+        self.auth = Some(Arc::new(AuthState::new(provider)));
+        self
+    }
+
+    /// Send the partner API key with every request, in the `x-api-key` header
+    ///
+    /// Requests the API rejects with `401` or `403` fail with `FogswapSdkError::AuthError`.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// let sdk = FogswapSdk::new().with_api_key("YOUR_API_KEY");
+    /// ```
+    pub fn with_api_key(self, key: &str) -> Self {
+        self.with_api_key_in(key, ApiKeyPlacement::default())
+    }
+
+    /// Send the partner API key with every request, in a custom header or query parameter
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{ApiKeyPlacement, FogswapSdk};
+    ///
+    /// let sdk = FogswapSdk::new().with_api_key_in("YOUR_API_KEY", ApiKeyPlacement::Query("api_key".to_string()));
+    /// ```
+    pub fn with_api_key_in(mut self, key: &str, placement: ApiKeyPlacement) -> Self {
+        self.api_key = Some(ApiKey { key: key.to_string(), placement });
+        self.share_secret_query_param();
+        self
+    }
+
+    /// Send `partner_id` as the `x-partner-id` header of every request, and as the
+    /// default `partner_id` of quotes and creates
+    pub fn with_partner_id(mut self, partner_id: &str) -> Self {
+        self.partner_id = Some(partner_id.to_string());
+        self
+    }
+
+    /// Default affiliate fee of quotes and creates, in basis points
+    ///
+    /// `QuoteRequest::fee_bps` and `CreateTransactionRequest::fee_bps` override it.
+    /// The applied fee is reported on `QuoteResponse::partner_fee` and
+    /// `TransactionInfo::partner_fee`.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// let sdk = FogswapSdk::new()
+    ///     .with_api_key("YOUR_API_KEY")
+    ///     .with_partner_id("my-wallet")
+    ///     .with_partner_fee_bps(50);
+    /// ```
+    pub fn with_partner_fee_bps(mut self, fee_bps: u32) -> Self {
+        self.partner_fee_bps = Some(fee_bps);
+        self
+    }
+
+    /// A handle acting as another partner account
+    ///
+    /// The handle shares the connection pool, caches, request queue, health and shutdown
+    /// of this client, but sends the account's API key (in this client's key placement)
+    /// and partner id. Duplicate-create protection is kept per account. The bearer token
+    /// of `with_token_provider` is not inherited; give the handle its own provider if
+    /// the account needs one.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Account, FogswapSdk};
+    ///
+    /// let sdk = FogswapSdk::new();
+    /// let brand_a = sdk.for_account(Account { api_key: Some("KEY_A".to_string()), partner_id: Some("brand-a".to_string()) });
+    /// let brand_b = sdk.for_account(Account { api_key: Some("KEY_B".to_string()), partner_id: Some("brand-b".to_string()) });
+    /// ```
+    pub fn for_account(&self, account: Account) -> Self {
+        let mut sdk = self.clone();
+        let placement = self.api_key.as_ref().map(|k| k.placement.clone()).unwrap_or_default();
+        sdk.api_key = account.api_key.map(|key| ApiKey { key, placement });
+        sdk.partner_id = account.partner_id;
+        // The token identifies this client's account, never another one
+        sdk.auth = None;
+        // A duplicate must never resolve to a transaction of another account
+        sdk.dedup = self.dedup.as_ref().map(|guard| Arc::new(DuplicateGuard::new(guard.config().clone())));
+        sdk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use anyhow::Result;
+    use futures::future::BoxFuture;
+
+    use super::TokenProvider;
+    use crate::testing::MockTransport;
+    use crate::{Account, FogswapSdk};
+
+    #[derive(Debug)]
+    struct StaticToken(String);
+
+    impl TokenProvider for StaticToken {
+        fn fetch_token(&self) -> BoxFuture<'_, Result<String>> {
+            Box::pin(async move { Ok(self.0.clone()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn account_handle_does_not_send_parent_token() {
+        let mock = Arc::new(MockTransport::new());
+        let sdk = FogswapSdk::new()
+            .with_transport(mock.clone())
+            .with_token_provider(Arc::new(StaticToken("parent-token".to_string())));
+        let account = sdk.for_account(Account { api_key: Some("KEY_A".to_string()), partner_id: Some("brand-a".to_string()) });
+
+        sdk.get_transaction_info("tx_1").await.unwrap();
+        account.get_transaction_info("tx_1").await.unwrap();
+
+        let requests = mock.requests();
+        assert_eq!(requests[0].headers["authorization"], "Bearer parent-token");
+        assert!(requests[1].headers.get("authorization").is_none());
+        assert_eq!(requests[1].headers["x-api-key"], "KEY_A");
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::endpoint::parse_result;
+use crate::error::FogswapSdkError;
+use crate::req_structs::CreateTransactionRequest;
+use crate::resp_structs::TransactionInfo;
+use crate::FogswapSdk;
+
+/// Maximum number of `get_transaction_info` calls in flight when the batch endpoint is missing
+const INFO_FAN_OUT_CONCURRENCY: usize = 8;
+
+/// How a batch reacts to a failed item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchMode {
+    /// Attempt every item regardless of failures
+    ContinueOnError,
+    /// Stop dispatching new items after the first failure and report the rest as skipped
+    StopOnError,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// Maximum number of create calls in flight at once
+    pub max_concurrency: usize,
+    pub mode: BatchMode,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self { max_concurrency: 4, mode: BatchMode::ContinueOnError }
+    }
+}
+
+/// Result of one item of a batch, in the same position as its request
+#[derive(Debug)]
+pub enum BatchItem {
+    Created(Box<TransactionInfo>),
+    Failed(anyhow::Error),
+    Skipped,
+}
+
+#[derive(Debug)]
+pub struct BatchOutcome {
+    pub items: Vec<BatchItem>,
+}
+
+/// Summary of a batch suitable for logging or returning to an operator
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatchReport {
+    pub total: usize,
+    pub created: usize,
+    pub failed: Vec<BatchFailure>,
+    pub skipped: Vec<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatchFailure {
+    pub index: usize,
+    pub reason: String,
+}
+
+impl BatchOutcome {
+
+    /// Whether every item was created
+    pub fn is_complete(&self) -> bool {
+        self.items.iter().all(|item| matches!(item, BatchItem::Created(_)))
+    }
+
+    /// Transactions that were created, with their request index
+    pub fn created(&self) -> impl Iterator<Item = (usize, &TransactionInfo)> {
+        self.items.iter().enumerate().filter_map(|(i, item)| match item {
+            BatchItem::Created(tx_info) => Some((i, tx_info.as_ref())),
+            _ => None,
+        })
+    }
+
+    /// Summarize which items failed or were skipped and why
+    pub fn report(&self) -> BatchReport {
+        let mut report = BatchReport {
+            total: self.items.len(),
+            created: 0,
+            failed: Vec::new(),
+            skipped: Vec::new(),
+        };
+        for (index, item) in self.items.iter().enumerate() {
+            match item {
+                BatchItem::Created(_) => report.created += 1,
+                BatchItem::Failed(e) => report.failed.push(BatchFailure { index, reason: e.to_string() }),
+                BatchItem::Skipped => report.skipped.push(index),
+            }
+        }
+        report
+    }
+}
+
+impl FogswapSdk {
+
+    /// Create many transactions with bounded concurrency
+    /// # Arguments
+    /// * `requests` - The transactions to create
+    /// * `options` - Concurrency limit and failure mode
+    /// # Returns
+    /// * `BatchOutcome` - One item per request, in request order
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{BatchMode, BatchOptions, CreateTransactionRequest, FogswapSdk};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let requests = vec![CreateTransactionRequest {
+    ///     network_from: "sol".to_string(),
+    ///     contract_address_from: "SOL".to_string(),
+    ///     network_to: "eth".to_string(),
+    ///     contract_address_to: "ETH".to_string(),
+    ///     amount_from: 0.5,
+    ///     payout_address: "YOUR_RECEIVE_ADDRESS_HERE".to_string(),
+    ///     ..Default::default()
+    /// }];
+    /// let outcome = sdk.create_transactions_batch(
+    ///     requests,
+    ///     BatchOptions { max_concurrency: 8, mode: BatchMode::StopOnError }
+    /// ).await;
+    /// let report = outcome.report();
+    /// println!("{}/{} created", report.created, report.total);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_transactions_batch(
+        &self,
+        requests: Vec<CreateTransactionRequest>,
+        options: BatchOptions
+    ) -> BatchOutcome {
+        let stop = AtomicBool::new(false);
+        let stop = &stop;
+
+        let items = stream::iter(requests)
+            .map(|req| async move {
+                if stop.load(Ordering::SeqCst) {
+                    return BatchItem::Skipped;
+                }
+                match self.create_transaction_with(&req).await {
+                    Ok(tx_info) => BatchItem::Created(Box::new(tx_info)),
+                    Err(e) => {
+                        if options.mode == BatchMode::StopOnError {
+                            stop.store(true, Ordering::SeqCst);
+                        }
+                        BatchItem::Failed(e)
+                    }
+                }
+            })
+            .buffered(options.max_concurrency.max(1))
+            .collect()
+            .await;
+
+        BatchOutcome { items }
+    }
+
+    /// Get the information about many transactions at once
+    ///
+    /// Uses the batch info endpoint, or falls back to `get_transaction_info` calls with
+    /// bounded concurrency when the API does not have it. A 404 or 405 from the batch
+    /// endpoint is remembered, so later calls go straight to the fallback. Any other
+    /// failure of the batch call, e.g. a rate limit or a server error, is returned for
+    /// every id without falling back. Duplicate ids are looked up once.
+    /// # Arguments
+    /// * `ids` - The transaction ids
+    /// # Returns
+    /// * `HashMap<String, Result<TransactionInfo>>` - One entry per distinct id
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    /// use fogswap_sdk_rust::testing::MockTransport;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_transport(Arc::new(MockTransport::new()));
+    /// let infos = sdk.get_transactions_info(&["tx_1", "tx_2"]).await;
+    /// for (id, result) in &infos {
+    ///     match result {
+    ///         Ok(tx_info) => println!("{}: {}", id, tx_info.status),
+    ///         Err(e) => eprintln!("{}: {}", id, e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_transactions_info(&self, ids: &[&str]) -> HashMap<String, Result<TransactionInfo>> {
+        let mut distinct: Vec<&str> = Vec::with_capacity(ids.len());
+        for id in ids {
+            if !distinct.contains(id) {
+                distinct.push(id);
+            }
+        }
+        if distinct.is_empty() {
+            return HashMap::new();
+        }
+
+        if !self.batch_info_missing.load(Ordering::Relaxed) {
+            match self.get_transactions_info_batch(&distinct).await {
+                Ok(infos) => return infos,
+                Err(e) if matches!(e.downcast_ref::<FogswapSdkError>(), Some(FogswapSdkError::Http { status: 404 | 405, .. })) => {
+                    self.batch_info_missing.store(true, Ordering::Relaxed);
+                }
+                // Fanning out during an outage or a rate limit would only multiply the load
+                Err(e) => {
+                    let mut infos: HashMap<String, Result<TransactionInfo>> = distinct[1..].iter()
+                        .map(|id| (id.to_string(), Err(replicate(&e))))
+                        .collect();
+                    infos.insert(distinct[0].to_string(), Err(e));
+                    return infos;
+                }
+            }
+        }
+
+        stream::iter(distinct)
+            .map(|id| async move { (id.to_string(), self.get_transaction_info(id).await) })
+            .buffer_unordered(INFO_FAN_OUT_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    async fn get_transactions_info_batch(&self, ids: &[&str]) -> Result<HashMap<String, Result<TransactionInfo>>> {
+        let endpoint = "/transaction/info/batch";
+
+        let resp = self.send_request(
+            reqwest::Method::POST,
+            endpoint,
+            Some(json!({
+                "tx_ids": ids
+            }))
+        ).await?;
+
+        let tx_infos: Vec<TransactionInfo> = parse_result(endpoint, resp)?;
+        let mut infos: HashMap<String, Result<TransactionInfo>> = HashMap::with_capacity(ids.len());
+        for tx_info in tx_infos {
+            self.track(&tx_info);
+            infos.insert(tx_info.id.clone(), Ok(tx_info));
+        }
+        for id in ids {
+            infos.entry(id.to_string()).or_insert_with(|| Err(FogswapSdkError::Api {
+                endpoint: endpoint.to_string(),
+                code: Some("not_found".to_string()),
+                message: format!("transaction {} not found", id),
+                body: json!(null),
+            }.into()));
+        }
+        Ok(infos)
+    }
+}
+
+/// Copy of `e` for another id; errors other than `FogswapSdkError` keep their message only
+fn replicate(e: &anyhow::Error) -> anyhow::Error {
+    match e.downcast_ref::<FogswapSdkError>() {
+        Some(e) => e.clone().into(),
+        None => anyhow::anyhow!("{:#}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use crate::testing::MockTransport;
+    use crate::{FogswapSdk, TransportResponse};
+
+    #[tokio::test]
+    async fn missing_batch_endpoint_falls_back_to_single_lookups() {
+        for status in [404, 405] {
+            let mock = Arc::new(MockTransport::new());
+            mock.on("/transaction/info/batch", TransportResponse::json(status, &json!({ "message": "no such endpoint" })));
+            let sdk = FogswapSdk::new().with_transport(mock.clone());
+
+            let infos = sdk.get_transactions_info(&["tx_1", "tx_2", "tx_1"]).await;
+            assert_eq!(infos.len(), 2);
+            assert!(infos.values().all(|result| result.is_ok()));
+            let endpoints: Vec<String> = mock.requests().into_iter().map(|r| r.endpoint).collect();
+            assert_eq!(endpoints, vec!["/transaction/info/batch", "/transaction/info", "/transaction/info"]);
+
+            // Remembered: the next call skips the batch endpoint
+            sdk.get_transactions_info(&["tx_1"]).await;
+            let requests = mock.requests();
+            assert_eq!(requests.len(), 4);
+            assert_eq!(requests[3].endpoint, "/transaction/info");
+        }
+    }
+}
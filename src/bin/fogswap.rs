@@ -0,0 +1,175 @@
+//! Command line client of the Fogswap API, built with the `cli` feature
+//!
+//! ```text
+//! fogswap tokens --network eth
+//! fogswap quote --from btc --to xmr --amount 0.1
+//! fogswap create --from btc --to eth:USDT --amount 0.1 --payout 0x...
+//! fogswap status S7ZulO3j16 --watch
+//! ```
+
+use std::process::ExitCode;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use futures::TryStreamExt;
+use serde::Serialize;
+
+use fogswap_sdk_rust::{Asset, CreateTransactionRequest, FogswapSdk, PollOptions, QuoteRequest, TokenListFilter, TxType};
+
+#[derive(Debug, Parser)]
+#[command(name = "fogswap", version, about = "Swap tokens with the Fogswap API")]
+struct Cli {
+    /// Print JSON instead of human-readable text
+    #[arg(long, global = true)]
+    json: bool,
+    /// Partner API key
+    #[arg(long, global = true, env = "FOGSWAP_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// List supported tokens
+    Tokens {
+        /// Only list tokens of this network
+        #[arg(long)]
+        network: Option<String>,
+        /// Match on token symbol or contract address
+        #[arg(long)]
+        search: Option<String>,
+    },
+    /// Quote a swap
+    Quote {
+        /// Token to send, as `network` for the native token or `network:TOKEN`
+        #[arg(long)]
+        from: String,
+        /// Token to receive, same format as `--from`
+        #[arg(long)]
+        to: String,
+        #[arg(long)]
+        amount: f64,
+        /// Quote a private swap
+        #[arg(long)]
+        private: bool,
+    },
+    /// Create a swap and print its deposit instructions
+    Create {
+        #[arg(long)]
+        from: String,
+        #[arg(long)]
+        to: String,
+        #[arg(long)]
+        amount: f64,
+        /// Address receiving the swapped tokens
+        #[arg(long)]
+        payout: String,
+        #[arg(long)]
+        payout_extra_id: Option<String>,
+        /// Address the deposit is returned to if the swap fails
+        #[arg(long)]
+        refund: Option<String>,
+        #[arg(long)]
+        private: bool,
+    },
+    /// Show the status of a transaction
+    Status {
+        id: String,
+        /// Keep printing status changes until the transaction is final
+        #[arg(long)]
+        watch: bool,
+    },
+}
+
+/// `btc` is the native token of `btc`, `eth:USDT` is USDT on `eth`
+fn parse_asset(value: &str) -> Asset {
+    match value.split_once(':') {
+        Some((network, token)) => Asset::new(network, token),
+        None => Asset::native(value),
+    }
+}
+
+fn tx_type(private: bool) -> TxType {
+    if private { TxType::Private } else { TxType::Standard }
+}
+
+fn print<T: Serialize>(json: bool, value: &T, human: impl FnOnce(&T) -> String) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    } else {
+        println!("{}", human(value));
+    }
+    Ok(())
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let mut sdk = FogswapSdk::new();
+    if let Some(key) = &cli.api_key {
+        sdk = sdk.with_api_key(key);
+    }
+
+    match cli.command {
+        Command::Tokens { network, search } => {
+            let filter = TokenListFilter { network, search, ..Default::default() };
+            let networks = sdk.get_token_list_filtered(filter).await?;
+            print(cli.json, &networks, |networks| {
+                networks
+                    .iter()
+                    .flat_map(|network| &network.tokens)
+                    .map(|token| format!("{:<12} {:<10} {}", token.network, token.token, token.contract_address))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+        }
+        Command::Quote { from, to, amount, private } => {
+            let (from, to) = (parse_asset(&from), parse_asset(&to));
+            let mut req = QuoteRequest::new(&from, &to, amount);
+            req.tx_type = Some(tx_type(private));
+            let quote = sdk.get_quote_with(&req).await?;
+            print(cli.json, &quote, |quote| {
+                format!(
+                    "{} {} ({}) -> {} {} ({})",
+                    quote.amount_from, quote.contract_address_from, quote.network_from,
+                    quote.amount_to, quote.contract_address_to, quote.network_to,
+                )
+            })
+        }
+        Command::Create { from, to, amount, payout, payout_extra_id, refund, private } => {
+            let mut builder = CreateTransactionRequest::builder()
+                .assets(&parse_asset(&from), &parse_asset(&to), amount)
+                .payout_address(&payout)
+                .tx_type(tx_type(private));
+            if let Some(extra_id) = &payout_extra_id {
+                builder = builder.payout_extra_id(extra_id);
+            }
+            if let Some(refund) = &refund {
+                builder = builder.refund_address(refund);
+            }
+            let tx = sdk.create_transaction_with(&builder.build()).await?;
+            print(cli.json, &tx, ToString::to_string)
+        }
+        Command::Status { id, watch: false } => {
+            let tx = sdk.get_transaction_info(&id).await?;
+            print(cli.json, &tx, ToString::to_string)
+        }
+        Command::Status { id, watch: true } => {
+            let mut updates = std::pin::pin!(sdk.watch_transaction(&id, PollOptions::default()));
+            while let Some(tx) = updates.try_next().await? {
+                print(cli.json, &tx, |tx| format!("{}: {}", tx.status, tx.status.description()))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use reqwest::blocking::Client;
+use serde_json::{json, Value};
+
+use crate::dedup::{DuplicateGuard, DuplicateProtection};
+use crate::endpoint::parse_result;
+use crate::error::FogswapSdkError;
+use crate::precision::{AmountPrecision, AmountTolerance};
+use crate::req_structs::{Asset, CreateTransactionRequest, QuoteRequest};
+use crate::resp_structs::{QuoteResponse, TokenList, TransactionInfo, TxType};
+use crate::transport::{Transport, TransportResponse};
+
+/// Held by the first of concurrent identical creates until its transaction is known
+type Slot = Arc<Mutex<Option<TransactionInfo>>>;
+
+/// Blocking counterpart of `crate::FogswapSdk`, built on `reqwest::blocking`
+///
+/// Covers the core calls only; retries, mirrors, stores and the other client options
+/// are async-only. Requests are checked and built as by the async client, including
+/// amount precision and duplicate-create protection. Must not be used from within an async runtime.
+/// # Examples
+/// ```no_run
+/// use fogswap_sdk_rust::blocking::FogswapSdk;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let sdk = FogswapSdk::new();
+/// let tokens = sdk.get_token_list()?;
+/// println!("{} networks", tokens.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FogswapSdk {
+    pub base_url: String,
+    pub client: Client,
+    /// Tolerance of the `min_amount_to` check and of duplicate-create protection
+    pub tolerance: AmountTolerance,
+    /// Decimal places of amounts sent in quotes and creates
+    pub precision: AmountPrecision,
+    dedup: Option<Arc<DuplicateGuard<Slot>>>,
+    transport: Option<Arc<dyn Transport>>,
+}
+
+impl Default for FogswapSdk {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FogswapSdk {
+
+    pub fn new() -> Self {
+        Self {
+            base_url: crate::FogswapSdk::BASE_URL.to_string(),
+            client: Client::new(),
+            tolerance: AmountTolerance::default(),
+            precision: AmountPrecision::default(),
+            dedup: None,
+            transport: None,
+        }
+    }
+
+    /// Send requests through `transport` instead of `client`, e.g. a
+    /// [`crate::testing::MockTransport`]; its futures are driven on the calling thread
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Return the transaction created by an identical `create_transaction_with` call
+    /// inside the configured window, as `crate::FogswapSdk::with_duplicate_protection`
+    pub fn with_duplicate_protection(mut self, config: DuplicateProtection) -> Self {
+        self.dedup = Some(Arc::new(DuplicateGuard::new(config)));
+        self
+    }
+
+    fn send_request(&self, req_method: reqwest::Method, endpoint: &str, payload: Option<Value>) -> Result<Value> {
+        let url = format!("{}{}", self.base_url, endpoint);
+        let req = match req_method {
+            reqwest::Method::GET => {
+                let mut req = self.client.get(&url);
+                if let Some(payload) = &payload {
+                    let params: HashMap<&String, &Value> = payload
+                        .as_object()
+                        .into_iter()
+                        .flatten()
+                        .filter(|(_, v)| !v.is_null())
+                        .collect();
+                    req = req.query(&params);
+                }
+                req
+            },
+            reqwest::Method::POST => {
+                let mut req = self.client.post(&url).header("Content-Type", "application/json");
+                if let Some(payload) = &payload {
+                    req = req.json(payload);
+                }
+                req
+            },
+            _ => return Err(FogswapSdkError::UnsupportedMethod.into()),
+        };
+
+        let resp = self.execute(req.build()?)?;
+        if resp.status != 200 {
+            let status = resp.status.as_u16();
+            let body = serde_json::from_slice::<Value>(&resp.body).ok();
+            if status == 503 {
+                let retry_after = resp.headers.get(reqwest::header::RETRY_AFTER).and_then(|value| value.to_str().ok()).map(str::to_string);
+                return Err(FogswapSdkError::service_unavailable(body.as_ref(), retry_after.as_deref()).into());
+            }
+            return Err(FogswapSdkError::Http { endpoint: endpoint.to_string(), status, body }.into());
+        }
+        let text = String::from_utf8_lossy(&resp.body).into_owned();
+        if text.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str::<Value>(&text).map_err(|e| {
+            FogswapSdkError::Decode { endpoint: endpoint.to_string(), reason: e.to_string(), body: Value::String(text.clone()) }.into()
+        })
+    }
+
+    fn execute(&self, req: reqwest::blocking::Request) -> Result<TransportResponse> {
+        let Some(transport) = &self.transport else {
+            let resp = self.client.execute(req).map_err(reqwest::Error::without_url)?;
+            let status = resp.status();
+            let headers = resp.headers().clone();
+            let body = resp.bytes().map_err(reqwest::Error::without_url)?.to_vec();
+            return Ok(TransportResponse { status, headers, body });
+        };
+        let mut async_req = reqwest::Request::new(req.method().clone(), req.url().clone());
+        *async_req.headers_mut() = req.headers().clone();
+        if let Some(body) = req.body().and_then(|body| body.as_bytes()) {
+            *async_req.body_mut() = Some(body.to_vec().into());
+        }
+        futures::executor::block_on(transport.execute(async_req))
+    }
+
+    /// Get the list of available tokens
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the token list is not found
+    pub fn get_token_list(&self) -> Result<Vec<TokenList>> {
+        let endpoint = "/market/tokens";
+        let resp = self.send_request(reqwest::Method::GET, endpoint, None)?;
+        parse_result(endpoint, resp)
+    }
+
+    /// Get the quote for a swap
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the quote for the swap is not found
+    #[deprecated(note = "positional arguments are easy to swap; use `get_quote_for(&from, &to, amount_from)` or `get_quote_with(&QuoteRequest::new(...))`")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_quote(
+        &self,
+        amount_from: f64,
+        network_from: &str,
+        contract_address_from: &str,
+        network_to: &str,
+        contract_address_to: &str,
+        tx_type: Option<TxType>,
+        is_use_xmr: Option<bool>
+    ) -> Result<QuoteResponse> {
+        self.get_quote_with(&QuoteRequest {
+            amount_from,
+            network_from: network_from.to_string(),
+            contract_address_from: contract_address_from.to_string(),
+            network_to: network_to.to_string(),
+            contract_address_to: contract_address_to.to_string(),
+            tx_type,
+            is_use_xmr,
+            ..Default::default()
+        })
+    }
+
+    /// Get the quote for swapping `amount_from` of `from` into `to`
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the quote for the swap is not found
+    pub fn get_quote_for(&self, from: &Asset, to: &Asset, amount_from: f64) -> Result<QuoteResponse> {
+        self.get_quote_with(&QuoteRequest::new(from, to, amount_from))
+    }
+
+    /// Get the quote for a swap from a `QuoteRequest`
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the quote for the swap is not found
+    pub fn get_quote_with(&self, req: &QuoteRequest) -> Result<QuoteResponse> {
+        let req = req.prepared(&self.precision, None, None)?;
+        let endpoint = "/transaction/quote";
+        let resp = self.send_request(reqwest::Method::GET, endpoint, Some(req.params()?))?;
+        parse_result(endpoint, resp)
+    }
+
+    /// Create a new transaction
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the transaction is not created
+    #[deprecated(note = "positional arguments are easy to swap; use `create_transaction_with(&CreateTransactionRequest::builder()...build())`")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_transaction(
+        &self,
+        network_from: &str,
+        contract_address_from: &str,
+        network_to: &str,
+        contract_address_to: &str,
+        amount_from: f64,
+        payout_address: &str,
+        payout_extra_id: &Option<String>,
+        tx_type: Option<TxType>,
+        is_use_xmr: Option<bool>
+    ) -> Result<TransactionInfo> {
+        self.create_transaction_with(&CreateTransactionRequest {
+            network_from: network_from.to_string(),
+            contract_address_from: contract_address_from.to_string(),
+            network_to: network_to.to_string(),
+            contract_address_to: contract_address_to.to_string(),
+            amount_from,
+            payout_address: payout_address.to_string(),
+            payout_extra_id: payout_extra_id.clone(),
+            tx_type,
+            is_use_xmr,
+            ..Default::default()
+        })
+    }
+
+    /// Create a new transaction from a `CreateTransactionRequest`
+    ///
+    /// `min_amount_to` and duplicate-create protection are enforced as by the async client;
+    /// `client_metadata` is ignored.
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the transaction is not created
+    /// * `FogswapSdkError::BelowMinimumReceived` - If a fresh quote is below `min_amount_to`
+    pub fn create_transaction_with(&self, req: &CreateTransactionRequest) -> Result<TransactionInfo> {
+        req.privacy_mode()?;
+        // Keyed before the idempotency key is generated, as by the async client
+        let slot = self.dedup.as_ref().map(|guard| guard.slot(guard.key(req, &self.tolerance), crate::rt::Instant::now()));
+        // Held until the transaction is created, so identical calls wait for it instead
+        // of sending their own; after a failure the next caller tries again
+        let mut held = slot.as_ref().map(|slot| slot.lock().unwrap_or_else(|e| e.into_inner()));
+        if let Some(tx_info) = held.as_deref().and_then(Option::as_ref) {
+            return Ok(tx_info.clone());
+        }
+        if req.min_amount_to.is_some() {
+            let quote = self.get_quote_with(&req.min_received_quote())?;
+            req.check_min_received(quote.amount_to, &self.tolerance)?;
+        }
+
+        let mut req = req.clone();
+        let idempotency_key = req.idempotency_key.get_or_insert_with(|| uuid::Uuid::new_v4().to_string()).clone();
+        let payload = req.payload(&self.precision, None, None);
+
+        let endpoint = "/transaction/create";
+        let resp = self.send_request(reqwest::Method::POST, endpoint, Some(payload))?;
+        let mut tx_info: TransactionInfo = parse_result(endpoint, resp)?;
+        tx_info.idempotency_key.get_or_insert(idempotency_key);
+        if let Some(held) = held.as_deref_mut() {
+            *held = Some(tx_info.clone());
+        }
+        Ok(tx_info)
+    }
+
+    /// Get the information about a transaction
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the transaction information is not found
+    pub fn get_transaction_info(&self, id: &str) -> Result<TransactionInfo> {
+        let endpoint = "/transaction/info";
+        let resp = self.send_request(reqwest::Method::GET, endpoint, Some(json!({ "tx_id": id })))?;
+        parse_result(endpoint, resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::FogswapSdk;
+    use crate::testing::MockTransport;
+    use crate::{CreateTransactionRequest, DuplicateProtection};
+
+    #[test]
+    fn concurrent_identical_creates_send_one_request() {
+        let mock = Arc::new(MockTransport::new());
+        let sdk = FogswapSdk::new()
+            .with_transport(mock.clone())
+            .with_duplicate_protection(DuplicateProtection::default());
+        let req = CreateTransactionRequest {
+            network_from: "sol".to_string(),
+            contract_address_from: "SOL".to_string(),
+            network_to: "eth".to_string(),
+            contract_address_to: "ETH".to_string(),
+            amount_from: 1.0,
+            payout_address: "0x742d35Cc6634C0532925a3b844Bc454e4438f44e".to_string(),
+            ..Default::default()
+        };
+
+        let threads: Vec<_> = (0..2).map(|_| {
+            let (sdk, req) = (sdk.clone(), req.clone());
+            std::thread::spawn(move || sdk.create_transaction_with(&req).unwrap())
+        }).collect();
+        let created: Vec<_> = threads.into_iter().map(|thread| thread.join().unwrap()).collect();
+
+        assert_eq!(created[0].id, created[1].id);
+        assert_eq!(mock.requests().iter().filter(|r| r.endpoint == "/transaction/create").count(), 1);
+    }
+}
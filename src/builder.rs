@@ -0,0 +1,485 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+#[cfg(all(not(target_arch = "wasm32"), any(feature = "rustls", feature = "native-tls")))]
+use reqwest::Certificate;
+use reqwest::Client;
+
+use crate::environment::Environment;
+use crate::error::FogswapSdkError;
+use crate::transport::ProxyConfig;
+use crate::FogswapSdk;
+
+/// HTTP versions the client may speak
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HttpVersion {
+    /// HTTP/1.1, or HTTP/2 when negotiated over TLS with the `http2` feature
+    #[default]
+    Auto,
+    /// Never use HTTP/2
+    Http1Only,
+    /// HTTP/2 without negotiation, multiplexing every request over one connection
+    /// per host; requires the `http2` feature
+    Http2Only,
+}
+
+/// Connection pool settings of the HTTP client
+///
+/// Every clone of a `FogswapSdk` shares the same pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolOptions {
+    /// Idle connections kept open per host; unbounded by default
+    pub max_idle_per_host: Option<usize>,
+    /// How long an idle connection is kept open; 90 seconds by default
+    pub idle_timeout: Option<Duration>,
+    /// Interval of TCP keep-alive probes on open connections; off by default
+    pub tcp_keepalive: Option<Duration>,
+    pub http_version: HttpVersion,
+}
+
+/// TLS implementation of the HTTP client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TlsBackend {
+    /// rustls when the `rustls` feature is enabled, the platform TLS library otherwise
+    #[default]
+    Auto,
+    /// rustls; requires the `rustls` feature
+    Rustls,
+    /// The platform TLS library (OpenSSL, Secure Transport, SChannel); requires the
+    /// `native-tls` feature
+    NativeTls,
+}
+
+/// HTTP client settings reapplied whenever the client is rebuilt
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClientOptions {
+    pub timeout: Option<Duration>,
+    pub user_agent: Option<String>,
+    pub default_headers: HeaderMap,
+    pub pool: PoolOptions,
+    pub tls_backend: TlsBackend,
+    /// The client was passed to `FogswapSdkBuilder::client` and cannot be rebuilt
+    pub external_client: bool,
+    /// Only certificates trusted for the API, replacing the built-in roots
+    #[cfg(all(not(target_arch = "wasm32"), any(feature = "rustls", feature = "native-tls")))]
+    pub pinned_certificates: Vec<Certificate>,
+}
+
+impl ClientOptions {
+
+    pub fn apply(&self, mut builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder> {
+        // Applied per request on wasm, see `send_once`
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if !self.default_headers.is_empty() {
+            builder = builder.default_headers(self.default_headers.clone());
+        }
+        builder = self.apply_tls(builder)?;
+        self.pool.apply(builder)
+    }
+
+    fn is_default(&self) -> bool {
+        self.timeout.is_none()
+            && self.user_agent.is_none()
+            && self.default_headers.is_empty()
+            && self.pool == PoolOptions::default()
+            && self.tls_backend == TlsBackend::Auto
+            && !self.has_pinned_certificates()
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), any(feature = "rustls", feature = "native-tls")))]
+    pub fn has_pinned_certificates(&self) -> bool {
+        !self.pinned_certificates.is_empty()
+    }
+
+    #[cfg(not(all(not(target_arch = "wasm32"), any(feature = "rustls", feature = "native-tls"))))]
+    pub fn has_pinned_certificates(&self) -> bool {
+        false
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn apply_tls(&self, builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder> {
+        let builder = match self.tls_backend {
+            #[cfg(feature = "rustls")]
+            TlsBackend::Auto | TlsBackend::Rustls => builder.use_rustls_tls(),
+            #[cfg(not(feature = "rustls"))]
+            TlsBackend::Auto => builder,
+            #[cfg(feature = "native-tls")]
+            TlsBackend::NativeTls => builder.use_native_tls(),
+            #[allow(unreachable_patterns)]
+            backend => {
+                let e = format!("the {:?} TLS backend requires its cargo feature", backend);
+                return Err(FogswapSdkError::InvalidConfig(e).into());
+            }
+        };
+        #[cfg(any(feature = "rustls", feature = "native-tls"))]
+        let builder = if self.pinned_certificates.is_empty() {
+            builder
+        } else {
+            self.pinned_certificates.iter()
+                .fold(builder.tls_built_in_root_certs(false), |builder, certificate| builder.add_root_certificate(certificate.clone()))
+        };
+        Ok(builder)
+    }
+
+    /// The browser owns TLS
+    #[cfg(target_arch = "wasm32")]
+    fn apply_tls(&self, builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder> {
+        if self.tls_backend != TlsBackend::Auto {
+            let e = "TLS settings are not available on wasm".to_string();
+            return Err(FogswapSdkError::InvalidConfig(e).into());
+        }
+        Ok(builder)
+    }
+}
+
+impl PoolOptions {
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn apply(&self, mut builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder> {
+        if let Some(max_idle) = self.max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(timeout) = self.idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+        match self.http_version {
+            HttpVersion::Auto => (),
+            HttpVersion::Http1Only => builder = builder.http1_only(),
+            #[cfg(feature = "http2")]
+            HttpVersion::Http2Only => builder = builder.http2_prior_knowledge(),
+            #[cfg(not(feature = "http2"))]
+            HttpVersion::Http2Only => {
+                let e = "HTTP/2 requires the `http2` feature".to_string();
+                return Err(FogswapSdkError::InvalidConfig(e).into());
+            }
+        }
+        Ok(builder)
+    }
+
+    /// The browser owns connections
+    #[cfg(target_arch = "wasm32")]
+    fn apply(&self, builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder> {
+        if *self != PoolOptions::default() {
+            let e = "connection pool settings are not available on wasm".to_string();
+            return Err(FogswapSdkError::InvalidConfig(e).into());
+        }
+        Ok(builder)
+    }
+}
+
+/// Configuration of a `FogswapSdk` before it is built
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use fogswap_sdk_rust::FogswapSdk;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let sdk = FogswapSdk::builder()
+///     .base_url("https://staging-api.fogswap.io/v1")
+///     .timeout(Duration::from_secs(20))
+///     .connect_timeout(Duration::from_secs(5))
+///     .user_agent("my-wallet/1.4")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FogswapSdkBuilder {
+    base_url: Option<String>,
+    environment: Option<Environment>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<ProxyConfig>,
+    options: ClientOptions,
+    client: Option<Client>,
+}
+
+impl FogswapSdkBuilder {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// API base URL, e.g. of a staging environment; replaces `environment`
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.trim_end_matches('/').to_string());
+        self.environment = None;
+        self
+    }
+
+    /// Deployment to talk to, production by default; replaces `base_url`
+    pub fn environment(mut self, environment: Environment) -> Self {
+        self.environment = Some(environment);
+        self.base_url = None;
+        self
+    }
+
+    /// Timeout of a whole request; endpoint profiles may set a shorter one
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.options.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Keep at most `max_idle` idle connections per host
+    ///
+    /// Clones of the built client share its pool, so hand clones to your tasks rather
+    /// than building a client per task. With a bounded number of calls in flight the
+    /// pool serves them over a stable set of connections.
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::builder()
+    ///     .pool_max_idle_per_host(32)
+    ///     .pool_idle_timeout(Duration::from_secs(30))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.options.pool.max_idle_per_host = Some(max_idle);
+        self
+    }
+
+    /// Close connections idle for longer than `timeout`
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.options.pool.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Send TCP keep-alive probes every `interval` on open connections
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.options.pool.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Never use HTTP/2
+    pub fn http1_only(mut self) -> Self {
+        self.options.pool.http_version = HttpVersion::Http1Only;
+        self
+    }
+
+    /// Speak HTTP/2 without negotiation, multiplexing requests over one connection per host
+    ///
+    /// Building fails without the `http2` feature.
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.options.pool.http_version = HttpVersion::Http2Only;
+        self
+    }
+
+    /// TLS implementation to use, see [`TlsBackend`]
+    ///
+    /// Building fails when the backend's cargo feature is not enabled.
+    pub fn tls_backend(mut self, backend: TlsBackend) -> Self {
+        self.options.tls_backend = backend;
+        self
+    }
+
+    /// Trust only `certificates` for the API instead of the built-in root certificates
+    ///
+    /// Pin the root or intermediate CA certificate Fogswap's certificate is issued by;
+    /// servers whose chain does not lead to one of them are refused, and calls fail with
+    /// `FogswapSdkError::CertificatePinning`. Requires the `rustls` or `native-tls`
+    /// feature; not available on wasm.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let pem = std::fs::read("fogswap-ca.pem")?;
+    /// let sdk = FogswapSdk::builder()
+    ///     .pin_certificates(reqwest::Certificate::from_pem_bundle(&pem)?)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(not(target_arch = "wasm32"), any(feature = "rustls", feature = "native-tls")))]
+    pub fn pin_certificates(mut self, certificates: Vec<Certificate>) -> Self {
+        self.options.pinned_certificates = certificates;
+        self
+    }
+
+    /// Header sent with every request
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.options.default_headers.insert(name, value);
+        self
+    }
+
+    /// Headers sent with every request, added to those set before
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.options.default_headers.extend(headers);
+        self
+    }
+
+    /// Send every request through `proxy`
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, ProxyConfig};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::builder()
+    ///     .proxy(ProxyConfig::Socks5 {
+    ///         host: "10.0.0.2".to_string(),
+    ///         port: 1080,
+    ///         auth: Some(("user".to_string(), "secret".to_string())),
+    ///     })
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Route every request over the local Tor daemon, see [`ProxyConfig::tor`]
+    pub fn via_tor(self) -> Self {
+        self.proxy(ProxyConfig::tor())
+    }
+
+    /// Use an existing client, e.g. one configured with a custom certificate
+    ///
+    /// The client is used as-is, so it cannot be combined with the other client settings,
+    /// nor rebuilt later by `FogswapSdk::with_transport_options` or `with_proxy`.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Build the configured client
+    /// # Errors
+    /// * `FogswapSdkError::InvalidConfig` - If an existing client is combined with client settings,
+    ///   or the base URL is not a URL
+    /// * If the HTTP client cannot be built
+    pub fn build(self) -> Result<FogswapSdk> {
+        let mut sdk = FogswapSdk::new();
+        if let Some(environment) = self.environment {
+            sdk.base_url = environment.base_url();
+            sdk.environment = environment;
+        } else if let Some(base_url) = self.base_url {
+            sdk.environment = Environment::from_base_url(&base_url)
+                .ok_or_else(|| FogswapSdkError::InvalidConfig(format!("base URL {}", crate::config::redact_url(&base_url))))?;
+            sdk.base_url = base_url;
+        }
+        sdk.transport.connect_timeout = self.connect_timeout;
+        sdk.transport.proxy = self.proxy;
+        let external_client = self.client.is_some();
+        sdk.client = match self.client {
+            Some(_) if self.connect_timeout.is_some() || sdk.transport.proxy.is_some() || !self.options.is_default() => {
+                let e = "timeouts, proxy, user agent, default headers, pool and TLS settings cannot be applied to an existing client";
+                return Err(FogswapSdkError::InvalidConfig(e.to_string()).into());
+            }
+            Some(client) => client,
+            None => self.options.apply(sdk.transport.apply(Client::builder())?)?.build()?,
+        };
+        sdk.client_options = ClientOptions { external_client, ..self.options };
+        Ok(sdk)
+    }
+}
+
+impl FogswapSdk {
+
+    /// Start configuring a client
+    pub fn builder() -> FogswapSdkBuilder {
+        FogswapSdkBuilder::new()
+    }
+
+    /// Deployment this client was built for
+    pub fn environment(&self) -> &Environment {
+        &self.environment
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use crate::testing::MockTransport;
+    use crate::{Asset, FogswapSdk};
+
+    /// Local HTTP/1.1 server answering every request with a quote; returns its URL and
+    /// the number of connections accepted so far
+    async fn quote_server() -> (String, Arc<AtomicUsize>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let body = serde_json::json!({ "result": MockTransport::sample_quote() }).to_string();
+        let accepted = connections.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                accepted.fetch_add(1, Ordering::SeqCst);
+                let body = body.clone();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    while let Ok(n) = socket.read(&mut chunk).await {
+                        if n == 0 {
+                            break;
+                        }
+                        request.extend_from_slice(&chunk[..n]);
+                        while let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                            request.drain(..end + 4);
+                            let response = format!("HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}", body.len(), body);
+                            if socket.write_all(response.as_bytes()).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        (base_url, connections)
+    }
+
+    #[tokio::test]
+    async fn pooled_connections_are_reused_across_tasks() {
+        let (base_url, connections) = quote_server().await;
+        let sdk = FogswapSdk::builder()
+            .base_url(&base_url)
+            .pool_max_idle_per_host(32)
+            .pool_idle_timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        // 1000 quote calls from as many tasks, at most 32 in flight
+        let in_flight = Arc::new(tokio::sync::Semaphore::new(32));
+        let tasks: Vec<_> = (0..1000).map(|_| {
+            let (sdk, in_flight) = (sdk.clone(), in_flight.clone());
+            tokio::spawn(async move {
+                let _permit = in_flight.acquire().await.unwrap();
+                sdk.get_quote_for(&Asset::native("sol"), &Asset::native("eth"), 1.0).await
+            })
+        }).collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        // Every call after the first 32 ran on a pooled connection
+        assert!(connections.load(Ordering::SeqCst) <= 32);
+    }
+}
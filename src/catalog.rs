@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+
+use anyhow::Result;
+use futures::StreamExt;
+
+use crate::estimate::NetworkFee;
+use crate::req_structs::{Pair, TokenListFilter};
+use crate::resp_structs::{PairLimits, TokenInfo, TokenList};
+use crate::rt::Instant;
+use crate::FogswapSdk;
+
+/// What `build_catalog` fetches up front
+#[derive(Debug, Clone)]
+pub struct CatalogOptions {
+    /// Server-side filters of the token list
+    pub filter: TokenListFilter,
+    /// Number of networks requested per token list page
+    pub page_size: u32,
+    /// Pairs whose limits are fetched eagerly; all other pairs are fetched on first use
+    pub prefetch_pairs: Vec<Pair>,
+    /// Maximum number of requests in flight while prefetching
+    pub max_concurrency: usize,
+}
+
+impl Default for CatalogOptions {
+    fn default() -> Self {
+        Self {
+            filter: TokenListFilter::default(),
+            page_size: 50,
+            prefetch_pairs: Vec::new(),
+            max_concurrency: 4,
+        }
+    }
+}
+
+/// Tokens, networks, limits and fees behind a single queryable object
+///
+/// The token list is fetched when the catalog is built and on `refresh()`.
+/// Limits and network fees are cached after their first lookup.
+#[derive(Debug)]
+pub struct TokenCatalog {
+    sdk: FogswapSdk,
+    options: CatalogOptions,
+    networks: RwLock<(Instant, Vec<TokenList>)>,
+    limits: Mutex<HashMap<Pair, PairLimits>>,
+    fees: Mutex<HashMap<(String, String), NetworkFee>>,
+}
+
+impl TokenCatalog {
+
+    /// Names of all networks
+    pub fn networks(&self) -> Vec<String> {
+        let networks = self.networks.read().unwrap_or_else(|e| e.into_inner());
+        networks.1.iter().map(|list| list.network.clone()).collect()
+    }
+
+    /// Image URL of a network
+    pub fn network_image(&self, network: &str) -> Option<String> {
+        let networks = self.networks.read().unwrap_or_else(|e| e.into_inner());
+        networks.1.iter().find(|list| list.network == network).map(|list| list.network_image.clone())
+    }
+
+    /// Tokens of a network, empty if the network is unknown
+    pub fn tokens(&self, network: &str) -> Vec<TokenInfo> {
+        let networks = self.networks.read().unwrap_or_else(|e| e.into_inner());
+        networks.1.iter()
+            .filter(|list| list.network == network)
+            .flat_map(|list| list.tokens.iter().cloned())
+            .collect()
+    }
+
+    /// Look up a token by network and contract address
+    pub fn find_token(&self, network: &str, contract_address: &str) -> Option<TokenInfo> {
+        self.tokens(network).into_iter().find(|t| t.contract_address.eq_ignore_ascii_case(contract_address))
+    }
+
+    /// Tokens of any network whose symbol or contract address contains `query`, case-insensitively
+    pub fn search(&self, query: &str) -> Vec<TokenInfo> {
+        let query = query.to_lowercase();
+        let networks = self.networks.read().unwrap_or_else(|e| e.into_inner());
+        networks.1.iter()
+            .flat_map(|list| list.tokens.iter())
+            .filter(|t| t.token.to_lowercase().contains(&query) || t.contract_address.to_lowercase().contains(&query))
+            .cloned()
+            .collect()
+    }
+
+    /// Time since the token list was last fetched
+    pub fn age(&self) -> std::time::Duration {
+        let fetched_at = self.networks.read().unwrap_or_else(|e| e.into_inner()).0;
+        self.sdk.clock.now().saturating_duration_since(fetched_at)
+    }
+
+    /// Limits of a pair, fetched on first use
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the limits cannot be retrieved
+    pub async fn limits(&self, pair: &Pair) -> Result<PairLimits> {
+        if let Some(limits) = self.limits.lock().unwrap_or_else(|e| e.into_inner()).get(pair) {
+            return Ok(limits.clone());
+        }
+        let limits = self.sdk.get_pair_limits(pair, None).await?;
+        self.limits.lock().unwrap_or_else(|e| e.into_inner()).insert(pair.clone(), limits.clone());
+        Ok(limits)
+    }
+
+    /// Network fee of sending a token, fetched on first use
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the fee cannot be retrieved
+    pub async fn network_fee(&self, network: &str, contract_address: &str) -> Result<NetworkFee> {
+        let key = (network.to_string(), contract_address.to_string());
+        if let Some(fee) = self.fees.lock().unwrap_or_else(|e| e.into_inner()).get(&key) {
+            return Ok(fee.clone());
+        }
+        let fee = self.sdk.get_network_fee(network, contract_address).await?;
+        self.fees.lock().unwrap_or_else(|e| e.into_inner()).insert(key, fee.clone());
+        Ok(fee)
+    }
+
+    /// Re-fetch the token list and drop cached limits and fees
+    ///
+    /// On error the catalog keeps its previous contents.
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the token list cannot be retrieved
+    pub async fn refresh(&self) -> Result<()> {
+        let lists = self.sdk.get_token_list_all_pages(self.options.filter.clone(), self.options.page_size).await?;
+        *self.networks.write().unwrap_or_else(|e| e.into_inner()) = (self.sdk.clock.now(), lists);
+        self.limits.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        self.fees.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        self.prefetch().await;
+        Ok(())
+    }
+
+    /// Fetch the limits of `prefetch_pairs`; failures are left for lazy lookup
+    async fn prefetch(&self) {
+        futures::stream::iter(&self.options.prefetch_pairs)
+            .for_each_concurrent(self.options.max_concurrency.max(1), |pair| async move {
+                let _ = self.limits(pair).await;
+            })
+            .await;
+    }
+}
+
+impl FogswapSdk {
+
+    /// Build a catalog of tokens, networks, limits and fees
+    /// # Arguments
+    /// * `options` - What to fetch up front
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the token list cannot be retrieved
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{CatalogOptions, FogswapSdk, Pair};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let catalog = sdk.build_catalog(CatalogOptions {
+    ///     prefetch_pairs: vec![Pair::new("sol", "SOL", "eth", "ETH")],
+    ///     ..Default::default()
+    /// }).await?;
+    ///
+    /// for network in catalog.networks() {
+    ///     println!("{}: {} tokens", network, catalog.tokens(&network).len());
+    /// }
+    /// let limits = catalog.limits(&Pair::new("sol", "SOL", "eth", "ETH")).await?;
+    /// println!("Minimum: {}", limits.min_amount);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build_catalog(&self, options: CatalogOptions) -> Result<TokenCatalog> {
+        let lists = self.get_token_list_all_pages(options.filter.clone(), options.page_size).await?;
+        let catalog = TokenCatalog {
+            sdk: self.clone(),
+            options,
+            networks: RwLock::new((self.clock.now(), lists)),
+            limits: Mutex::new(HashMap::new()),
+            fees: Mutex::new(HashMap::new()),
+        };
+        catalog.prefetch().await;
+        Ok(catalog)
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use futures::Stream;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::clock::{Clock, SystemClock};
+use crate::error::FogswapSdkError;
+use crate::networks::Network;
+use crate::resp_structs::TransactionInfo;
+
+/// A transfer to a payin address, seen on chain before Fogswap reports it
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepositDetected {
+    pub tx_hash: String,
+    /// Amount received by the payin address, in token units
+    pub amount: f64,
+    /// 0 while in the mempool
+    pub confirmations: u32,
+}
+
+/// Lookup of the transfers received by an address on one network
+///
+/// Implement it for explorers or nodes of networks without a built-in source.
+pub trait DepositSource: Debug + Send + Sync {
+
+    /// Transfers of `token` (symbol or contract address) received by `address`, newest first
+    fn deposits<'a>(&'a self, client: &'a Client, address: &'a str, token: &'a str) -> BoxFuture<'a, Result<Vec<DepositDetected>>>;
+}
+
+/// Esplora REST API of Bitcoin-like networks, as served by Blockstream and mempool.space
+///
+/// Native coin only.
+#[derive(Debug, Clone)]
+pub struct Esplora {
+    base_url: String,
+    decimals: u32,
+}
+
+impl Esplora {
+
+    /// API at `base_url`, e.g. `https://mempool.space/api`, of a coin with 8 decimals
+    pub fn new(base_url: &str) -> Self {
+        Self { base_url: base_url.trim_end_matches('/').to_string(), decimals: 8 }
+    }
+
+    async fn fetch(&self, client: &Client, address: &str) -> Result<Vec<DepositDetected>> {
+        #[derive(Deserialize)]
+        struct Tx { txid: String, status: TxStatus, vout: Vec<Output> }
+        #[derive(Deserialize)]
+        struct TxStatus { confirmed: bool, block_height: Option<u64> }
+        #[derive(Deserialize)]
+        struct Output { scriptpubkey_address: Option<String>, value: u64 }
+
+        let url = format!("{}/address/{}/txs", self.base_url, address);
+        let txs: Vec<Tx> = client.get(&url).send().await?.error_for_status()?.json().await?;
+        let tip: u64 = client.get(format!("{}/blocks/tip/height", self.base_url)).send().await?.error_for_status()?.text().await?.trim().parse()?;
+
+        Ok(txs.into_iter().filter_map(|tx| {
+            let received: u64 = tx.vout.iter()
+                .filter(|output| output.scriptpubkey_address.as_deref() == Some(address))
+                .map(|output| output.value)
+                .sum();
+            let confirmations = match (tx.status.confirmed, tx.status.block_height) {
+                (true, Some(height)) => tip.saturating_sub(height) as u32 + 1,
+                _ => 0,
+            };
+            (received > 0).then(|| DepositDetected {
+                tx_hash: tx.txid,
+                amount: received as f64 / 10f64.powi(self.decimals as i32),
+                confirmations,
+            })
+        }).collect())
+    }
+}
+
+impl DepositSource for Esplora {
+    fn deposits<'a>(&'a self, client: &'a Client, address: &'a str, _token: &'a str) -> BoxFuture<'a, Result<Vec<DepositDetected>>> {
+        Box::pin(self.fetch(client, address))
+    }
+}
+
+/// Etherscan-compatible explorer API of an EVM network, for the native coin and tokens
+#[derive(Debug, Clone)]
+pub struct EtherscanLike {
+    base_url: String,
+    api_key: Option<String>,
+    native_symbol: String,
+}
+
+impl EtherscanLike {
+
+    /// API at `base_url`, e.g. `https://api.etherscan.io/api`, of a network whose coin is `native_symbol`
+    pub fn new(base_url: &str, native_symbol: &str) -> Self {
+        Self { base_url: base_url.to_string(), api_key: None, native_symbol: native_symbol.to_string() }
+    }
+
+    pub fn api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+
+    async fn fetch(&self, client: &Client, address: &str, token: &str) -> Result<Vec<DepositDetected>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Transfer { hash: String, to: String, value: String, confirmations: String, token_decimal: Option<String> }
+
+        let native = token.eq_ignore_ascii_case(&self.native_symbol);
+        let mut query = vec![
+            ("module", "account"),
+            ("action", if native { "txlist" } else { "tokentx" }),
+            ("address", address),
+            ("sort", "desc"),
+        ];
+        if !native {
+            query.push(("contractaddress", token));
+        }
+        if let Some(api_key) = &self.api_key {
+            query.push(("apikey", api_key.as_str()));
+        }
+        let body: Value = client.get(&self.base_url).query(&query).send().await?.error_for_status()?.json().await?;
+        // Errors and "no transactions found" come with a string `result`
+        let transfers: Vec<Transfer> = match body.get("result") {
+            Some(Value::Array(_)) => serde_json::from_value(body["result"].clone())?,
+            Some(Value::String(_)) if body["message"] == "No transactions found" => Vec::new(),
+            _ => {
+                let reason = "explorer returned no transfer list".to_string();
+                return Err(FogswapSdkError::Decode { endpoint: self.base_url.clone(), reason, body }.into());
+            }
+        };
+
+        Ok(transfers.into_iter()
+            .filter(|transfer| transfer.to.eq_ignore_ascii_case(address))
+            .map(|transfer| {
+                let decimals = transfer.token_decimal.as_deref().and_then(|d| d.parse().ok()).unwrap_or(18);
+                DepositDetected {
+                    amount: transfer.value.parse::<f64>().unwrap_or_default() / 10f64.powi(decimals),
+                    confirmations: transfer.confirmations.parse().unwrap_or_default(),
+                    tx_hash: transfer.hash,
+                }
+            })
+            .collect())
+    }
+}
+
+impl DepositSource for EtherscanLike {
+    fn deposits<'a>(&'a self, client: &'a Client, address: &'a str, token: &'a str) -> BoxFuture<'a, Result<Vec<DepositDetected>>> {
+        Box::pin(self.fetch(client, address, token))
+    }
+}
+
+/// Watch payin addresses on chain, to confirm a deposit before Fogswap reports it
+///
+/// Bitcoin and Litecoin are watched through mempool.space and litecoinspace.org by
+/// default; register a [`DepositSource`] for any other network, e.g. an
+/// [`EtherscanLike`] with your API key.
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use futures::TryStreamExt;
+/// use fogswap_sdk_rust::chainwatch::{DepositWatcher, EtherscanLike};
+/// use fogswap_sdk_rust::FogswapSdk;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let sdk = FogswapSdk::new();
+/// let tx_info = sdk.get_transaction_info("S7ZulO3j16").await?;
+/// let watcher = DepositWatcher::new()
+///     .with_source("eth", Arc::new(EtherscanLike::new("https://api.etherscan.io/api", "ETH").api_key("KEY")));
+/// let mut deposits = std::pin::pin!(watcher.watch(&tx_info));
+/// while let Some(deposit) = deposits.try_next().await? {
+///     println!("{} {} seen in {} ({} confirmations)", deposit.amount, tx_info.contract_address_from, deposit.tx_hash, deposit.confirmations);
+///     if deposit.confirmations > 0 {
+///         break;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DepositWatcher {
+    client: Client,
+    sources: HashMap<String, Arc<dyn DepositSource>>,
+    interval: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for DepositWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DepositWatcher {
+
+    pub fn new() -> Self {
+        let mut sources: HashMap<String, Arc<dyn DepositSource>> = HashMap::new();
+        sources.insert(Network::Btc.to_string(), Arc::new(Esplora::new("https://mempool.space/api")));
+        sources.insert(Network::Ltc.to_string(), Arc::new(Esplora::new("https://litecoinspace.org/api")));
+        Self { client: Client::new(), sources, interval: Duration::from_secs(30), clock: Arc::new(SystemClock) }
+    }
+
+    /// Look deposits on `network` up with `source`, replacing any built-in one
+    pub fn with_source(mut self, network: &str, source: Arc<dyn DepositSource>) -> Self {
+        self.sources.insert(network.to_lowercase(), source);
+        self
+    }
+
+    /// Time between two lookups, 30 seconds by default; public explorers rate limit
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Stream deposits to the payin address of `tx_info`, each time one is first seen
+    /// and whenever its confirmations change
+    ///
+    /// The stream never ends on its own; drop it once Fogswap reports the deposit,
+    /// e.g. when `watch_transaction` yields `Confirming`.
+    /// # Errors
+    /// * `FogswapSdkError::InvalidConfig` - If no source is registered for the payin network; the stream then ends
+    /// * Any error of the source; the stream goes on, and polling it again retries after one interval
+    pub fn watch<'a>(&'a self, tx_info: &'a TransactionInfo) -> impl Stream<Item = Result<DepositDetected>> + 'a {
+        let state = Some((HashMap::<String, u32>::new(), VecDeque::<DepositDetected>::new(), false));
+        // `unfold` rather than `try_unfold`, which would end the stream at the first error
+        futures::stream::unfold(state, move |state| async move {
+            let (mut seen, mut pending, mut polled) = state?;
+            let Some(source) = self.sources.get(&tx_info.network_from.to_lowercase()) else {
+                let e = FogswapSdkError::InvalidConfig(format!("no deposit source for network {}", tx_info.network_from));
+                return Some((Err(e.into()), None));
+            };
+            loop {
+                if let Some(deposit) = pending.pop_front() {
+                    return Some((Ok(deposit), Some((seen, pending, polled))));
+                }
+                if polled {
+                    self.clock.sleep(self.interval).await;
+                }
+                polled = true;
+                let mut deposits = match source.deposits(&self.client, &tx_info.payin_address, &tx_info.contract_address_from).await {
+                    Ok(deposits) => deposits,
+                    // Keep what was seen; the next poll waits one interval, then asks again
+                    Err(e) => return Some((Err(e), Some((seen, pending, polled)))),
+                };
+                // Oldest first, so events come in chain order
+                deposits.reverse();
+                for deposit in deposits {
+                    if seen.insert(deposit.tx_hash.clone(), deposit.confirmations) != Some(deposit.confirmations) {
+                        pending.push_back(deposit);
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use anyhow::Result;
+    use futures::future::BoxFuture;
+    use futures::StreamExt;
+    use reqwest::Client;
+
+    use super::{DepositDetected, DepositSource, DepositWatcher};
+    use crate::testing::MockTransport;
+    use crate::TransactionInfo;
+
+    /// Rate limited on the first lookup, then reports one deposit
+    #[derive(Debug, Default)]
+    struct FlakyExplorer {
+        calls: AtomicUsize,
+    }
+
+    impl DepositSource for FlakyExplorer {
+        fn deposits<'a>(&'a self, _client: &'a Client, _address: &'a str, _token: &'a str) -> BoxFuture<'a, Result<Vec<DepositDetected>>> {
+            Box::pin(async move {
+                if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    anyhow::bail!("429 Too Many Requests");
+                }
+                Ok(vec![DepositDetected { tx_hash: "0xabc".to_string(), amount: 1.0, confirmations: 0 }])
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn watch_continues_after_a_source_error() {
+        let tx_info: TransactionInfo = serde_json::from_value(MockTransport::sample_transaction_info()).unwrap();
+        let watcher = DepositWatcher::new()
+            .with_source("sol", Arc::new(FlakyExplorer::default()))
+            .interval(Duration::from_millis(1));
+        let mut deposits = std::pin::pin!(watcher.watch(&tx_info));
+
+        assert!(deposits.next().await.unwrap().is_err());
+        let deposit = deposits.next().await.unwrap().unwrap();
+        assert_eq!(deposit.tx_hash, "0xabc");
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::resp_structs::TransactionInfo;
+use crate::FogswapSdk;
+
+/// Opaque key/value pairs an integrator attaches to a transaction, e.g. its own order id
+///
+/// Never sent to the API; kept in memory and in the configured store.
+pub type ClientMetadata = HashMap<String, String>;
+
+/// A transaction together with the client metadata attached at creation
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrackedTransaction {
+    pub info: TransactionInfo,
+    pub client_metadata: Option<ClientMetadata>,
+}
+
+/// Client metadata of transactions created by this process, keyed by transaction id
+#[derive(Debug, Default)]
+pub(crate) struct MetadataIndex {
+    entries: Mutex<HashMap<String, ClientMetadata>>,
+}
+
+impl MetadataIndex {
+
+    pub fn insert(&self, tx_id: &str, metadata: ClientMetadata) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(tx_id.to_string(), metadata);
+    }
+
+    pub fn get(&self, tx_id: &str) -> Option<ClientMetadata> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.get(tx_id).cloned()
+    }
+}
+
+impl FogswapSdk {
+
+    /// Attach client metadata to a transaction, in memory and in the configured store
+    /// # Errors
+    /// * If the store fails; the metadata is still kept in memory
+    pub fn attach_client_metadata(&self, tx_id: &str, metadata: ClientMetadata) -> Result<()> {
+        self.metadata.insert(tx_id, metadata.clone());
+        if let Some(store) = &self.store {
+            store.save_metadata(tx_id, &metadata)?;
+        }
+        Ok(())
+    }
+
+    /// Client metadata attached to a transaction, if any
+    pub fn client_metadata(&self, tx_id: &str) -> Result<Option<ClientMetadata>> {
+        if let Some(metadata) = self.metadata.get(tx_id) {
+            return Ok(Some(metadata));
+        }
+        match &self.store {
+            Some(store) => store.load_metadata(tx_id),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the information about a transaction together with its client metadata
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{CreateTransactionRequest, FogswapSdk};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tx_info = sdk.create_transaction_with(&CreateTransactionRequest {
+    ///     network_from: "sol".to_string(),
+    ///     contract_address_from: "SOL".to_string(),
+    ///     network_to: "eth".to_string(),
+    ///     contract_address_to: "ETH".to_string(),
+    ///     amount_from: 0.5,
+    ///     payout_address: "YOUR_RECEIVE_ADDRESS_HERE".to_string(),
+    ///     client_metadata: Some([("order_id".to_string(), "A-1042".to_string())].into()),
+    ///     ..Default::default()
+    /// }).await?;
+    ///
+    /// let tracked = sdk.get_tracked_transaction(&tx_info.id).await?;
+    /// println!("{} belongs to {:?}", tracked.info.id, tracked.client_metadata);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_tracked_transaction(&self, id: &str) -> Result<TrackedTransaction> {
+        let info = self.get_transaction_info(id).await?;
+        let client_metadata = self.client_metadata(id)?;
+        Ok(TrackedTransaction { info, client_metadata })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use anyhow::Result;
+
+    use crate::client_metadata::ClientMetadata;
+    use crate::testing::MockTransport;
+    use crate::{CreateTransactionRequest, FogswapSdk, MemoryStore, PendingIntent, TxStore};
+
+    /// Store whose metadata writes always fail
+    #[derive(Debug, Default)]
+    struct MetadataFailingStore(MemoryStore);
+
+    impl TxStore for MetadataFailingStore {
+        fn save_intent(&self, intent: &PendingIntent) -> Result<()> {
+            self.0.save_intent(intent)
+        }
+
+        fn complete_intent(&self, external_id: &str, tx_id: &str) -> Result<()> {
+            self.0.complete_intent(external_id, tx_id)
+        }
+
+        fn discard_intent(&self, external_id: &str) -> Result<()> {
+            self.0.discard_intent(external_id)
+        }
+
+        fn pending_intents(&self) -> Result<Vec<PendingIntent>> {
+            self.0.pending_intents()
+        }
+
+        fn save_metadata(&self, _tx_id: &str, _metadata: &ClientMetadata) -> Result<()> {
+            anyhow::bail!("disk full")
+        }
+    }
+
+    #[tokio::test]
+    async fn create_succeeds_when_the_store_cannot_save_metadata() {
+        let sdk = FogswapSdk::new()
+            .with_transport(Arc::new(MockTransport::new()))
+            .with_store(Arc::new(MetadataFailingStore::default()));
+        let metadata = ClientMetadata::from([("order_id".to_string(), "1042".to_string())]);
+
+        let tx_info = sdk.create_transaction_with(&CreateTransactionRequest {
+            network_from: "sol".to_string(),
+            contract_address_from: "SOL".to_string(),
+            network_to: "eth".to_string(),
+            contract_address_to: "ETH".to_string(),
+            amount_from: 1.0,
+            payout_address: "0xpayout".to_string(),
+            client_metadata: Some(metadata.clone()),
+            ..Default::default()
+        }).await.unwrap();
+
+        assert_eq!(sdk.client_metadata(&tx_info.id).unwrap(), Some(metadata));
+        assert!(sdk.attach_client_metadata(&tx_info.id, ClientMetadata::new()).is_err());
+    }
+}
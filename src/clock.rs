@@ -0,0 +1,166 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use tokio::sync::oneshot;
+
+use crate::rt::{Instant, SystemTime, UNIX_EPOCH};
+use crate::FogswapSdk;
+
+/// Source of time for expiry checks, cache staleness, backoff, polling and scheduling
+///
+/// The default [`SystemClock`] reads the system clock and sleeps on the tokio timer;
+/// [`MockClock`] lets tests move time forward by hand. Latencies reported in metrics
+/// and response metadata always use the real clock.
+pub trait Clock: Debug + Send + Sync {
+
+    /// Monotonic time
+    fn now(&self) -> Instant;
+
+    /// Wall-clock time
+    fn system_time(&self) -> SystemTime;
+
+    /// Complete once `duration` has passed on this clock
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+
+    /// Complete once this clock reaches `deadline`
+    fn sleep_until(&self, deadline: Instant) -> BoxFuture<'static, ()> {
+        self.sleep(deadline.saturating_duration_since(self.now()))
+    }
+
+    /// Seconds since the Unix epoch, as used by API timestamps
+    fn unix_time(&self) -> i64 {
+        self.system_time().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64
+    }
+}
+
+/// The system clock and the tokio timer
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        crate::rt::sleep(duration)
+    }
+}
+
+/// Clock that only moves when told to, for deterministic tests
+///
+/// Sleeps complete as soon as `advance` or `set` moves the clock past their deadline.
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use fogswap_sdk_rust::{Clock, FogswapSdk, MockClock};
+///
+/// # async fn example() {
+/// let clock = Arc::new(MockClock::new());
+/// let sdk = FogswapSdk::new().with_clock(clock.clone());
+///
+/// let started = clock.now();
+/// let sleep = tokio::spawn(clock.sleep(Duration::from_secs(60)));
+/// clock.advance(Duration::from_secs(60));
+/// sleep.await.unwrap();
+/// assert_eq!(clock.now() - started, Duration::from_secs(60));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MockClock {
+    start: Instant,
+    start_system: SystemTime,
+    state: Mutex<MockState>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    elapsed: Duration,
+    sleepers: Vec<(Duration, oneshot::Sender<()>)>,
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockClock {
+
+    /// Clock starting at the current time
+    pub fn new() -> Self {
+        Self::starting_at(SystemTime::now())
+    }
+
+    /// Clock whose wall-clock time starts at `system_time`
+    pub fn starting_at(system_time: SystemTime) -> Self {
+        Self { start: Instant::now(), start_system: system_time, state: Mutex::default() }
+    }
+
+    /// Move the clock forward by `duration`, waking every sleep that is due
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.elapsed = state.elapsed.saturating_add(duration);
+        let elapsed = state.elapsed;
+        let (due, pending) = std::mem::take(&mut state.sleepers).into_iter().partition(|(until, _)| *until <= elapsed);
+        state.sleepers = pending;
+        for (_, wake) in due {
+            // The sleep may have been dropped
+            let _ = wake.send(());
+        }
+    }
+
+    /// Time passed since the clock was created
+    pub fn elapsed(&self) -> Duration {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).elapsed
+    }
+}
+
+impl Clock for MockClock {
+
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        self.start_system + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if duration.is_zero() {
+            return Box::pin(std::future::ready(()));
+        }
+        let (wake, woken) = oneshot::channel();
+        let until = state.elapsed.saturating_add(duration);
+        state.sleepers.push((until, wake));
+        Box::pin(async move {
+            let _ = woken.await;
+        })
+    }
+}
+
+impl FogswapSdk {
+
+    /// Read time from `clock` in polling, caches, quote expiry, retries and request scheduling
+    ///
+    /// Set it before `with_request_queue` or `with_rate_limit`, which start from the clock's
+    /// current time.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Clock used by this client
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+}
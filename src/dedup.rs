@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::sync::OnceCell;
+
+use crate::precision::AmountTolerance;
+use crate::req_structs::CreateTransactionRequest;
+use crate::resp_structs::TransactionInfo;
+use crate::rt::Instant;
+
+/// Which `create_transaction` parameters make two calls duplicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DedupKeying {
+    /// Every parameter of the request as given must match; the `idempotency_key` and
+    /// `external_id` the SDK generates when missing are not compared
+    AllParams,
+    /// Only the pair, the amount and the payout address must match
+    PairAmountPayout,
+}
+
+/// Duplicate-create protection settings
+#[derive(Debug, Clone)]
+pub struct DuplicateProtection {
+    /// How long a created transaction is returned for identical calls
+    pub window: Duration,
+    pub keying: DedupKeying,
+}
+
+impl Default for DuplicateProtection {
+    fn default() -> Self {
+        Self { window: Duration::from_secs(30), keying: DedupKeying::AllParams }
+    }
+}
+
+/// Slot of the async client, filled once by the first of concurrent identical calls
+pub(crate) type Slot = Arc<OnceCell<TransactionInfo>>;
+
+/// Tracks in-flight and recently created transactions
+///
+/// `S` is the slot shared by identical calls: a `OnceCell` for the async client, a
+/// mutex held across the request for the blocking one.
+#[derive(Debug)]
+pub(crate) struct DuplicateGuard<S = Slot> {
+    config: DuplicateProtection,
+    recent: Mutex<HashMap<String, (Instant, S)>>,
+}
+
+impl<S: Clone + Default> DuplicateGuard<S> {
+
+    pub fn new(config: DuplicateProtection) -> Self {
+        Self { config, recent: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn config(&self) -> &DuplicateProtection {
+        &self.config
+    }
+
+    /// Return the slot shared by every call with the same `key` inside the window
+    pub fn slot(&self, key: String, now: Instant) -> S {
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        recent.retain(|_, (at, _)| now.saturating_duration_since(*at) < self.config.window);
+        recent
+            .entry(key)
+            .or_insert_with(|| (now, S::default()))
+            .1
+            .clone()
+    }
+
+    /// Key of `k`, amounts matching within `tolerance`
+    ///
+    /// Computed on the request as the caller built it, before the SDK fills in generated ids.
+    pub fn key(&self, k: &CreateTransactionRequest, tolerance: &AmountTolerance) -> String {
+        match self.config.keying {
+            // The serialized request, so that fields added later are part of the key
+            DedupKeying::AllParams => match serde_json::to_value(k) {
+                Ok(Value::Object(mut fields)) => {
+                    fields.insert("amount_from".to_string(), tolerance.key(k.amount_from).into());
+                    fields.insert("min_amount_to".to_string(), k.min_amount_to.map(|min| tolerance.key(min)).into());
+                    Value::Object(fields).to_string()
+                }
+                _ => format!("{:?}", k),
+            },
+            DedupKeying::PairAmountPayout => format!(
+                "{}|{}|{}|{}|{}|{}",
+                k.network_from, k.contract_address_from, k.network_to, k.contract_address_to,
+                tolerance.key(k.amount_from), k.payout_address
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::MockTransport;
+    use crate::{CreateTransactionRequest, DuplicateProtection, FogswapSdk};
+
+    fn request(refund_address: &str) -> CreateTransactionRequest {
+        CreateTransactionRequest {
+            network_from: "sol".to_string(),
+            contract_address_from: "SOL".to_string(),
+            network_to: "eth".to_string(),
+            contract_address_to: "ETH".to_string(),
+            amount_from: 1.0,
+            payout_address: "0x742d35Cc6634C0532925a3b844Bc454e4438f44e".to_string(),
+            refund_address: Some(refund_address.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn creates_differing_only_in_refund_address_are_both_sent() {
+        let mock = Arc::new(MockTransport::new());
+        let sdk = FogswapSdk::new()
+            .with_transport(mock.clone())
+            .with_duplicate_protection(DuplicateProtection::default());
+
+        sdk.create_transaction_with(&request("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU")).await.unwrap();
+        sdk.create_transaction_with(&request("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")).await.unwrap();
+        // Same request again, answered from the window
+        sdk.create_transaction_with(&request("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")).await.unwrap();
+
+        let creates: Vec<_> = mock.requests().into_iter().filter(|r| r.endpoint == "/transaction/create").collect();
+        assert_eq!(creates.len(), 2);
+        assert_eq!(creates[0].body.as_ref().unwrap()["refund_address"], "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU");
+        assert_eq!(creates[1].body.as_ref().unwrap()["refund_address"], "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+    }
+}
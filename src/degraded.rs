@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::rates::RateInfo;
+use crate::req_structs::{Asset, Pair};
+use crate::resp_structs::TokenList;
+use crate::rt::Instant;
+use crate::FogswapSdk;
+
+/// How current a value returned in degraded mode is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFreshness {
+    /// Fetched from the API by this call
+    Fresh,
+    /// Served from the last successful response because the API call failed
+    Stale { age: Duration },
+}
+
+/// A value tagged with its freshness
+#[derive(Debug, Clone)]
+pub struct MaybeStale<T> {
+    pub data: T,
+    pub freshness: DataFreshness,
+}
+
+impl<T> MaybeStale<T> {
+    pub fn is_stale(&self) -> bool {
+        matches!(self.freshness, DataFreshness::Stale { .. })
+    }
+}
+
+/// Last successful response per cache key, used when the API is down
+#[derive(Debug)]
+pub(crate) struct LastGoodCache {
+    /// Cached values older than this are not served
+    max_age: Option<Duration>,
+    entries: Mutex<HashMap<String, (Instant, Value)>>,
+}
+
+impl LastGoodCache {
+
+    pub fn new(max_age: Option<Duration>) -> Self {
+        Self { max_age, entries: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    pub fn put(&self, key: &str, value: Value, now: Instant) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(key.to_string(), (now, value));
+    }
+
+    pub fn get(&self, key: &str, now: Instant) -> Option<(Duration, Value)> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let (at, value) = entries.get(key)?;
+        let age = now.saturating_duration_since(*at);
+        match self.max_age {
+            Some(max_age) if age > max_age => None,
+            _ => Some((age, value.clone())),
+        }
+    }
+}
+
+impl FogswapSdk {
+
+    /// Get the list of available tokens, falling back to the last successful
+    /// response when the API call fails and degraded mode is enabled
+    /// # Returns
+    /// * `MaybeStale<Vec<TokenList>>` - The token list tagged with its freshness
+    /// # Errors
+    /// * The original error if degraded mode is disabled or nothing usable is cached
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_degraded_mode(Some(Duration::from_secs(3600)));
+    /// let tokens = sdk.get_token_list_with_freshness().await?;
+    /// if tokens.is_stale() {
+    ///     println!("Showing cached token list: {:?}", tokens.freshness);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_token_list_with_freshness(&self) -> Result<MaybeStale<Vec<TokenList>>> {
+        match self.get_token_list().await {
+            Ok(data) => Ok(MaybeStale { data, freshness: DataFreshness::Fresh }),
+            Err(e) => match self.stale(TOKEN_LIST_KEY) {
+                Some(stale) => Ok(stale),
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Get the current market rates of `pairs`, falling back to the last rate
+    /// received for each pair when the API call fails and degraded mode is enabled
+    /// # Returns
+    /// * `MaybeStale<Vec<RateInfo>>` - The rates, stale with the age of the oldest one
+    /// # Errors
+    /// * The original error if degraded mode is disabled or a pair has no usable cached rate
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::{FogswapSdk, Pair};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_degraded_mode(Some(Duration::from_secs(300)));
+    /// let rates = sdk.get_rates_with_freshness(&[Pair::new("btc", "BTC", "xmr", "XMR")]).await?;
+    /// if rates.is_stale() {
+    ///     println!("Rates from {:?} ago", rates.freshness);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_rates_with_freshness(&self, pairs: &[Pair]) -> Result<MaybeStale<Vec<RateInfo>>> {
+        let e = match self.get_rates(pairs).await {
+            Ok(data) => return Ok(MaybeStale { data, freshness: DataFreshness::Fresh }),
+            Err(e) => e,
+        };
+        let mut data = Vec::with_capacity(pairs.len());
+        let mut oldest = Duration::ZERO;
+        for pair in pairs {
+            match self.stale::<RateInfo>(&rate_key(pair)) {
+                Some(MaybeStale { data: rate, freshness: DataFreshness::Stale { age } }) => {
+                    oldest = oldest.max(age);
+                    data.push(rate);
+                }
+                _ => return Err(e),
+            }
+        }
+        Ok(MaybeStale { data, freshness: DataFreshness::Stale { age: oldest } })
+    }
+
+    /// Get the current USD price of one unit of `asset`, falling back to the last
+    /// price received when the API call fails and degraded mode is enabled
+    /// # Errors
+    /// * The original error if degraded mode is disabled or nothing usable is cached
+    pub async fn get_usd_price_with_freshness(&self, asset: &Asset) -> Result<MaybeStale<f64>> {
+        match self.get_usd_price(asset).await {
+            Ok(data) => Ok(MaybeStale { data, freshness: DataFreshness::Fresh }),
+            Err(e) => self.stale(&price_key(asset)).ok_or(e),
+        }
+    }
+
+    pub(crate) fn remember(&self, key: &str, value: &impl serde::Serialize) {
+        if let Some(cache) = &self.last_good
+            && let Ok(value) = serde_json::to_value(value) {
+            cache.put(key, value, self.clock.now());
+        }
+    }
+
+    pub(crate) fn stale<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<MaybeStale<T>> {
+        let (age, value) = self.last_good.as_ref()?.get(key, self.clock.now())?;
+        let data = serde_json::from_value(value).ok()?;
+        Some(MaybeStale { data, freshness: DataFreshness::Stale { age } })
+    }
+}
+
+pub(crate) const TOKEN_LIST_KEY: &str = "market/tokens";
+
+pub(crate) fn rate_key(pair: &Pair) -> String {
+    format!("market/rates/{}:{}/{}:{}", pair.network_from, pair.contract_address_from, pair.network_to, pair.contract_address_to)
+}
+
+pub(crate) fn price_key(asset: &Asset) -> String {
+    format!("market/price/{}:{}", asset.network, asset.contract_address)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::testing::MockTransport;
+    use crate::{Asset, DataFreshness, FogswapSdk, MockClock, Pair, TransportResponse};
+
+    #[tokio::test]
+    async fn rates_and_prices_are_served_stale_while_the_api_fails() {
+        let clock = Arc::new(MockClock::new());
+        let mock = Arc::new(MockTransport::new());
+        let sdk = FogswapSdk::new()
+            .with_transport(mock.clone())
+            .with_clock(clock.clone())
+            .with_degraded_mode(Some(Duration::from_secs(300)));
+        let pairs = [Pair::new("sol", "SOL", "eth", "ETH")];
+        let sol = Asset::native("sol");
+
+        assert_eq!(sdk.get_rates_with_freshness(&pairs).await.unwrap().freshness, DataFreshness::Fresh);
+        assert_eq!(sdk.get_usd_price_with_freshness(&sol).await.unwrap().freshness, DataFreshness::Fresh);
+
+        mock.on("/market/rates", TransportResponse::api_error("internal_error", "down"));
+        mock.on("/market/price", TransportResponse::api_error("internal_error", "down"));
+        clock.advance(Duration::from_secs(60));
+        let rates = sdk.get_rates_with_freshness(&pairs).await.unwrap();
+        assert_eq!(rates.freshness, DataFreshness::Stale { age: Duration::from_secs(60) });
+        assert_eq!(rates.data[0].rate, 0.05);
+        let price = sdk.get_usd_price_with_freshness(&sol).await.unwrap();
+        assert_eq!(price.freshness, DataFreshness::Stale { age: Duration::from_secs(60) });
+        assert_eq!(price.data, 150.0);
+
+        // No cached rate for this pair, and nothing older than max_age is served
+        assert!(sdk.get_rates_with_freshness(&[Pair::new("btc", "BTC", "xmr", "XMR")]).await.is_err());
+        clock.advance(Duration::from_secs(300));
+        assert!(sdk.get_rates_with_freshness(&pairs).await.is_err());
+        assert!(sdk.get_usd_price_with_freshness(&sol).await.is_err());
+    }
+}
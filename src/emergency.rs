@@ -0,0 +1,68 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::endpoint::parse_result;
+use crate::resp_structs::TransactionInfo;
+use crate::FogswapSdk;
+
+/// How to resolve a transaction put on hold, see `TransactionInfo::emergency`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmergencyAction {
+    /// Return the deposit to `address` on the source network
+    Refund { address: String, extra_id: Option<String> },
+    /// Go on with the swap, e.g. at the rate of the amount actually deposited
+    Continue,
+}
+
+impl EmergencyAction {
+
+    /// Refund to an address without extra id
+    pub fn refund(address: &str) -> Self {
+        EmergencyAction::Refund { address: address.to_string(), extra_id: None }
+    }
+
+    fn params(&self, tx_id: &str) -> Value {
+        match self {
+            EmergencyAction::Refund { address, extra_id } => json!({
+                "tx_id": tx_id,
+                "choice": "refund",
+                "address": address,
+                "extra_id": extra_id,
+            }),
+            EmergencyAction::Continue => json!({ "tx_id": tx_id, "choice": "continue" }),
+        }
+    }
+}
+
+impl FogswapSdk {
+
+    /// Choose how to resolve a transaction on hold, after a deposit amount mismatch or a KYT flag
+    /// # Arguments
+    /// * `tx_id` - The id of the transaction
+    /// * `action` - Refund the deposit or continue the swap
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the transaction is not on hold or the action is refused
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{EmergencyAction, FogswapSdk};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tx_info = sdk.get_transaction_info("S7ZulO3j16").await?;
+    /// if let Some(emergency) = &tx_info.emergency {
+    ///     println!("On hold: {}", emergency.reason);
+    ///     sdk.set_emergency_action(&tx_info.id, EmergencyAction::refund("YOUR_REFUND_ADDRESS")).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_emergency_action(&self, tx_id: &str, action: EmergencyAction) -> Result<TransactionInfo> {
+        let endpoint = "/transaction/emergency";
+
+        let resp = self.send_request(reqwest::Method::POST, endpoint, Some(action.params(tx_id))).await?;
+
+        let tx_info = parse_result(endpoint, resp)?;
+        self.track(&tx_info);
+        Ok(tx_info)
+    }
+}
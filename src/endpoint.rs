@@ -0,0 +1,112 @@
+use anyhow::Result;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::FogswapSdkError;
+use crate::meta::WithMeta;
+use crate::resp_structs::ApiResponse;
+use crate::FogswapSdk;
+
+/// A typed Fogswap API endpoint
+///
+/// Implement this for endpoints the SDK does not wrap yet and call them with
+/// [`FogswapSdk::execute`], which reuses the SDK transport and envelope handling.
+/// # Examples
+/// ```
+/// use reqwest::Method;
+/// use serde::Deserialize;
+/// use serde_json::{json, Value};
+/// use fogswap_sdk_rust::{Endpoint, FogswapSdk};
+///
+/// struct NetworkFee { network: String }
+///
+/// #[derive(Deserialize)]
+/// struct NetworkFeeResponse { fee: f64 }
+///
+/// impl Endpoint for NetworkFee {
+///     type Response = NetworkFeeResponse;
+///     fn method(&self) -> Method { Method::GET }
+///     fn path(&self) -> String { "/market/network_fee".to_string() }
+///     fn params(&self) -> Option<Value> { Some(json!({ "network": self.network })) }
+/// }
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let sdk = FogswapSdk::new();
+/// let fee = sdk.execute(&NetworkFee { network: "eth".to_string() }).await?;
+/// println!("Fee: {}", fee.fee);
+/// # Ok(())
+/// # }
+/// ```
+pub trait Endpoint {
+    /// Type of `result`; `Ack` for endpoints that only acknowledge
+    type Response: DeserializeOwned;
+
+    /// HTTP method, `GET` or `POST`
+    fn method(&self) -> Method;
+
+    /// Path relative to the base URL, e.g. `/market/tokens`
+    fn path(&self) -> String;
+
+    /// Query parameters for `GET`, JSON body for `POST`
+    fn params(&self) -> Option<Value> {
+        None
+    }
+}
+
+impl FogswapSdk {
+
+    /// Call a user-defined endpoint
+    /// # Arguments
+    /// * `req` - The endpoint and its parameters
+    /// # Returns
+    /// * `E::Response` - The `result` of the response envelope
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the API returns an error
+    pub async fn execute<E: Endpoint>(&self, req: &E) -> Result<E::Response> {
+        Ok(self.execute_with_meta(req).await?.data)
+    }
+
+    /// Call a user-defined endpoint and keep the response metadata
+    pub async fn execute_with_meta<E: Endpoint>(&self, req: &E) -> Result<WithMeta<E::Response>> {
+        let endpoint = req.path();
+        let (resp, meta) = self.send_request_meta(req.method(), &endpoint, req.params()).await?;
+        let data = parse_result(&endpoint, resp)?;
+        Ok(WithMeta { data, meta })
+    }
+}
+
+/// Extract `result` from a response envelope, or turn its `error` into `FogswapSdkError::Api`
+///
+/// An empty body or a null or missing `result` is accepted when `T` accepts null,
+/// such as `Ack`, `()` or `Option<_>`. Other responses that are not an envelope, or
+/// whose `result` is not a `T`, become `FogswapSdkError::Decode`.
+pub(crate) fn parse_result<T: DeserializeOwned>(endpoint: &str, resp: Value) -> Result<T> {
+    let decode = |reason: String, body: Value| -> anyhow::Error {
+        FogswapSdkError::Decode { endpoint: endpoint.to_string(), reason, body }.into()
+    };
+    let envelope = match &resp {
+        Value::Null => ApiResponse { result: None, error: None },
+        resp => match ApiResponse::<T>::deserialize(resp) {
+            Ok(envelope) => envelope,
+            Err(e) => return Err(decode(e.to_string(), resp.clone())),
+        },
+    };
+
+    if let Some(e) = envelope.error {
+        let code = e.code.filter(|c| !c.is_null()).map(|c| match c {
+            Value::String(code) => code,
+            code => code.to_string(),
+        });
+        if code.as_deref() == Some("maintenance") {
+            return Err(FogswapSdkError::service_unavailable(Some(&resp), None).into());
+        }
+        return Err(FogswapSdkError::Api { endpoint: endpoint.to_string(), code, message: e.message, body: resp }.into());
+    }
+    match envelope.result {
+        Some(result) => Ok(result),
+        // `T` may accept a null or missing result, e.g. `Option<_>`
+        None => T::deserialize(Value::Null).map_err(|_| decode("missing result".to_string(), resp)),
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+
+use reqwest::Url;
+
+/// Base URL of the production API
+pub const PRODUCTION_BASE_URL: &str = "https://api.fogswap.io/v1";
+
+/// Base URL of the sandbox API: no funds move, and transactions advance only through
+/// the `sandbox_*` helpers
+pub const SANDBOX_BASE_URL: &str = "https://sandbox-api.fogswap.io/v1";
+
+/// Fogswap deployment a client talks to
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{Environment, FogswapSdk, SANDBOX_BASE_URL};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let sdk = FogswapSdk::builder().environment(Environment::Sandbox).build()?;
+/// assert_eq!(sdk.base_url, SANDBOX_BASE_URL);
+/// assert_eq!(sdk.environment(), &Environment::Sandbox);
+///
+/// let local = Environment::Custom("http://localhost:8080/v1".parse()?);
+/// let sdk = FogswapSdk::builder().environment(local).build()?;
+/// assert!(!sdk.environment().is_production());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Environment {
+    #[default]
+    Production,
+    Sandbox,
+    /// Any other deployment, e.g. a local mock server
+    Custom(Url),
+}
+
+impl Environment {
+
+    /// API base URL, without trailing slash
+    pub fn base_url(&self) -> String {
+        match self {
+            Environment::Production => PRODUCTION_BASE_URL.to_string(),
+            Environment::Sandbox => SANDBOX_BASE_URL.to_string(),
+            Environment::Custom(url) => url.as_str().trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn is_production(&self) -> bool {
+        *self == Environment::Production
+    }
+
+    /// Environment served at `base_url`; unknown or unparsable URLs are `None`
+    pub fn from_base_url(base_url: &str) -> Option<Self> {
+        match base_url.trim_end_matches('/') {
+            PRODUCTION_BASE_URL => Some(Environment::Production),
+            SANDBOX_BASE_URL => Some(Environment::Sandbox),
+            other => Url::parse(other).ok().map(Environment::Custom),
+        }
+    }
+}
+
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Environment::Production => write!(f, "production"),
+            Environment::Sandbox => write!(f, "sandbox"),
+            Environment::Custom(url) => write!(f, "{}", url),
+        }
+    }
+}
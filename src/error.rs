@@ -1,25 +1,108 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use thiserror::Error;
 
-#[derive(Debug, Serialize, Deserialize, Error)]
+use crate::resp_structs::TransactionInfo;
+use crate::status::TransactionStatus;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Error)]
 pub enum FogswapSdkError {
     
     #[error("Unsupported method")]
     UnsupportedMethod,
 
-    #[error("send request error")]
-    SendRequestError,
+    #[error("Http Error : {endpoint} returned status {status}")]
+    Http { endpoint: String, status: u16, body: Option<Value> },
+
+    #[error("Api Error : {endpoint} failed with {message}")]
+    Api { endpoint: String, code: Option<String>, message: String, body: Value },
+
+    #[error("Decode Error : unexpected response from {endpoint} : {reason}")]
+    Decode { endpoint: String, reason: String, body: Value },
+
+    #[error("Rate Limited : retry after {retry_after:?}")]
+    RateLimited { retry_after: Option<std::time::Duration> },
+
+    #[error("Offline : the API is unreachable")]
+    Offline,
+
+    #[error("Shutdown : the client was shut down")]
+    Shutdown,
+
+    #[error("Cancelled : the operation was cancelled")]
+    Cancelled,
+
+    #[error("Invalid Config : {0}")]
+    InvalidConfig(String),
+
+    #[error("Auth Error : {0}")]
+    AuthError(String),
+
+    #[error("Certificate Pinning : {0} presented no pinned certificate")]
+    CertificatePinning(String),
+
+    #[error("Insecure Url : {0} is not HTTPS")]
+    InsecureUrl(String),
+
+    #[error("Invalid Request : {0}")]
+    InvalidRequest(String),
+
+    #[error("Amount Out Of Limits : {0}")]
+    AmountOutOfLimits(String),
+
+    #[error("No Route : {0}")]
+    NoRoute(String),
+
+    #[error("Not Final : transaction {id} is still {status}")]
+    NotFinal { id: String, status: TransactionStatus },
+
+    #[error("Invalid Address : {0}")]
+    InvalidAddress(String),
+
+    #[error("Invalid Signature : {0}")]
+    InvalidSignature(String),
+
+    #[error("Slippage Exceeded : expected {expected}, got {actual}")]
+    SlippageExceeded { expected: f64, actual: f64 },
+
+    #[error("Value Loss Exceeded : sending ${usd_from}, receiving ${usd_to}")]
+    ValueLossExceeded { usd_from: f64, usd_to: f64 },
+
+    #[error("Below Minimum Received : quoted {quoted}, minimum {min_amount_to}")]
+    BelowMinimumReceived { min_amount_to: f64, quoted: f64 },
+
+    #[error("Quote Expired : still expired after {requotes} re-quotes")]
+    QuoteExpired { requotes: u32 },
+
+    #[error("Deadline Exceeded")]
+    DeadlineExceeded { last_known: Option<Box<TransactionInfo>> },
 
-    #[error("Get Available Coins Error : {0}")]
-    GetAvailableCoinsError(String),
+    #[error("Timeout : {endpoint} did not answer within {after:?}")]
+    Timeout { endpoint: String, after: std::time::Duration },
 
-    #[error("Get Estimated Exchange Amount Error : {0}")]
-    GetEstimatedExchangeAmountError(String),
+    /// The API is down for maintenance or overloaded; `estimated_return` is a Unix time
+    #[error("Service Unavailable : {message}")]
+    ServiceUnavailable { message: String, estimated_return: Option<i64> },
 
-    #[error("Create Transaction Error : {0}")]
-    CreateTransactionError(String),
+}
 
-    #[error("Get Transaction Info Error : {0}")]
-    GetTransactionInfoError(String),
+impl FogswapSdkError {
 
+    /// `ServiceUnavailable` from a `503` or maintenance answer
+    ///
+    /// The message and return time are read from the body, at the top level or under
+    /// `error`, falling back to the `Retry-After` header in seconds.
+    pub(crate) fn service_unavailable(body: Option<&Value>, retry_after: Option<&str>) -> Self {
+        let field = |name: &str| body.and_then(|body| body.get(name).or_else(|| body.get("error")?.get(name)));
+        let message = field("message")
+            .and_then(Value::as_str)
+            .unwrap_or("the service is temporarily unavailable")
+            .to_string();
+        let estimated_return = field("estimated_return").and_then(Value::as_i64).or_else(|| {
+            let secs = retry_after?.trim().parse::<i64>().ok()?;
+            let now = crate::rt::SystemTime::now().duration_since(crate::rt::UNIX_EPOCH).ok()?;
+            Some(now.as_secs() as i64 + secs)
+        });
+        FogswapSdkError::ServiceUnavailable { message, estimated_return }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::endpoint::parse_result;
+use crate::limits::check_amount;
+use crate::req_structs::{Pair, QuoteRequest};
+use crate::resp_structs::TxType;
+use crate::FogswapSdk;
+
+/// Fee charged to send the payout on the destination network
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NetworkFee {
+    /// Fee in units of the destination token
+    #[serde(deserialize_with = "crate::amount::number_or_string")]
+    pub fee: f64,
+}
+
+/// Everything a checkout screen needs to show for a swap, in destination units
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Estimate {
+    /// Amount before any fee
+    pub gross: f64,
+    pub network_fee: f64,
+    pub service_fee: f64,
+    /// Amount the payout address receives
+    pub net: f64,
+    /// USD value of `net`, if the API priced the pair
+    pub usd_net: Option<f64>,
+}
+
+impl FogswapSdk {
+
+    /// Get the network fee of sending a token on its network
+    /// # Arguments
+    /// * `network` - The network of the token
+    /// * `contract_address` - The contract address of the token
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the fee cannot be retrieved
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip(self)))]
+    pub async fn get_network_fee(&self, network: &str, contract_address: &str) -> Result<NetworkFee> {
+        let endpoint = "/market/network_fee";
+
+        let resp = self.send_request(
+            reqwest::Method::GET,
+            endpoint,
+            Some(json!({
+                "network": network,
+                "contract_address": contract_address,
+            }))
+        ).await?;
+
+        parse_result(endpoint, resp)
+    }
+
+    /// Estimate what the payout address receives when swapping `amount` over `pair`
+    ///
+    /// Limits, quote and network fee are fetched concurrently.
+    /// # Arguments
+    /// * `pair` - The swap direction
+    /// * `amount` - The amount to send
+    /// * `tx_type` - The type of the transaction
+    /// # Returns
+    /// * `Estimate` - Gross amount, fees and net amount received
+    /// # Errors
+    /// * `FogswapSdkError::AmountOutOfLimits` - If `amount` is outside the pair limits
+    /// * Any error of `get_pair_limits`, `get_quote_with` or `get_network_fee`
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, Pair, TxType};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let estimate = sdk.estimate_total_received(&Pair::new("sol", "SOL", "eth", "ETH"), 0.5, TxType::Standard).await?;
+    /// println!("You receive {} ETH (${:?})", estimate.net, estimate.usd_net);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn estimate_total_received(&self, pair: &Pair, amount: f64, tx_type: TxType) -> Result<Estimate> {
+        let quote_req = QuoteRequest {
+            amount_from: amount,
+            network_from: pair.network_from.clone(),
+            contract_address_from: pair.contract_address_from.clone(),
+            network_to: pair.network_to.clone(),
+            contract_address_to: pair.contract_address_to.clone(),
+            tx_type: Some(tx_type.clone()),
+            ..Default::default()
+        };
+        let (limits, quote, network_fee) = tokio::try_join!(
+            self.get_pair_limits(pair, Some(tx_type)),
+            self.get_quote_with(&quote_req),
+            self.get_network_fee(&pair.network_to, &pair.contract_address_to),
+        )?;
+
+        check_amount(amount, &limits, &self.tolerance)?;
+
+        let service_fee = quote.service_fee.unwrap_or_default();
+        let net = (quote.amount_to - network_fee.fee).max(0.0);
+        let usd_net = match quote.convert_usd.to {
+            Some(usd) if quote.amount_to > 0.0 => Some(usd * net / quote.amount_to),
+            _ => None,
+        };
+        Ok(Estimate {
+            gross: quote.amount_to + service_fee,
+            network_fee: network_fee.fee,
+            service_fee,
+            net,
+            usd_net,
+        })
+    }
+}
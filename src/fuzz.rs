@@ -0,0 +1,131 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use arbitrary::{Arbitrary, Unstructured};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::endpoint::parse_result;
+use crate::estimate::NetworkFee;
+use crate::messages::ErrorMessages;
+use crate::resp_structs::{Ack, PairLimits, QuoteResponse, TokenList, TransactionInfo};
+use crate::status::StatusDescriptions;
+use crate::transport::TransportResponse;
+use crate::FogswapSdk;
+
+/// Endpoint whose response type a fuzzed body is decoded into
+#[derive(Debug, Clone, Copy, Arbitrary)]
+pub enum FuzzEndpoint {
+    TokenList,
+    PairLimits,
+    NetworkFee,
+    Quote,
+    CreateTransaction,
+    TransactionInfo,
+    Ack,
+    /// A WebSocket event of the `transaction` channel
+    WsEvent,
+}
+
+/// Server response built from fuzzer input
+#[derive(Debug, Clone, Arbitrary)]
+pub struct FuzzResponse {
+    pub endpoint: FuzzEndpoint,
+    pub status: u16,
+    pub retry_after: Option<String>,
+    pub body: Vec<u8>,
+}
+
+static SDK: LazyLock<FogswapSdk> = LazyLock::new(FogswapSdk::new);
+
+/// Decode raw fuzzer bytes as a [`FuzzResponse`]; input too short to build one is ignored
+///
+/// Meant as the body of a `cargo fuzz` target:
+/// ```ignore
+/// #![no_main]
+/// libfuzzer_sys::fuzz_target!(|data: &[u8]| fogswap_sdk_rust::fuzz::decode_bytes(data));
+/// ```
+pub fn decode_bytes(data: &[u8]) {
+    if let Ok(input) = FuzzResponse::arbitrary(&mut Unstructured::new(data)) {
+        decode(input);
+    }
+}
+
+/// Run a response through every step between the transport and the caller
+///
+/// Status mapping, envelope and result decoding, error formatting and the helpers of
+/// the decoded type. Any panic is a bug.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::fuzz::{decode, FuzzEndpoint, FuzzResponse};
+///
+/// decode(FuzzResponse {
+///     endpoint: FuzzEndpoint::Quote,
+///     status: 200,
+///     retry_after: None,
+///     body: br#"{"result":{"amount_from":"1e400"}}"#.to_vec(),
+/// });
+/// ```
+pub fn decode(input: FuzzResponse) {
+    let Ok(status) = reqwest::StatusCode::from_u16(input.status) else {
+        return;
+    };
+    let mut resp = TransportResponse { status, headers: Default::default(), body: input.body };
+    if let Some(retry_after) = input.retry_after
+        && let Ok(value) = reqwest::header::HeaderValue::from_str(&retry_after) {
+        resp.headers.insert(reqwest::header::RETRY_AFTER, value);
+    }
+
+    let body = match SDK.decode_response("/fuzz", resp, Duration::ZERO) {
+        Ok((body, _meta)) => body,
+        Err((e, _retryable)) => return describe(&e),
+    };
+    match input.endpoint {
+        FuzzEndpoint::TokenList => check::<Vec<TokenList>>(body, |lists| {
+            let _ = lists.iter().map(|list| list.tokens.len()).sum::<usize>();
+        }),
+        FuzzEndpoint::PairLimits => check::<PairLimits>(body, |_| ()),
+        FuzzEndpoint::NetworkFee => check::<NetworkFee>(body, |_| ()),
+        FuzzEndpoint::Quote => check::<QuoteResponse>(body, |quote| {
+            let _ = quote.is_expired_at(i64::MIN);
+            let _ = quote.is_expired_at(i64::MAX);
+            let _ = quote.tx_type.to_string();
+        }),
+        FuzzEndpoint::CreateTransaction | FuzzEndpoint::TransactionInfo => check::<TransactionInfo>(body, |tx_info| {
+            let _ = tx_info.requires_payin_extra_id();
+            let _ = tx_info.progress();
+            let _ = tx_info.eta();
+            let _ = tx_info.to_string();
+            let _ = StatusDescriptions::default().describe(&tx_info.status);
+        }),
+        FuzzEndpoint::Ack => check::<Ack>(body, |_| ()),
+        FuzzEndpoint::WsEvent => ws_event(&body),
+    }
+}
+
+fn check<T: DeserializeOwned>(body: Value, helpers: impl FnOnce(&T)) {
+    match parse_result::<T>("/fuzz", body) {
+        Ok(data) => helpers(&data),
+        Err(e) => describe(&e),
+    }
+}
+
+fn describe(e: &anyhow::Error) {
+    let _ = e.to_string();
+    let _ = format!("{:#}", e);
+    let _ = ErrorMessages::default().describe(e);
+}
+
+#[cfg(feature = "ws")]
+fn ws_event(body: &Value) {
+    match crate::ws::decode_event::<TransactionInfo>("transaction", &body.to_string()) {
+        Some(Ok(tx_info)) => {
+            let _ = tx_info.to_string();
+        }
+        Some(Err(e)) => describe(&e),
+        None => (),
+    }
+}
+
+#[cfg(not(feature = "ws"))]
+fn ws_event(_body: &Value) {}
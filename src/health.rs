@@ -0,0 +1,150 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::Stream;
+use tokio::sync::watch;
+
+use crate::endpoint::parse_result;
+use crate::error::FogswapSdkError;
+use crate::resp_structs::ServiceStatus;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::shutdown::BackgroundTask;
+use crate::FogswapSdk;
+
+/// Result of the most recent health check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthStatus {
+    /// No check has completed yet
+    Unknown,
+    Healthy,
+    Unhealthy(String),
+}
+
+/// Health shared by every clone of a client
+#[derive(Debug)]
+pub(crate) struct HealthState {
+    status: watch::Sender<HealthStatus>,
+}
+
+impl HealthState {
+
+    pub fn new() -> Self {
+        Self { status: watch::Sender::new(HealthStatus::Unknown) }
+    }
+
+    pub fn set(&self, status: HealthStatus) {
+        self.status.send_if_modified(|current| {
+            let changed = *current != status;
+            *current = status;
+            changed
+        });
+    }
+}
+
+impl FogswapSdk {
+
+    /// Periodically check that the API is reachable
+    ///
+    /// Every `interval` a `HEAD` request is sent to the current base URL; any response
+    /// below 500 counts as healthy. The task runs until the returned handle is aborted
+    /// or dropped, or the client is shut down.
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use futures::StreamExt;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() {
+    /// let sdk = FogswapSdk::new();
+    /// let monitor = sdk.spawn_health_monitor(Duration::from_secs(30));
+    /// let mut events = std::pin::pin!(sdk.health_events());
+    /// while let Some(status) = events.next().await {
+    ///     println!("Fogswap health changed: {:?}", status);
+    /// }
+    /// monitor.abort();
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn_health_monitor(&self, interval: Duration) -> BackgroundTask {
+        let sdk = self.clone();
+        BackgroundTask::new(self.spawn_until_shutdown("fogswap-health-monitor", async move {
+            loop {
+                let status = match sdk.client.head(sdk.current_base_url()).send().await {
+                    Ok(resp) if resp.status().is_server_error() => HealthStatus::Unhealthy(format!("status {}", resp.status())),
+                    Ok(_) => HealthStatus::Healthy,
+                    Err(e) => HealthStatus::Unhealthy(e.to_string()),
+                };
+                sdk.health.set(status);
+                sdk.clock.sleep(interval).await;
+            }
+        }))
+    }
+
+    /// Get the availability of the API, e.g. to show a maintenance banner
+    ///
+    /// A maintenance answer is returned as a `ServiceStatus`, not as an error. Other
+    /// requests made during maintenance fail with `FogswapSdkError::ServiceUnavailable`.
+    /// # Errors
+    /// * Any error other than the API being unavailable
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    /// use serde_json::json;
+    /// use fogswap_sdk_rust::{ApiErrorCode, FogswapSdk, FogswapSdkError, TransportResponse};
+    /// use fogswap_sdk_rust::testing::MockTransport;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mock = Arc::new(MockTransport::new());
+    /// let sdk = FogswapSdk::new().with_transport(mock.clone());
+    /// assert!(sdk.get_service_status().await?.is_available());
+    ///
+    /// let down = json!({ "message": "Scheduled upgrade", "estimated_return": 1767225600 });
+    /// mock.on("/status", TransportResponse::json(503, &down));
+    /// mock.on("/market/tokens", TransportResponse::json(503, &down));
+    /// let status = sdk.get_service_status().await?;
+    /// assert!(!status.is_available());
+    /// assert_eq!(status.estimated_return, Some(1767225600));
+    ///
+    /// let e = sdk.get_token_list().await.unwrap_err();
+    /// assert!(matches!(e.downcast_ref(), Some(FogswapSdkError::ServiceUnavailable { .. })));
+    /// assert_eq!(ApiErrorCode::of(&e), ApiErrorCode::ServiceUnavailable);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_service_status(&self) -> Result<ServiceStatus> {
+        let endpoint = "/status";
+        match self.send_request(reqwest::Method::GET, endpoint, None).await.and_then(|resp| parse_result(endpoint, resp)) {
+            Ok(status) => Ok(status),
+            Err(e) => match e.chain().find_map(|e| e.downcast_ref::<FogswapSdkError>()) {
+                Some(FogswapSdkError::ServiceUnavailable { message, estimated_return }) => Ok(ServiceStatus {
+                    status: "maintenance".to_string(),
+                    maintenance: true,
+                    message: Some(message.clone()),
+                    estimated_return: *estimated_return,
+                }),
+                _ => Err(e),
+            },
+        }
+    }
+
+    /// Whether the last health check succeeded (`true` before the first check)
+    pub fn is_healthy(&self) -> bool {
+        !matches!(self.health_status(), HealthStatus::Unhealthy(_))
+    }
+
+    pub fn health_status(&self) -> HealthStatus {
+        self.health.status.borrow().clone()
+    }
+
+    /// Stream of health status changes
+    pub fn health_events(&self) -> impl Stream<Item = HealthStatus> + use<> {
+        let mut rx = self.health.status.subscribe();
+        rx.mark_unchanged();
+        futures::stream::unfold(rx, |mut rx| async move {
+            rx.changed().await.ok()?;
+            let status = rx.borrow_and_update().clone();
+            Some((status, rx))
+        })
+    }
+}
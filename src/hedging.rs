@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::meta::ResponseMeta;
+use crate::metrics::Attempt;
+use crate::profiles::RequestProfile;
+use crate::FogswapSdk;
+
+impl FogswapSdk {
+
+    /// Send a duplicate of every quote request when the first has not answered after `delay`
+    ///
+    /// The duplicate goes to the fastest other mirror, or to the same base URL without
+    /// mirrors. The first successful response wins and the other request is cancelled.
+    /// Only quotes are hedged; other endpoints are never sent twice.
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// let sdk = FogswapSdk::new().with_quote_hedging(Duration::from_millis(300));
+    /// ```
+    pub fn with_quote_hedging(mut self, delay: Duration) -> Self {
+        self.hedge_delay = Some(delay);
+        self
+    }
+
+    /// Base URL hedged requests are sent to
+    pub(crate) fn hedge_base_url(&self) -> String {
+        self.mirrors
+            .as_ref()
+            .and_then(|mirrors| mirrors.alternate_url())
+            .filter(|url| self.transport.check_url(url).is_ok())
+            .map(str::to_string)
+            .unwrap_or_else(|| self.current_base_url())
+    }
+
+    /// Race `url` against `hedge_url` started `delay` later
+    ///
+    /// Returns the first success, or the primary error once both have failed.
+    pub(crate) async fn send_hedged(
+        &self,
+        req_method: &reqwest::Method,
+        endpoint: &str,
+        url: &str,
+        hedge_url: &str,
+        payload: &Option<Value>,
+        profile: &RequestProfile,
+        delay: Duration,
+    ) -> (Result<(Value, ResponseMeta)>, Vec<Attempt>) {
+        let primary = self.send_with_retries(req_method, endpoint, url, payload, profile);
+        let hedge = async {
+            self.clock.sleep(delay).await;
+            self.send_with_retries(req_method, endpoint, hedge_url, payload, profile).await
+        };
+        tokio::pin!(primary, hedge);
+
+        let (mut primary_failed, mut hedge_failed) = (None, None);
+        while primary_failed.is_none() || hedge_failed.is_none() {
+            tokio::select! {
+                (result, attempts) = &mut primary, if primary_failed.is_none() => {
+                    if result.is_ok() {
+                        return (result, attempts);
+                    }
+                    primary_failed = Some((result, attempts));
+                }
+                (result, attempts) = &mut hedge, if hedge_failed.is_none() => {
+                    if result.is_ok() {
+                        return (result, attempts);
+                    }
+                    hedge_failed = Some(attempts);
+                }
+            }
+        }
+        let (result, mut attempts) = primary_failed.expect("loop ends once both have failed");
+        attempts.extend(hedge_failed.unwrap_or_default());
+        (result, attempts)
+    }
+}
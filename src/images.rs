@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use futures::stream::{self, StreamExt};
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+
+use crate::config::redact_url;
+use crate::resp_structs::TokenList;
+use crate::FogswapSdk;
+
+/// Local cache directory for network and token logos
+#[derive(Debug, Clone)]
+pub struct ImageCache {
+    pub dir: PathBuf,
+    /// Maximum number of downloads in flight at once
+    pub max_concurrency: usize,
+}
+
+impl ImageCache {
+
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), max_concurrency: 8 }
+    }
+
+    /// Path an image URL is cached under
+    pub fn path_for(&self, url: &str) -> PathBuf {
+        let ext = url
+            .rsplit('/')
+            .next()
+            .and_then(|name| name.split(['?', '#']).next())
+            .and_then(|name| name.rsplit_once('.').map(|(_, ext)| ext))
+            .filter(|ext| !ext.is_empty() && ext.len() <= 5 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+            .unwrap_or("img");
+        self.dir.join(format!("{:016x}.{}", fnv1a(url.as_bytes()), ext))
+    }
+}
+
+impl FogswapSdk {
+
+    /// Download every `network_image` and token `image` to a local cache directory
+    ///
+    /// Already cached images are revalidated with their ETag and only downloaded
+    /// again when they changed. Downloads go through the transport and interceptors of
+    /// the client. Images that fail to download are left out of the result.
+    /// # Arguments
+    /// * `token_lists` - Token lists as returned by `get_token_list`
+    /// * `cache` - Cache directory and concurrency limit
+    /// # Returns
+    /// * `HashMap<String, PathBuf>` - Local path per image URL
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, ImageCache};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tokens = sdk.get_token_list().await?;
+    /// let paths = sdk.prefetch_token_images(&tokens, &ImageCache::new("logos")).await?;
+    /// println!("{} logos cached", paths.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prefetch_token_images(
+        &self,
+        token_lists: &[TokenList],
+        cache: &ImageCache
+    ) -> Result<HashMap<String, PathBuf>> {
+        tokio::fs::create_dir_all(&cache.dir).await?;
+
+        let mut urls: Vec<&str> = token_lists
+            .iter()
+            .flat_map(|list| std::iter::once(list.network_image.as_str()).chain(list.tokens.iter().map(|t| t.image.as_str())))
+            .filter(|url| !url.is_empty())
+            .collect();
+        urls.sort_unstable();
+        urls.dedup();
+
+        let paths = stream::iter(urls)
+            .map(|url| async move {
+                let path = cache.path_for(url);
+                self.fetch_image(url, &path).await.ok().map(|_| (url.to_string(), path))
+            })
+            .buffer_unordered(cache.max_concurrency.max(1))
+            .filter_map(|entry| async move { entry })
+            .collect()
+            .await;
+        Ok(paths)
+    }
+
+    async fn fetch_image(&self, url: &str, path: &Path) -> Result<()> {
+        let etag_path = path.with_extension("etag");
+        let mut req = self.client.get(url);
+        if tokio::fs::try_exists(path).await.unwrap_or(false)
+            && let Ok(etag) = tokio::fs::read_to_string(&etag_path).await {
+            req = req.header(IF_NONE_MATCH, etag);
+        }
+
+        let resp = self.execute_direct(req.build()?).await?;
+        if resp.status == StatusCode::NOT_MODIFIED {
+            return Ok(());
+        }
+        if !resp.status.is_success() {
+            anyhow::bail!("image {} returned {}", redact_url(url), resp.status);
+        }
+        let etag = resp.headers.get(ETAG).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let bytes = resp.body;
+
+        tokio::fs::write(path, &bytes).await?;
+        match etag {
+            Some(etag) => tokio::fs::write(&etag_path, etag).await?,
+            None => { let _ = tokio::fs::remove_file(&etag_path).await; }
+        }
+        Ok(())
+    }
+}
+
+/// Stable 64-bit FNV-1a hash, used for cache file names
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| (hash ^ *b as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use reqwest::header::{HeaderValue, ETAG, IF_NONE_MATCH};
+
+    use crate::testing::MockTransport;
+    use crate::{FogswapSdk, ImageCache, TokenList, TransportResponse};
+
+    #[tokio::test]
+    async fn images_are_fetched_and_revalidated_through_the_transport() {
+        let mock = Arc::new(MockTransport::empty());
+        mock.on("/sol.png", TransportResponse::new(200, "logo").with_header(ETAG, HeaderValue::from_static("\"v1\"")));
+        let sdk = FogswapSdk::new().with_transport(mock.clone());
+        let tokens: Vec<TokenList> = vec![serde_json::from_value(MockTransport::sample_token_list()).unwrap()];
+        let cache = ImageCache::new(std::env::temp_dir().join(format!("fogswap-images-{}", uuid::Uuid::new_v4())));
+
+        let paths = sdk.prefetch_token_images(&tokens, &cache).await.unwrap();
+        let path = &paths["https://example.com/sol.png"];
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "logo");
+
+        mock.on("/sol.png", TransportResponse::new(304, ""));
+        sdk.prefetch_token_images(&tokens, &cache).await.unwrap();
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].headers[IF_NONE_MATCH], "\"v1\"");
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "logo");
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use reqwest::{Method, StatusCode};
+
+use crate::transport::TransportResponse;
+use crate::FogswapSdk;
+
+/// A request about to be sent, as seen by `on_request` hooks
+///
+/// Credentials (bearer token, API key) are replaced by `***` in the URL and headers.
+#[derive(Debug, Clone)]
+pub struct RequestInfo {
+    pub method: Method,
+    pub endpoint: String,
+    pub url: String,
+    pub headers: HeaderMap,
+    /// Raw JSON body of POST requests
+    pub body: Option<Vec<u8>>,
+}
+
+/// A response as received, before any decoding, as seen by `on_response` hooks
+#[derive(Debug, Clone)]
+pub struct ResponseInfo {
+    pub method: Method,
+    pub endpoint: String,
+    pub url: String,
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+    pub latency: Duration,
+}
+
+type RequestHook = Arc<dyn Fn(&RequestInfo) + Send + Sync>;
+type ResponseHook = Arc<dyn Fn(&ResponseInfo) + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub(crate) struct Interceptors {
+    request: Vec<RequestHook>,
+    response: Vec<ResponseHook>,
+}
+
+impl Debug for Interceptors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Interceptors")
+            .field("request", &self.request.len())
+            .field("response", &self.response.len())
+            .finish()
+    }
+}
+
+impl FogswapSdk {
+
+    /// Call `hook` with every request sent, retries and credential refreshes included
+    ///
+    /// Hooks run in the order they were added, on the calling task, so they should
+    /// be quick; hand the data to a channel for slow work such as persistence.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// let sdk = FogswapSdk::new()
+    ///     .on_request(|req| println!("-> {} {}", req.method, req.url))
+    ///     .on_response(|resp| println!("<- {} {} in {:?}", resp.status, resp.endpoint, resp.latency));
+    /// ```
+    pub fn on_request(mut self, hook: impl Fn(&RequestInfo) + Send + Sync + 'static) -> Self {
+        self.interceptors.request.push(Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` with every raw response received, whatever its status
+    pub fn on_response(mut self, hook: impl Fn(&ResponseInfo) + Send + Sync + 'static) -> Self {
+        self.interceptors.response.push(Arc::new(hook));
+        self
+    }
+
+    pub(crate) fn intercept_request(&self, endpoint: &str, req: &reqwest::Request, token: Option<&str>) {
+        if self.interceptors.request.is_empty() {
+            return;
+        }
+        let secrets = self.secrets(token);
+        let info = RequestInfo {
+            method: req.method().clone(),
+            endpoint: endpoint.to_string(),
+            url: mask(req.url().as_str(), &secrets),
+            headers: mask_headers(req.headers(), &secrets),
+            body: req.body().and_then(|body| body.as_bytes()).map(<[u8]>::to_vec),
+        };
+        for hook in &self.interceptors.request {
+            hook(&info);
+        }
+    }
+
+    pub(crate) fn intercept_response(&self, req_method: &Method, endpoint: &str, url: &str, resp: &TransportResponse, latency: Duration) {
+        if self.interceptors.response.is_empty() {
+            return;
+        }
+        let info = ResponseInfo {
+            method: req_method.clone(),
+            endpoint: endpoint.to_string(),
+            url: mask(url, &self.secrets(None)),
+            status: resp.status,
+            headers: resp.headers.clone(),
+            body: resp.body.clone(),
+            latency,
+        };
+        for hook in &self.interceptors.response {
+            hook(&info);
+        }
+    }
+
+    fn secrets<'a>(&'a self, token: Option<&'a str>) -> Vec<&'a str> {
+        self.api_key.iter().map(|api_key| api_key.key.as_str()).chain(token).filter(|s| !s.is_empty()).collect()
+    }
+}
+
+fn mask(value: &str, secrets: &[&str]) -> String {
+    secrets.iter().fold(value.to_string(), |value, secret| value.replace(secret, "***"))
+}
+
+fn mask_headers(headers: &HeaderMap, secrets: &[&str]) -> HeaderMap {
+    let mut masked = headers.clone();
+    for (name, value) in masked.iter_mut() {
+        let Ok(text) = value.to_str() else {
+            continue;
+        };
+        if name == AUTHORIZATION || secrets.iter().any(|secret| text.contains(secret)) {
+            *value = HeaderValue::from_static("***");
+        }
+    }
+    masked
+}
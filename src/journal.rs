@@ -0,0 +1,127 @@
+use std::fmt::Debug;
+use std::path::Path;
+
+use anyhow::Result;
+use futures::StreamExt;
+
+use crate::projection::{StatusChange, StatusReconcileReport};
+use crate::resp_structs::TransactionInfo;
+use crate::FogswapSdk;
+
+/// Maximum number of lookups in flight during `resume_pending`
+const RESUME_CONCURRENCY: usize = 8;
+
+/// Durable record of created transactions and their last known state
+///
+/// `record` must be durable before returning, so a restarted process finds every
+/// swap it had in flight.
+pub trait TxJournal: Debug + Send + Sync {
+    /// Insert or replace the state of a transaction
+    fn record(&self, info: &TransactionInfo) -> Result<()>;
+
+    /// Every recorded transaction
+    fn transactions(&self) -> Result<Vec<TransactionInfo>>;
+
+    /// Forget a transaction, e.g. once it has been archived
+    fn remove(&self, tx_id: &str) -> Result<()>;
+
+    /// Recorded transactions whose last known status is not final
+    fn pending(&self) -> Result<Vec<TransactionInfo>> {
+        Ok(self.transactions()?.into_iter().filter(|info| !info.status.is_terminal()).collect())
+    }
+}
+
+/// Journal backed by a sled database, one JSON entry per transaction
+#[derive(Debug, Clone)]
+pub struct SledJournal {
+    db: sled::Db,
+}
+
+impl SledJournal {
+
+    /// Open or create the database at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self { db: sled::open(path)? })
+    }
+}
+
+impl TxJournal for SledJournal {
+
+    fn record(&self, info: &TransactionInfo) -> Result<()> {
+        self.db.insert(info.id.as_bytes(), serde_json::to_vec(info)?)?;
+        self.db.flush()?;
+        Ok(())
+    }
+
+    fn transactions(&self) -> Result<Vec<TransactionInfo>> {
+        self.db
+            .iter()
+            .map(|entry| Ok(serde_json::from_slice(&entry?.1)?))
+            .collect()
+    }
+
+    fn remove(&self, tx_id: &str) -> Result<()> {
+        self.db.remove(tx_id.as_bytes())?;
+        self.db.flush()?;
+        Ok(())
+    }
+}
+
+impl FogswapSdk {
+
+    /// Record every transaction created or looked up through this client (and its
+    /// clones) in `journal`
+    ///
+    /// A failing journal does not fail the call whose result it records.
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    /// use fogswap_sdk_rust::journal::SledJournal;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let journal = Arc::new(SledJournal::open("fogswap-journal")?);
+    /// let sdk = FogswapSdk::new().with_journal(journal.clone());
+    /// let report = sdk.resume_pending(&*journal).await?;
+    /// println!("{} swaps moved on while we were down", report.changed.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_journal(mut self, journal: std::sync::Arc<dyn TxJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Refresh every transaction the journal knows as still in flight, typically on startup
+    ///
+    /// The refreshed states are written back to `journal` and tracked. Transactions
+    /// that could not be looked up are reported as failed and stay pending.
+    /// # Errors
+    /// * Any error of `TxJournal::pending` or `TxJournal::record`
+    pub async fn resume_pending(&self, journal: &dyn TxJournal) -> Result<StatusReconcileReport> {
+        let results: Vec<(TransactionInfo, Result<TransactionInfo>)> = futures::stream::iter(journal.pending()?)
+            .map(|last| async move {
+                let result = self.get_transaction_info(&last.id).await;
+                (last, result)
+            })
+            .buffer_unordered(RESUME_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut report = StatusReconcileReport::default();
+        for (last, result) in results {
+            match result {
+                Ok(info) => {
+                    journal.record(&info)?;
+                    if info.status == last.status {
+                        report.unchanged.push(info.id);
+                    } else {
+                        report.changed.push(StatusChange { tx_id: info.id, from: Some(last.status), to: info.status });
+                    }
+                }
+                Err(e) => report.failed.push((last.id, e.to_string())),
+            }
+        }
+        Ok(report)
+    }
+}
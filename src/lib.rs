@@ -1,14 +1,135 @@
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("building for wasm32 requires the `wasm` feature");
+#[cfg(all(target_arch = "wasm32", any(feature = "ws", feature = "blocking", feature = "images", feature = "cli", feature = "store", feature = "replay")))]
+compile_error!("the `ws`, `blocking`, `images`, `cli`, `store` and `replay` features are not available on wasm32");
+
+pub mod amount;
+pub mod api;
+pub mod audit;
+pub mod auth;
+pub mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod builder;
+pub mod catalog;
+#[cfg(feature = "chainwatch")]
+pub mod chainwatch;
+pub mod client_metadata;
+pub mod clock;
+pub mod config;
+pub mod dedup;
+pub mod degraded;
+pub mod emergency;
+pub mod endpoint;
+pub mod environment;
 pub mod error;
+pub mod estimate;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
+pub mod health;
+pub mod hedging;
+#[cfg(feature = "images")]
+pub mod images;
+pub mod interceptors;
+#[cfg(feature = "store")]
+pub mod journal;
+pub mod limits;
+pub mod messages;
+pub mod meta;
+pub mod metrics;
+pub mod mirrors;
+pub mod networks;
+pub mod offline;
+pub mod orchestrator;
+pub mod pagination;
+pub mod polling;
+pub mod precision;
+pub mod profiles;
+pub mod projection;
+#[cfg(feature = "qr")]
+mod qr;
+pub mod rates;
+pub mod receipt;
+#[cfg(feature = "replay")]
+pub mod replay;
+pub mod req_structs;
 pub mod resp_structs;
+pub mod routing;
+mod rt;
+pub mod sandbox;
+pub mod scheduler;
+pub mod session;
+pub mod shutdown;
+pub mod status;
+pub mod store;
+#[cfg(feature = "metrics")]
+mod telemetry;
+pub mod testing;
+pub mod token_cache;
+#[cfg(feature = "tracing")]
+mod trace;
+pub mod transport;
+pub mod validation;
+#[cfg(feature = "webhooks")]
+pub mod webhooks;
+#[cfg(feature = "ws")]
+pub mod ws;
 
 // Re-export commonly used types for convenience
-pub use resp_structs::{TokenList, QuoteResponse, TransactionInfo, TxType};
+pub use resp_structs::{Ack, ApiError, ApiResponse, Emergency, TokenList, QuoteResponse, TransactionInfo, TxType, PairLimits, PartnerFee, Paginated, ServiceStatus};
+pub use emergency::EmergencyAction;
+pub use rates::RateInfo;
+pub use req_structs::{Asset, CreateTransactionBuilder, CreateTransactionRequest, ListTxParams, PrivacyMode, QuoteRequest, TokenListFilter, Pair};
+pub use batch::{BatchItem, BatchMode, BatchOptions, BatchOutcome, BatchReport};
+pub use builder::{FogswapSdkBuilder, HttpVersion, PoolOptions, TlsBackend};
+pub use environment::{Environment, PRODUCTION_BASE_URL, SANDBOX_BASE_URL};
 pub use error::FogswapSdkError;
+pub use messages::{ApiErrorCode, ErrorMessages};
+pub use endpoint::Endpoint;
+pub use api::{ArcFogswap, FogswapApi};
+pub use audit::{AuditPolicy, AuditRecord, AuditSink};
+pub use interceptors::{RequestInfo, ResponseInfo};
+pub use clock::{Clock, MockClock, SystemClock};
+pub use config::ConfigSnapshot;
+pub use health::HealthStatus;
+pub use mirrors::{MirrorOptions, MirrorStatus};
+pub use transport::{IpPreference, ProxyConfig, RedirectPolicy, Transport, TransportOptions, TransportResponse};
+pub use auth::{Account, ApiKeyPlacement, TokenProvider};
+pub use profiles::{EndpointClass, RequestOptions, RequestProfile, RequestProfiles, RetryPolicy};
+pub use meta::{ResponseMeta, WithMeta};
+pub use metrics::{Attempt, CallMetrics, MetricsSink};
+pub use client_metadata::{ClientMetadata, TrackedTransaction};
+pub use projection::{StatusChange, StatusReconcileReport, TrackedFilter};
+pub use estimate::{Estimate, NetworkFee};
+pub use catalog::{CatalogOptions, TokenCatalog};
+pub use receipt::SwapReceipt;
+pub use precision::{AmountFormat, AmountPrecision, AmountTolerance};
+pub use networks::{Network, NetworkFormat, NetworkRegistry};
+pub use status::{StatusDescriptions, StatusEvent, StatusSequencer, TransactionStatus, TransactionStatusInfo};
+pub use orchestrator::{OrchestratorEvent, SwapOrchestrator};
+pub use session::{SwapSession, SwapStage, SwapTransition};
+pub use routing::{QuoteComparison, RouteOption, RouteOptions, RoutePlan, RoutePlanner, RouteWeights};
+pub use pagination::{Page, PageCursor, PageOptions, PageStream};
+pub use polling::PollOptions;
+#[cfg(not(target_arch = "wasm32"))]
+pub use shutdown::BackgroundTask;
+pub use shutdown::TaskInfo;
+pub use dedup::{DedupKeying, DuplicateProtection};
+pub use degraded::{DataFreshness, MaybeStale};
+#[cfg(feature = "images")]
+pub use images::ImageCache;
+#[cfg(feature = "ws")]
+pub use ws::{RateTickEvent, TxStatusEvent};
+pub use store::{JsonFileStore, MemoryStore, PendingIntent, ReconcileReport, TxStore};
 
 use std::collections::HashMap;
+use std::sync::Arc;
+use endpoint::parse_result;
+use futures::{StreamExt, TryStreamExt};
 use reqwest::Client;
 use serde_json::{json, Value};
 use anyhow::Result;
+use tokio_util::sync::CancellationToken;
 
 
 
@@ -16,11 +137,53 @@ use anyhow::Result;
 pub struct FogswapSdk {
     pub base_url: String,
     pub client: Client,
+    environment: Environment,
+    dedup: Option<Arc<dedup::DuplicateGuard>>,
+    store: Option<Arc<dyn TxStore>>,
+    last_good: Option<Arc<degraded::LastGoodCache>>,
+    health: Arc<health::HealthState>,
+    mirrors: Option<Arc<mirrors::MirrorSet>>,
+    transport: TransportOptions,
+    http: Option<Arc<dyn Transport>>,
+    client_options: builder::ClientOptions,
+    auth: Option<Arc<auth::AuthState>>,
+    api_key: Option<auth::ApiKey>,
+    partner_id: Option<String>,
+    partner_fee_bps: Option<u32>,
+    profiles: RequestProfiles,
+    retry: RetryPolicy,
+    metadata: Arc<client_metadata::MetadataIndex>,
+    queue: Option<Arc<scheduler::RequestQueue>>,
+    limiter: Option<Arc<scheduler::RateLimiter>>,
+    offline: Option<Arc<offline::OfflineState>>,
+    networks: Arc<networks::NetworkRegistry>,
+    shutdown: Arc<shutdown::ShutdownState>,
+    cancel: Option<CancellationToken>,
+    metrics: Option<Arc<dyn MetricsSink>>,
+    audit: Option<audit::Audit>,
+    hedge_delay: Option<std::time::Duration>,
+    precision: AmountPrecision,
+    tolerance: AmountTolerance,
+    token_cache: Arc<token_cache::TokenListCache>,
+    limits: Arc<limits::KnownLimits>,
+    clock: Arc<dyn Clock>,
+    projection: Option<Arc<projection::TransactionProjection>>,
+    interceptors: interceptors::Interceptors,
+    /// Set once the API answered 404 to the batch info endpoint
+    batch_info_missing: Arc<std::sync::atomic::AtomicBool>,
+    #[cfg(feature = "store")]
+    journal: Option<Arc<dyn journal::TxJournal>>,
+}
+
+impl Default for FogswapSdk {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl FogswapSdk {
 
-    const BASE_URL: &str = "https://api.fogswap.io/v1";
+    const BASE_URL: &str = environment::PRODUCTION_BASE_URL;
     
     /// Create a new FogswapSdk instance
     /// # Examples
@@ -33,7 +196,84 @@ impl FogswapSdk {
         let client = Client::builder()
             .build()
             .unwrap_or_default();
-        Self { base_url: Self::BASE_URL.to_string(), client }
+        Self {
+            base_url: Self::BASE_URL.to_string(),
+            client,
+            environment: Environment::Production,
+            dedup: None,
+            store: None,
+            last_good: None,
+            health: Arc::new(health::HealthState::new()),
+            mirrors: None,
+            transport: TransportOptions::default(),
+            http: None,
+            client_options: builder::ClientOptions::default(),
+            auth: None,
+            api_key: None,
+            partner_id: None,
+            partner_fee_bps: None,
+            profiles: RequestProfiles::default(),
+            retry: RetryPolicy::default(),
+            metadata: Arc::default(),
+            queue: None,
+            limiter: None,
+            offline: None,
+            networks: Arc::default(),
+            shutdown: Arc::default(),
+            cancel: None,
+            metrics: None,
+            audit: None,
+            hedge_delay: None,
+            precision: AmountPrecision::default(),
+            tolerance: AmountTolerance::default(),
+            token_cache: Arc::default(),
+            limits: Arc::default(),
+            clock: Arc::new(SystemClock),
+            projection: None,
+            interceptors: interceptors::Interceptors::default(),
+            batch_info_missing: Arc::default(),
+            #[cfg(feature = "store")]
+            journal: None,
+        }
+    }
+
+    /// Return the existing transaction when `create_transaction` is called
+    /// again with the same parameters inside the configured window
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::{DedupKeying, DuplicateProtection, FogswapSdk};
+    ///
+    /// let sdk = FogswapSdk::new().with_duplicate_protection(DuplicateProtection {
+    ///     window: Duration::from_secs(60),
+    ///     keying: DedupKeying::PairAmountPayout,
+    /// });
+    /// ```
+    pub fn with_duplicate_protection(mut self, config: DuplicateProtection) -> Self {
+        self.dedup = Some(Arc::new(dedup::DuplicateGuard::new(config)));
+        self
+    }
+
+    /// Persist create intents to `store` before they are sent
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    /// use fogswap_sdk_rust::{FogswapSdk, JsonFileStore};
+    ///
+    /// let sdk = FogswapSdk::new().with_store(Arc::new(JsonFileStore::new("fogswap-intents.json")));
+    /// ```
+    pub fn with_store(mut self, store: Arc<dyn TxStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Keep the last successful token list, rates and USD prices so `*_with_freshness`
+    /// calls can serve them, tagged as stale, while the API is down
+    /// # Arguments
+    /// * `max_age` - Cached data older than this is never served (`None` for no limit)
+    pub fn with_degraded_mode(mut self, max_age: Option<std::time::Duration>) -> Self {
+        self.last_good = Some(Arc::new(degraded::LastGoodCache::new(max_age)));
+        self
     }
 
     /// Send a request to the Fogswap API
@@ -43,57 +283,268 @@ impl FogswapSdk {
         endpoint: &str,
         payload: Option<Value>,
     ) -> Result<Value> {
+        Ok(self.send_request_meta(req_method, endpoint, payload).await?.0)
+    }
 
-        let url = format!("{}{}", self.base_url, endpoint);
-        
-        let resp={
-            match req_method {
-                reqwest::Method::GET => {
-                    match payload {
-                        Some(payload) => {
-                            let params: HashMap<&String, &Value> = payload
-                                .as_object()
-                                .unwrap()
-                                .iter()
-                                .flat_map(|(k, v)| {
-                                    if v.is_null() {
-                                        None
-                                    } else {
-                                        Some((k, v))
-                                    }
-                                })
-                                .collect();
-                            self.client.get(url).query(&params).send().await?
-                        }
-                        _=> self.client.get(url).send().await?
-                    }
-                },
-                reqwest::Method::POST => {
-                    match payload {
-                        Some(payload) => {
-                            self.client.post(url).header("Content-Type", "application/json").json(&payload).send().await?
-                        }
-                        _=> self.client.post(url).header("Content-Type", "application/json").send().await?
-                    }   
+    /// Send a request to the Fogswap API and keep the response metadata
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", name = "fogswap_request", skip(self, req_method, payload), fields(method = %req_method)))]
+    async fn send_request_meta(
+        &self,
+        req_method: reqwest::Method,
+        endpoint: &str,
+        payload: Option<Value>,
+    ) -> Result<(Value, ResponseMeta)> {
+
+        if self.is_offline() {
+            return Err(FogswapSdkError::Offline.into());
+        }
+
+        let url = format!("{}{}", self.current_base_url(), endpoint);
+        self.transport.check_url(&url)?;
+        #[cfg(feature = "tracing")]
+        trace::request(&payload);
+        let class = EndpointClass::from_path(endpoint);
+        let profile = self.profiles.get(class);
+        let started_at = rt::SystemTime::now();
+
+        let result = self.until_shutdown(async {
+            let started = rt::Instant::now();
+            let (result, attempts) = match self.hedge_delay.filter(|_| class == EndpointClass::Quote) {
+                Some(delay) => {
+                    let hedge_url = format!("{}{}", self.hedge_base_url(), endpoint);
+                    self.send_hedged(&req_method, endpoint, &url, &hedge_url, &payload, profile, delay).await
+                }
+                None => self.send_with_retries(&req_method, endpoint, &url, &payload, profile).await,
+            };
+
+            let elapsed = started.elapsed();
+            #[cfg(feature = "metrics")]
+            telemetry::call(&req_method, endpoint, &result, &attempts, elapsed);
+            if let Some(sink) = &self.metrics {
+                sink.record_call(&CallMetrics {
+                    endpoint: endpoint.to_string(),
+                    attempts: attempts.clone(),
+                    elapsed,
+                    success: result.is_ok(),
+                });
+            }
+            result.map(|(body, meta)| (body, meta.with_attempts(attempts, elapsed)))
+        }).await;
+
+        #[cfg(feature = "tracing")]
+        trace::response(&result);
+        self.audit(&req_method, endpoint, &payload, started_at, &result);
+        result
+    }
+
+    /// Send a request, retrying as allowed by `profile`, and keep every attempt
+    async fn send_with_retries(
+        &self,
+        req_method: &reqwest::Method,
+        endpoint: &str,
+        url: &str,
+        payload: &Option<Value>,
+        profile: &RequestProfile,
+    ) -> (Result<(Value, ResponseMeta)>, Vec<Attempt>) {
+        let mut attempts = Vec::new();
+        let mut attempt = 0;
+        let result = loop {
+            let attempt_started = rt::Instant::now();
+            let outcome = self.send_once(req_method, endpoint, url, payload, profile.timeout).await;
+            attempts.push(Attempt {
+                elapsed: attempt_started.elapsed(),
+                error: outcome.as_ref().err().map(|(e, _)| e.to_string()),
+            });
+            match outcome {
+                Ok(body) => break Ok(body),
+                Err((e, _)) if self.note_connectivity_error(&e) => {
+                    break Err(e.context(FogswapSdkError::Offline));
+                }
+                Err((e, true)) if attempt < profile.max_retries && self.retry.allows_request(req_method, payload) => {
+                    let backoff = self.retry.backoff(profile.backoff, attempt);
+                    // The server knows best when to come back, within the longest delay allowed
+                    let delay = match e.downcast_ref::<FogswapSdkError>() {
+                        Some(FogswapSdkError::RateLimited { retry_after: Some(retry_after) }) => (*retry_after).min(self.retry.max_backoff),
+                        Some(FogswapSdkError::ServiceUnavailable { estimated_return: Some(at), .. }) => {
+                            let now = rt::SystemTime::now().duration_since(rt::UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+                            std::time::Duration::from_secs(at.saturating_sub(now).max(0) as u64).max(backoff).min(self.retry.max_backoff)
+                        },
+                        _ => backoff,
+                    };
+                    #[cfg(feature = "tracing")]
+                    tracing::info!(attempt, ?delay, error = %e, "retrying");
+                    self.clock.sleep(delay).await;
+                    attempt += 1;
+                }
+                Err((e, _)) => break Err(e),
+            }
+        };
+        (result, attempts)
+    }
+
+    /// Send a single attempt; the flag tells whether the failure is transient
+    async fn send_once(
+        &self,
+        req_method: &reqwest::Method,
+        endpoint: &str,
+        url: &str,
+        payload: &Option<Value>,
+        timeout: Option<std::time::Duration>,
+    ) -> std::result::Result<(Value, ResponseMeta), (anyhow::Error, bool)> {
+        let started = rt::Instant::now();
+        let token = match &self.auth {
+            Some(auth) => Some(auth.token().await.map_err(|e| (e, false))?),
+            None => None,
+        };
+        let send = |token: Option<String>| async move {
+            if let Some(queue) = &self.queue {
+                queue.acquire(&*self.clock).await;
+            }
+            if let Some(limiter) = &self.limiter {
+                limiter.acquire(&*self.clock).await;
+            }
+            let mut req = self.build_request(req_method, url, payload, token.as_deref()).map_err(|e| (e, false))?;
+            #[cfg(target_arch = "wasm32")]
+            let timeout = timeout.or(self.client_options.timeout);
+            if let Some(timeout) = timeout {
+                req = req.timeout(timeout);
+            }
+            let req = req.build().map_err(|e| (e.into(), false))?;
+            self.intercept_request(endpoint, &req, token.as_deref());
+            let sent = rt::Instant::now();
+            // Raced against the clock too, so custom transports cannot hang either
+            let result = match timeout {
+                Some(after) => tokio::select! {
+                    result = self.execute(req) => result,
+                    _ = self.clock.sleep(after) => Err(FogswapSdkError::Timeout { endpoint: endpoint.to_string(), after }.into()),
                 },
-                _ => return Err(FogswapSdkError::UnsupportedMethod.into()),
+                None => self.execute(req).await,
+            };
+            if let Ok(resp) = &result {
+                self.intercept_response(req_method, endpoint, url, resp, sent.elapsed());
             }
+            result.map_err(|e| match (e.downcast_ref::<reqwest::Error>(), timeout) {
+                (Some(re), Some(after)) if re.is_timeout() => (FogswapSdkError::Timeout { endpoint: endpoint.to_string(), after }.into(), true),
+                (Some(re), _) if self.client_options.has_pinned_certificates() && transport::is_certificate_error(re) => {
+                    (FogswapSdkError::CertificatePinning(config::redact_url(url)).into(), false)
+                }
+                _ => (e, true),
+            })
         };
+        let mut resp = send(token.clone()).await?;
+
+        // Refresh the credentials once and replay the request if they expired
+        if resp.status == reqwest::StatusCode::UNAUTHORIZED
+            && let (Some(auth), Some(stale)) = (&self.auth, &token) {
+            let fresh = auth.refresh(stale).await.map_err(|e| (e, false))?;
+            resp = send(Some(fresh)).await?;
+        }
+
+        #[cfg(feature = "metrics")]
+        if resp.status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            telemetry::rate_limited(endpoint);
+        }
+        self.decode_response(endpoint, resp, started.elapsed())
+    }
+
+    /// Map a response to its JSON body or an error; the flag tells whether the failure is transient
+    ///
+    /// Never panics, whatever the status, headers and body.
+    pub(crate) fn decode_response(
+        &self,
+        endpoint: &str,
+        resp: TransportResponse,
+        latency: std::time::Duration,
+    ) -> std::result::Result<(Value, ResponseMeta), (anyhow::Error, bool)> {
+        if matches!(resp.status, reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN) {
+            let e = format!("credentials rejected with status {}", resp.status);
+            return Err((FogswapSdkError::AuthError(e).into(), false));
+        }
+
+        if resp.status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = resp.headers
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map(std::time::Duration::from_secs);
+            let retryable = self.retry.is_retryable_status(429);
+            return Err((FogswapSdkError::RateLimited { retry_after }.into(), retryable));
+        }
 
-        if resp.status() != 200 {   
-            return Err(FogswapSdkError::SendRequestError.into());
+        if resp.status != 200 {
+            let status = resp.status.as_u16();
+            let retryable = self.retry.is_retryable_status(status);
+            // Error pages are not always JSON
+            let body = serde_json::from_slice::<Value>(&resp.body).ok();
+            if resp.status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+                let retry_after = resp.headers.get(reqwest::header::RETRY_AFTER).and_then(|value| value.to_str().ok());
+                return Err((FogswapSdkError::service_unavailable(body.as_ref(), retry_after).into(), retryable));
+            }
+            let e = FogswapSdkError::Http { endpoint: endpoint.to_string(), status, body };
+            return Err((e.into(), retryable));
         }
 
-        let body = resp.json::<Value>().await?;
-        Ok(body)
+        let status = resp.status.as_u16();
+        let headers = resp.headers;
+        let text = String::from_utf8_lossy(&resp.body).into_owned();
+        if text.trim().is_empty() {
+            return Ok((Value::Null, ResponseMeta::new(status, headers, latency)));
+        }
+        // A proxy or CDN may answer 200 with an HTML page
+        let body = serde_json::from_str::<Value>(&text).map_err(|e| {
+            let e = FogswapSdkError::Decode { endpoint: endpoint.to_string(), reason: e.to_string(), body: Value::String(text.clone()) };
+            (e.into(), false)
+        })?;
+        Ok((body, ResponseMeta::new(status, headers, latency)))
+    }
 
+    fn build_request(
+        &self,
+        req_method: &reqwest::Method,
+        url: &str,
+        payload: &Option<Value>,
+        token: Option<&str>,
+    ) -> Result<reqwest::RequestBuilder> {
+        let mut req = match *req_method {
+            reqwest::Method::GET => {
+                let mut req = self.client.get(url);
+                if let Some(payload) = payload {
+                    let params: HashMap<&String, &Value> = payload
+                        .as_object()
+                        .into_iter()
+                        .flatten()
+                        .filter(|(_, v)| !v.is_null())
+                        .collect();
+                    req = req.query(&params);
+                }
+                req
+            },
+            reqwest::Method::POST => {
+                let mut req = self.client.post(url).header("Content-Type", "application/json");
+                if let Some(payload) = payload {
+                    req = req.json(payload);
+                }
+                req
+            },
+            _ => return Err(FogswapSdkError::UnsupportedMethod.into()),
+        };
+        if let Some(token) = token {
+            req = req.bearer_auth(token);
+        }
+        if let Some(api_key) = &self.api_key {
+            req = api_key.apply(req);
+        }
+        if let Some(partner_id) = &self.partner_id {
+            req = req.header("x-partner-id", partner_id);
+        }
+        Ok(req)
     }
 
     /// Get the list of available tokens
     /// # Returns
     /// * `Vec<TokenList>` - A vector of token lists grouped by network
     /// # Errors
-    /// * `FogswapSdkError::GetAvailableCoinsError` - If the token list cannot be retrieved
+    /// * `FogswapSdkError::Api` - If the token list cannot be retrieved
     /// # Examples
     /// ```
     /// use fogswap_sdk_rust::FogswapSdk;
@@ -106,20 +557,123 @@ impl FogswapSdk {
     /// # }
     /// ```
     pub async fn get_token_list(&self) -> Result<Vec<TokenList>> {
-        let endpoint = "/market/tokens";
+        Ok(self.get_token_list_with_meta().await?.data)
+    }
 
-        let resp = self.send_request(reqwest::Method::GET, endpoint, None).await?;
+    /// Get the list of available tokens, filtered server-side
+    /// # Arguments
+    /// * `filter` - Network, search term and native-only filters
+    /// # Returns
+    /// * `Vec<TokenList>` - The matching token lists grouped by network
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the token list cannot be retrieved
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, TokenListFilter};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tokens = sdk.get_token_list_filtered(TokenListFilter {
+    ///     network: Some("eth".to_string()),
+    ///     search: Some("usdc".to_string()),
+    ///     ..Default::default()
+    /// }).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_token_list_filtered(&self, filter: TokenListFilter) -> Result<Vec<TokenList>> {
+        Ok(self.fetch_token_list(Some(serde_json::to_value(filter)?)).await?.data)
+    }
 
-        if let Some(e) = resp.get("error").unwrap().as_object() {
-            let e=e.get("message").unwrap().as_str().unwrap();
-            return Err(FogswapSdkError::GetAvailableCoinsError(e.to_string()).into());
+    /// Get every page of the token list and merge them
+    /// # Arguments
+    /// * `filter` - Server-side filters; `page` is ignored
+    /// * `page_size` - Number of networks requested per page
+    /// # Returns
+    /// * `Vec<TokenList>` - All token lists, merged by network
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If any page cannot be retrieved
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, TokenListFilter};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tokens = sdk.get_token_list_all_pages(TokenListFilter::default(), 50).await?;
+    /// println!("Found {} networks", tokens.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_token_list_all_pages(&self, filter: TokenListFilter, page_size: u32) -> Result<Vec<TokenList>> {
+        let mut merged: Vec<TokenList> = Vec::new();
+        let mut pages = std::pin::pin!(self.token_list_stream(filter, page_size));
+        while let Some(list) = pages.try_next().await? {
+            match merged.iter_mut().find(|m| m.network == list.network) {
+                Some(existing) => existing.tokens.extend(list.tokens),
+                None => merged.push(list),
+            }
         }
-        
-        let resp=resp.get("result").unwrap();
-        let coins=serde_json::from_value::<Vec<TokenList>>(resp.to_owned())?;
+        Ok(merged)
+    }
+
+    /// Stream the token list page by page without holding it all in memory
+    ///
+    /// Pages are requested lazily; the stream ends after the first page with fewer
+    /// than `page_size` entries.
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use fogswap_sdk_rust::{FogswapSdk, TokenListFilter};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let mut lists = std::pin::pin!(sdk.token_list_stream(TokenListFilter::default(), 20));
+    /// while let Some(list) = lists.try_next().await? {
+    ///     println!("{}: {} tokens", list.network, list.tokens.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn token_list_stream(
+        &self,
+        filter: TokenListFilter,
+        page_size: u32
+    ) -> PageStream<'_, TokenList> {
+        let page_size = page_size.max(1);
+        PageStream::new(PageCursor::default(), page_size, move |cursor| {
+            let filter = TokenListFilter { page: Some(cursor.page), limit: Some(page_size), ..filter.clone() };
+            async move { Ok(self.get_token_list_filtered(filter).await?.into()) }
+        })
+    }
+
+    /// Get the list of available tokens together with the response metadata
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tokens = sdk.get_token_list_with_meta().await?;
+    /// println!("{} networks in {:?} (request id {:?})", tokens.data.len(), tokens.meta.latency, tokens.meta.request_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_token_list_with_meta(&self) -> Result<WithMeta<Vec<TokenList>>> {
+        let coins = self.fetch_token_list(None).await?;
+        self.remember(degraded::TOKEN_LIST_KEY, &coins.data);
+        self.token_cache.put(&coins.data, self.clock.now());
         Ok(coins)
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+    async fn fetch_token_list(&self, params: Option<Value>) -> Result<WithMeta<Vec<TokenList>>> {
+        let endpoint = "/market/tokens";
+
+        let (resp, meta) = self.send_request_meta(reqwest::Method::GET, endpoint, params).await?;
+        let coins = parse_result(endpoint, resp)?;
+        Ok(WithMeta { data: coins, meta })
+    }
+
     /// Get the quote for an swap
     /// # Arguments
     /// * `amount_from` - The amount of the token to swap
@@ -132,7 +686,7 @@ impl FogswapSdk {
     /// # Returns
     /// * `QuoteResponse` - The quote for the swap
     /// # Errors
-    /// * `FogswapSdkError::GetEstimatedExchangeAmountError` - If the quote for the swap is not found
+    /// * `FogswapSdkError::Api` - If the quote for the swap is not found
     /// # Examples
     /// ```
     /// use fogswap_sdk_rust::{FogswapSdk, TxType};
@@ -152,11 +706,8 @@ impl FogswapSdk {
     /// # Ok(())
     /// # }
     /// ```
-    /// # Panics
-    /// * If the quote for the swap is not found
-    /// * If the request to the Fogswap API fails
-    /// * If the response from the Fogswap API is not valid
-    /// * If the response from the Fogswap API is not valid
+    #[deprecated(note = "positional arguments are easy to swap; use `get_quote_for(&from, &to, amount_from)` or `get_quote_with(&QuoteRequest::new(...))`")]
+    #[allow(clippy::too_many_arguments)]
     pub async fn get_quote(
         &self,
         amount_from: f64,
@@ -167,31 +718,139 @@ impl FogswapSdk {
         tx_type: Option<TxType>,
         is_use_xmr: Option<bool>
     ) -> Result<QuoteResponse> {
+        self.get_quote_with(&QuoteRequest {
+            amount_from,
+            network_from: network_from.to_string(),
+            contract_address_from: contract_address_from.to_string(),
+            network_to: network_to.to_string(),
+            contract_address_to: contract_address_to.to_string(),
+            tx_type,
+            is_use_xmr,
+            ..Default::default()
+        }).await
+    }
+
+    /// Get the quote for swapping `amount_from` of `from` into `to`
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the quote is not retrieved
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, FogswapSdk};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let quote = sdk.get_quote_for(&Asset::native("sol"), &Asset::new("eth", "USDT"), 1.0).await?;
+    /// println!("Receive {} USDT", quote.amount_to);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_quote_for(&self, from: &Asset, to: &Asset, amount_from: f64) -> Result<QuoteResponse> {
+        self.get_quote_with(&QuoteRequest::new(from, to, amount_from)).await
+    }
+
+    /// Get the quote for receiving exactly `amount_to` of `to`, e.g. a fixed payout
+    ///
+    /// `amount_from` of the response is the amount of `from` to deposit.
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the quote is not retrieved
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, FogswapSdk};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let quote = sdk.get_quote_reverse_for(&Asset::native("sol"), &Asset::new("eth", "USDT"), 100.0).await?;
+    /// println!("Deposit {} SOL to receive 100 USDT", quote.amount_from);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_quote_reverse_for(&self, from: &Asset, to: &Asset, amount_to: f64) -> Result<QuoteResponse> {
+        self.get_quote_with(&QuoteRequest::receiving(from, to, amount_to)).await
+    }
+
+    /// Get the quote for receiving exactly `amount_to`
+    #[deprecated(note = "positional arguments are easy to swap; use `get_quote_reverse_for(&from, &to, amount_to)`")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_quote_reverse(
+        &self,
+        amount_to: f64,
+        network_from: &str,
+        contract_address_from: &str,
+        network_to: &str,
+        contract_address_to: &str,
+        tx_type: Option<TxType>,
+        is_use_xmr: Option<bool>
+    ) -> Result<QuoteResponse> {
+        self.get_quote_with(&QuoteRequest {
+            amount_to: Some(amount_to),
+            network_from: network_from.to_string(),
+            contract_address_from: contract_address_from.to_string(),
+            network_to: network_to.to_string(),
+            contract_address_to: contract_address_to.to_string(),
+            tx_type,
+            is_use_xmr,
+            ..Default::default()
+        }).await
+    }
+
+    /// Get the quote for a swap from a `QuoteRequest`
+    /// # Arguments
+    /// * `req` - The swap to quote
+    /// # Returns
+    /// * `QuoteResponse` - The quote for the swap
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the quote for the swap is not found
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, QuoteRequest};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let quote = sdk.get_quote_with(&QuoteRequest {
+    ///     amount_from: 1.0,
+    ///     network_from: "sol".to_string(),
+    ///     contract_address_from: "SOL".to_string(),
+    ///     network_to: "eth".to_string(),
+    ///     contract_address_to: "ETH".to_string(),
+    ///     promo_code: Some("WELCOME10".to_string()),
+    ///     ..Default::default()
+    /// }).await?;
+    /// println!("Discount applied: {:?}", quote.promo_discount);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_quote_with(&self, req: &QuoteRequest) -> Result<QuoteResponse> {
+        Ok(self.get_quote_with_meta(req).await?.data)
+    }
+
+    /// Get the quote for a swap together with the response metadata
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all, fields(
+        network_from = %req.network_from, contract_address_from = %req.contract_address_from,
+        network_to = %req.network_to, contract_address_to = %req.contract_address_to,
+    )))]
+    pub async fn get_quote_with_meta(&self, req: &QuoteRequest) -> Result<WithMeta<QuoteResponse>> {
+        #[cfg(feature = "metrics")]
+        let started = rt::Instant::now();
+        let result = self.send_quote(req).await;
+        #[cfg(feature = "metrics")]
+        telemetry::operation("get_quote", &result, started.elapsed());
+        result
+    }
+
+    async fn send_quote(&self, req: &QuoteRequest) -> Result<WithMeta<QuoteResponse>> {
 
         let endpoint = "/transaction/quote";
 
-        let resp=self.send_request(
+        let req = req.prepared(&self.precision, self.partner_id.as_ref(), self.partner_fee_bps)?;
+
+        let (resp, meta)=self.send_request_meta(
             reqwest::Method::GET, 
             endpoint, 
-            Some(json!({
-                "amount_from": amount_from,
-                "network_from": network_from,
-                "contract_address_from": contract_address_from,
-                "network_to": network_to,
-                "contract_address_to": contract_address_to,
-                "tx_type": tx_type,
-                "is_use_xmr":is_use_xmr,
-            })),
+            Some(req.params()?),
         ).await?;
 
-        if let Some(e) = resp.get("error").unwrap().as_object() {
-            let e=e.get("message").unwrap().as_str().unwrap();
-            return Err(FogswapSdkError::GetEstimatedExchangeAmountError(e.to_string()).into());
-        }
-        let resp=resp.get("result").unwrap();
-        let estimated_exchange_amount=serde_json::from_value::<QuoteResponse>(resp.to_owned())?;
-        Ok(estimated_exchange_amount)
-   
+        let estimated_exchange_amount=parse_result(endpoint, resp)?;
+        Ok(WithMeta { data: estimated_exchange_amount, meta })
     }
 
     /// Create a new transaction
@@ -208,7 +867,10 @@ impl FogswapSdk {
     /// # Returns
     /// * `TransactionInfo` - The information about the transaction
     /// # Errors
-    /// * `FogswapSdkError::CreateTransactionError` - If the transaction is not created
+    /// * `FogswapSdkError::Api` - If the transaction is not created
+    ///
+    /// With duplicate protection enabled, an identical call made while the first one is
+    /// in flight or within the window returns the same transaction instead of creating a new one.
     /// # Examples
     /// ```
     /// use fogswap_sdk_rust::{FogswapSdk, TxType};
@@ -231,11 +893,8 @@ impl FogswapSdk {
     /// # Ok(())
     /// # }
     /// ```
-    /// # Panics
-    /// * If the transaction is not created
-    /// * If the request to the Fogswap API fails
-    /// * If the response from the Fogswap API is not valid
-    /// * If the response from the Fogswap API is not valid
+    #[deprecated(note = "positional arguments are easy to swap; use `create_transaction_with(&CreateTransactionRequest::builder()...build())`")]
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_transaction(
         &self,
         network_from: &str,
@@ -248,32 +907,154 @@ impl FogswapSdk {
         tx_type: Option<TxType>,
         is_use_xmr: Option<bool>
     ) -> Result<TransactionInfo> {
+        self.create_transaction_with(&CreateTransactionRequest {
+            network_from: network_from.to_string(),
+            contract_address_from: contract_address_from.to_string(),
+            network_to: network_to.to_string(),
+            contract_address_to: contract_address_to.to_string(),
+            amount_from,
+            payout_address: payout_address.to_string(),
+            payout_extra_id: payout_extra_id.clone(),
+            tx_type,
+            is_use_xmr,
+            ..Default::default()
+        }).await
+    }
+
+    /// Create a new transaction from a `CreateTransactionRequest`
+    ///
+    /// When a store is configured, the request is recorded as a pending intent under its
+    /// `external_id` (generated if missing) before the POST, so `reconcile()` can recover it
+    /// after a crash. `client_metadata` is kept locally under the new transaction id; a store
+    /// failing to save it is logged (feature `tracing`) and never fails the create.
+    ///
+    /// With `min_amount_to` set, the pair is quoted again first and nothing is created
+    /// when the quote is below it; the floor is also sent to the API.
+    ///
+    /// Every create carries an `idempotency_key`, generated when missing and returned on
+    /// `TransactionInfo`. Calling again with the same key after a timeout returns the
+    /// transaction created the first time instead of a duplicate.
+    /// # Arguments
+    /// * `req` - The transaction to create
+    /// # Returns
+    /// * `TransactionInfo` - The information about the transaction
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the transaction is not created
+    /// * `FogswapSdkError::BelowMinimumReceived` - If the fresh quote is below `min_amount_to`
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{CreateTransactionRequest, FogswapSdk};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tx_info = sdk.create_transaction_with(&CreateTransactionRequest {
+    ///     network_from: "sol".to_string(),
+    ///     contract_address_from: "SOL".to_string(),
+    ///     network_to: "eth".to_string(),
+    ///     contract_address_to: "ETH".to_string(),
+    ///     amount_from: 0.5,
+    ///     payout_address: "YOUR_RECEIVE_ADDRESS_HERE".to_string(),
+    ///     external_id: Some("order-1042".to_string()),
+    ///     ..Default::default()
+    /// }).await?;
+    /// println!("Transaction ID: {}", tx_info.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all, fields(
+        network_from = %req.network_from, contract_address_from = %req.contract_address_from,
+        network_to = %req.network_to, contract_address_to = %req.contract_address_to,
+        tx_id = tracing::field::Empty,
+    )))]
+    pub async fn create_transaction_with(&self, req: &CreateTransactionRequest) -> Result<TransactionInfo> {
+        #[cfg(feature = "metrics")]
+        let started = rt::Instant::now();
+        let result = self.create_transaction_checked(req).await;
+        #[cfg(feature = "metrics")]
+        telemetry::operation("create_transaction", &result, started.elapsed());
+        result
+    }
+
+    async fn create_transaction_checked(&self, req: &CreateTransactionRequest) -> Result<TransactionInfo> {
+        req.privacy_mode()?;
+        // Keyed before the idempotency key and external id are generated
+        let dedup_key = self.dedup.as_ref().map(|guard| guard.key(req, &self.tolerance));
+        let mut req = req.clone();
+        req.idempotency_key.get_or_insert_with(|| uuid::Uuid::new_v4().to_string());
+        let req = &req;
+        #[cfg(feature = "validation")]
+        self.check_request_addresses(req)?;
+        self.limits.check(req, &self.tolerance)?;
+        if req.min_amount_to.is_some() {
+            let quote = self.get_quote_with(&req.min_received_quote()).await?;
+            req.check_min_received(quote.amount_to, &self.tolerance)?;
+        }
+        let tx_info = self.create_transaction_journaled(req, dedup_key).await?;
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("tx_id", tx_info.id.as_str());
+        self.track(&tx_info);
+        if let Some(metadata) = &req.client_metadata {
+            // The swap exists: failing the call now would make a retry create a second one
+            if let Err(_e) = self.attach_client_metadata(&tx_info.id, metadata.clone()) {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(tx_id = %tx_info.id, error = %_e, "client metadata not saved to the store");
+            }
+        }
+        Ok(tx_info)
+    }
+
+    async fn create_transaction_journaled(&self, req: &CreateTransactionRequest, dedup_key: Option<String>) -> Result<TransactionInfo> {
+        let Some(store) = &self.store else {
+            return self.create_transaction_deduped(req, dedup_key).await;
+        };
+
+        let mut req = req.clone();
+        let external_id = req.external_id
+            .get_or_insert_with(|| uuid::Uuid::new_v4().to_string())
+            .clone();
+        store.save_intent(&store::PendingIntent::new(req.clone()))?;
+
+        match self.create_transaction_deduped(&req, dedup_key).await {
+            Ok(tx_info) => {
+                store.complete_intent(&external_id, &tx_info.id)?;
+                Ok(tx_info)
+            }
+            Err(e) => {
+                // Only a definite API rejection proves nothing was created
+                if matches!(e.downcast_ref::<FogswapSdkError>(), Some(FogswapSdkError::Api { .. })) {
+                    store.discard_intent(&external_id)?;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn create_transaction_deduped(&self, req: &CreateTransactionRequest, dedup_key: Option<String>) -> Result<TransactionInfo> {
+        let (Some(guard), Some(key)) = (&self.dedup, dedup_key) else {
+            return self.send_create_transaction(req).await;
+        };
+
+        let slot = guard.slot(key, self.clock.now());
+        let tx_info = slot.get_or_try_init(|| self.send_create_transaction(req)).await?;
+        Ok(tx_info.clone())
+    }
+
+    async fn send_create_transaction(&self, req: &CreateTransactionRequest) -> Result<TransactionInfo> {
         let endpoint = "/transaction/create";
 
+        let payload = req.payload(&self.precision, self.partner_id.as_ref(), self.partner_fee_bps);
+
         let resp=self.send_request(
             reqwest::Method::POST, 
             endpoint, 
-            Some(json!({
-                "network_from": network_from,
-                "contract_address_from": contract_address_from,
-                "amount_from": amount_from,
-                "network_to": network_to,
-                "contract_address_to": contract_address_to,
-                "payout_address": payout_address,
-                "payout_extra_id": payout_extra_id,
-                "tx_type": tx_type,
-                "is_use_xmr": is_use_xmr,
-            }))
+            Some(payload)
         ).await?;
 
-        if let Some(e) = resp.get("error").unwrap().as_object() {
-            let e=e.get("message").unwrap().as_str().unwrap();
-            return Err(FogswapSdkError::CreateTransactionError(e.to_string()).into());
+        let mut tx_info: TransactionInfo = parse_result(endpoint, resp)?;
+        if tx_info.idempotency_key.is_none() {
+            tx_info.idempotency_key = req.idempotency_key.clone();
         }
-
-        let resp=resp.get("result").unwrap();
-        let tx_info=serde_json::from_value::<TransactionInfo>(resp.to_owned());
-        Ok(tx_info?)
+        Ok(tx_info)
     }
 
     /// Get the information about a transaction
@@ -282,7 +1063,7 @@ impl FogswapSdk {
     /// # Returns
     /// * `TransactionInfo` - The information about the transaction
     /// # Errors
-    /// * `FogswapSdkError::GetTransactionInfoError` - If the transaction information is not found
+    /// * `FogswapSdkError::Api` - If the transaction information is not found
     /// # Examples
     /// ```
     /// use fogswap_sdk_rust::FogswapSdk;
@@ -296,17 +1077,22 @@ impl FogswapSdk {
     /// # Ok(())
     /// # }
     /// ```
-    /// # Panics
-    /// * If the transaction information is not found
-    /// * If the request to the Fogswap API fails
-    /// * If the response from the Fogswap API is not valid
     pub async fn get_transaction_info(
         &self,
         id: &str
     ) -> Result<TransactionInfo> {
+        Ok(self.get_transaction_info_with_meta(id).await?.data)
+    }
+
+    /// Get the information about a transaction together with the response metadata
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all, fields(tx_id = %id)))]
+    pub async fn get_transaction_info_with_meta(
+        &self,
+        id: &str
+    ) -> Result<WithMeta<TransactionInfo>> {
         let endpoint = "/transaction/info";
 
-        let resp=self.send_request(
+        let (resp, meta)=self.send_request_meta(
             reqwest::Method::GET, 
             endpoint, 
             Some(json!({
@@ -314,14 +1100,129 @@ impl FogswapSdk {
             }))
         ).await?;
 
-        if let Some(e) = resp.get("error").unwrap().as_object() {
-            let e=e.get("message").unwrap().as_str().unwrap();
-            return Err(FogswapSdkError::GetTransactionInfoError(e.to_string()).into());
-        }
+        let tx_info=parse_result(endpoint, resp)?;
+        self.track(&tx_info);
+        Ok(WithMeta { data: tx_info, meta })
+    }
 
-        let resp=resp.get("result").unwrap();
-        let tx_info=serde_json::from_value::<TransactionInfo>(resp.to_owned())?;
-        Ok(tx_info)
+    /// Get the minimum and maximum swap amounts for a pair
+    /// # Arguments
+    /// * `pair` - The swap direction
+    /// * `tx_type` - The type of the transaction
+    /// # Returns
+    /// * `PairLimits` - The minimum and maximum `amount_from`
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the limits cannot be retrieved
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, Pair, TxType};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let limits = sdk.get_pair_limits(&Pair::new("sol", "SOL", "eth", "ETH"), Some(TxType::Standard)).await?;
+    /// println!("Minimum: {}", limits.min_amount);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all, fields(
+        network_from = %pair.network_from, contract_address_from = %pair.contract_address_from,
+        network_to = %pair.network_to, contract_address_to = %pair.contract_address_to,
+    )))]
+    pub async fn get_pair_limits(
+        &self,
+        pair: &Pair,
+        tx_type: Option<TxType>
+    ) -> Result<PairLimits> {
+        let endpoint = "/market/limits";
+
+        let resp=self.send_request(
+            reqwest::Method::GET,
+            endpoint,
+            Some(json!({
+                "network_from": pair.network_from,
+                "contract_address_from": pair.contract_address_from,
+                "network_to": pair.network_to,
+                "contract_address_to": pair.contract_address_to,
+                "tx_type": tx_type,
+            }))
+        ).await?;
+
+        let limits = parse_result(endpoint, resp)?;
+        self.limits.remember(pair, tx_type, &limits);
+        Ok(limits)
+    }
+
+    /// Get the limits of many pairs concurrently
+    /// # Arguments
+    /// * `pairs` - The swap directions
+    /// * `tx_type` - The type of the transactions, the same for every pair
+    /// * `max_concurrency` - Maximum number of requests in flight at once
+    /// # Returns
+    /// * `HashMap<Pair, Result<PairLimits>>` - The limits, or the error, per pair
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, Pair, TxType};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let pairs = [Pair::new("sol", "SOL", "eth", "ETH"), Pair::new("eth", "ETH", "sol", "SOL")];
+    /// let limits = sdk.get_limits_for_pairs(&pairs, Some(TxType::Standard), 4).await;
+    /// for (pair, limits) in &limits {
+    ///     println!("{}->{}: {:?}", pair.network_from, pair.network_to, limits.as_ref().map(|l| l.min_amount));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_limits_for_pairs(
+        &self,
+        pairs: &[Pair],
+        tx_type: Option<TxType>,
+        max_concurrency: usize
+    ) -> HashMap<Pair, Result<PairLimits>> {
+        futures::stream::iter(pairs)
+            .map(|pair| {
+                let tx_type = tx_type.clone();
+                async move { (pair.clone(), self.get_pair_limits(pair, tx_type).await) }
+            })
+            .buffer_unordered(max_concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Get quotes for many requests concurrently
+    ///
+    /// Every request still passes the client rate limit and request queue, if configured.
+    /// # Arguments
+    /// * `requests` - The quotes to fetch
+    /// * `max_concurrency` - Maximum number of requests in flight at once
+    /// # Returns
+    /// * `Vec<Result<QuoteResponse>>` - The quote, or the error, in the order of `requests`
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, FogswapSdk, QuoteRequest};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_rate_limit(5.0, 5);
+    /// let sol = Asset::new("sol", "SOL");
+    /// let requests = vec![
+    ///     QuoteRequest::new(&sol, &Asset::new("eth", "ETH"), 1.0),
+    ///     QuoteRequest::new(&sol, &Asset::new("btc", "BTC"), 1.0),
+    /// ];
+    /// for quote in sdk.get_quotes(requests, 4).await {
+    ///     match quote {
+    ///         Ok(quote) => println!("{} -> {}", quote.network_to, quote.amount_to),
+    ///         Err(e) => println!("no quote: {}", e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_quotes(&self, requests: Vec<QuoteRequest>, max_concurrency: usize) -> Vec<Result<QuoteResponse>> {
+        futures::stream::iter(requests)
+            .map(|req| async move { self.get_quote_with(&req).await })
+            .buffered(max_concurrency.max(1))
+            .collect()
+            .await
     }
 
 }
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::Result;
+
+use crate::error::FogswapSdkError;
+use crate::precision::AmountTolerance;
+use crate::req_structs::{CreateTransactionRequest, Pair};
+use crate::resp_structs::{PairLimits, TxType};
+
+/// Limits returned by `get_pair_limits`, per pair and transaction type
+#[derive(Debug, Default)]
+pub(crate) struct KnownLimits {
+    limits: Mutex<HashMap<(Pair, Option<TxType>), PairLimits>>,
+}
+
+impl KnownLimits {
+
+    pub fn remember(&self, pair: &Pair, tx_type: Option<TxType>, limits: &PairLimits) {
+        let mut known = self.limits.lock().unwrap_or_else(|e| e.into_inner());
+        known.insert((pair.clone(), tx_type), limits.clone());
+    }
+
+    /// Check the amount of `req` against the limits of its pair, if they are known
+    pub fn check(&self, req: &CreateTransactionRequest, tolerance: &AmountTolerance) -> Result<()> {
+        let pair = Pair::new(&req.network_from, &req.contract_address_from, &req.network_to, &req.contract_address_to);
+        let known = self.limits.lock().unwrap_or_else(|e| e.into_inner());
+        match known.get(&(pair, req.tx_type.clone())) {
+            Some(limits) => check_amount(req.amount_from, limits, tolerance),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Fail with `FogswapSdkError::AmountOutOfLimits` if `amount` is outside `limits`
+pub(crate) fn check_amount(amount: f64, limits: &PairLimits, tolerance: &AmountTolerance) -> Result<()> {
+    if !tolerance.at_least(amount, limits.min_amount) {
+        let e = format!("amount {} is below the minimum of {}", amount, limits.min_amount);
+        return Err(FogswapSdkError::AmountOutOfLimits(e).into());
+    }
+    if let Some(max) = limits.max_amount && !tolerance.at_most(amount, max) {
+        let e = format!("amount {} is above the maximum of {}", amount, max);
+        return Err(FogswapSdkError::AmountOutOfLimits(e).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::error::FogswapSdkError;
+    use crate::testing::MockTransport;
+    use crate::{CreateTransactionRequest, FogswapSdk, Pair, TxType};
+
+    #[tokio::test]
+    async fn limits_for_pairs_are_fetched_and_remembered_per_tx_type() {
+        let mock = Arc::new(MockTransport::new());
+        let sdk = FogswapSdk::new().with_transport(mock.clone());
+        let pairs = [Pair::new("sol", "SOL", "eth", "ETH"), Pair::new("eth", "ETH", "sol", "SOL")];
+
+        let limits = sdk.get_limits_for_pairs(&pairs, Some(TxType::Private), 2).await;
+        assert!(limits.values().all(|limits| limits.is_ok()));
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(|r| r.query["tx_type"] == "private"));
+
+        let create = |tx_type| CreateTransactionRequest {
+            network_from: "sol".to_string(),
+            contract_address_from: "SOL".to_string(),
+            network_to: "eth".to_string(),
+            contract_address_to: "ETH".to_string(),
+            amount_from: 5000.0,
+            payout_address: "0xpayout".to_string(),
+            tx_type: Some(tx_type),
+            ..Default::default()
+        };
+        let e = sdk.create_transaction_with(&create(TxType::Private)).await.unwrap_err();
+        assert!(matches!(e.downcast_ref(), Some(FogswapSdkError::AmountOutOfLimits(_))));
+        // Standard limits were never fetched, so the API checks the amount
+        sdk.create_transaction_with(&create(TxType::Standard)).await.unwrap();
+    }
+}
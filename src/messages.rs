@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::error::FogswapSdkError;
+
+/// What went wrong, from the point of view of an end user
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ApiErrorCode {
+    Offline,
+    Unauthorized,
+    RateLimited,
+    ServiceUnavailable,
+    Timeout,
+    InvalidAddress,
+    AmountOutOfLimits,
+    QuoteExpired,
+    /// The rate moved beyond a slippage, value-loss or minimum-received guard
+    PriceMoved,
+    NoRoute,
+    /// Error code reported by the API
+    Api(String),
+    Unknown,
+}
+
+impl ApiErrorCode {
+
+    /// Classify an error returned by the SDK
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{ApiErrorCode, FogswapSdkError};
+    ///
+    /// let e = anyhow::Error::from(FogswapSdkError::Http { endpoint: "/transaction/quote".to_string(), status: 503, body: None });
+    /// assert_eq!(ApiErrorCode::of(&e), ApiErrorCode::ServiceUnavailable);
+    /// ```
+    pub fn of(error: &anyhow::Error) -> Self {
+        if let Some(e) = error.chain().find_map(|e| e.downcast_ref::<FogswapSdkError>()) {
+            return match e {
+                FogswapSdkError::Offline => ApiErrorCode::Offline,
+                FogswapSdkError::AuthError(_) => ApiErrorCode::Unauthorized,
+                FogswapSdkError::RateLimited { .. } => ApiErrorCode::RateLimited,
+                FogswapSdkError::Http { status: 500..=599, .. } | FogswapSdkError::ServiceUnavailable { .. } => ApiErrorCode::ServiceUnavailable,
+                FogswapSdkError::Api { code: Some(code), .. } => ApiErrorCode::Api(code.clone()),
+                FogswapSdkError::DeadlineExceeded { .. } | FogswapSdkError::Timeout { .. } => ApiErrorCode::Timeout,
+                FogswapSdkError::InvalidAddress(_) => ApiErrorCode::InvalidAddress,
+                FogswapSdkError::AmountOutOfLimits(_) => ApiErrorCode::AmountOutOfLimits,
+                FogswapSdkError::QuoteExpired { .. } => ApiErrorCode::QuoteExpired,
+                FogswapSdkError::SlippageExceeded { .. }
+                | FogswapSdkError::ValueLossExceeded { .. }
+                | FogswapSdkError::BelowMinimumReceived { .. } => ApiErrorCode::PriceMoved,
+                FogswapSdkError::NoRoute(_) => ApiErrorCode::NoRoute,
+                _ => ApiErrorCode::Unknown,
+            };
+        }
+        match error.chain().find_map(|e| e.downcast_ref::<reqwest::Error>()) {
+            Some(e) if e.is_timeout() => ApiErrorCode::Timeout,
+            Some(e) if crate::transport::is_connect_error(e) => ApiErrorCode::Offline,
+            _ => ApiErrorCode::Unknown,
+        }
+    }
+
+    /// User-presentable English message
+    pub fn message(&self) -> &'static str {
+        match self {
+            ApiErrorCode::Offline => "Can't reach the exchange, check your connection",
+            ApiErrorCode::Unauthorized => "This app is not authorized to use the exchange",
+            ApiErrorCode::RateLimited => "Too many requests, please wait a moment",
+            ApiErrorCode::ServiceUnavailable => "The exchange is temporarily unavailable",
+            ApiErrorCode::Timeout => "The exchange took too long to answer",
+            ApiErrorCode::InvalidAddress => "This address is not valid for the selected network",
+            ApiErrorCode::AmountOutOfLimits => "This amount is too small or too large for this swap",
+            ApiErrorCode::QuoteExpired => "The quote expired, please request a new one",
+            ApiErrorCode::PriceMoved => "The rate changed, please review the new quote",
+            ApiErrorCode::NoRoute => "This swap is not available right now",
+            ApiErrorCode::Api(_) => "The exchange rejected the request",
+            ApiErrorCode::Unknown => "Something went wrong, please try again",
+        }
+    }
+}
+
+type MessageHook = Arc<dyn Fn(&ApiErrorCode, &anyhow::Error) -> Option<String> + Send + Sync>;
+
+/// Error messages with per-code overrides and an optional hook, e.g. for translations
+///
+/// `describe()` asks the hook first, then the overrides, and falls back to
+/// `ApiErrorCode::message()`.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{ApiErrorCode, ErrorMessages, FogswapSdkError};
+///
+/// let de = ErrorMessages::default()
+///     .with(ApiErrorCode::QuoteExpired, "Das Angebot ist abgelaufen")
+///     .with_hook(|code, _error| match code {
+///         ApiErrorCode::Api(code) if code == "PAIR_DISABLED" => Some("Dieses Paar ist deaktiviert".to_string()),
+///         _ => None,
+///     });
+/// let e = anyhow::Error::from(FogswapSdkError::QuoteExpired { requotes: 3 });
+/// assert_eq!(de.describe(&e), "Das Angebot ist abgelaufen");
+/// ```
+#[derive(Clone, Default)]
+pub struct ErrorMessages {
+    overrides: HashMap<ApiErrorCode, String>,
+    hook: Option<MessageHook>,
+}
+
+impl Debug for ErrorMessages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErrorMessages").field("overrides", &self.overrides).field("hook", &self.hook.is_some()).finish()
+    }
+}
+
+impl ErrorMessages {
+
+    pub fn with(mut self, code: ApiErrorCode, message: &str) -> Self {
+        self.overrides.insert(code, message.to_string());
+        self
+    }
+
+    /// Compute messages from the code and the error itself, returning `None` to fall back
+    pub fn with_hook(mut self, hook: impl Fn(&ApiErrorCode, &anyhow::Error) -> Option<String> + Send + Sync + 'static) -> Self {
+        self.hook = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn describe(&self, error: &anyhow::Error) -> String {
+        let code = ApiErrorCode::of(error);
+        if let Some(message) = self.hook.as_ref().and_then(|hook| hook(&code, error)) {
+            return message;
+        }
+        self.overrides.get(&code).cloned().unwrap_or_else(|| code.message().to_string())
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+
+use crate::metrics::Attempt;
+
+/// Transport details of a successful response
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    pub status: u16,
+    pub headers: HeaderMap,
+    /// Time from sending the final attempt to receiving its body
+    pub latency: Duration,
+    /// Server request id (`x-request-id` header), when present
+    pub request_id: Option<String>,
+    /// Every attempt of the call, the successful one last
+    pub attempts: Vec<Attempt>,
+    /// Time from the call to its result, retries and backoff included
+    pub total_elapsed: Duration,
+}
+
+impl ResponseMeta {
+    pub(crate) fn new(status: u16, headers: HeaderMap, latency: Duration) -> Self {
+        let request_id = headers
+            .get("x-request-id")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        Self { status, headers, latency, request_id, attempts: Vec::new(), total_elapsed: latency }
+    }
+
+    pub(crate) fn with_attempts(mut self, attempts: Vec<Attempt>, total_elapsed: Duration) -> Self {
+        self.attempts = attempts;
+        self.total_elapsed = total_elapsed;
+        self
+    }
+}
+
+/// A parsed result together with its response metadata
+#[derive(Debug, Clone)]
+pub struct WithMeta<T> {
+    pub data: T,
+    pub meta: ResponseMeta,
+}
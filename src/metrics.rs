@@ -0,0 +1,76 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::FogswapSdk;
+
+/// One try of an API call
+#[derive(Debug, Clone)]
+pub struct Attempt {
+    /// Time from sending this attempt to its outcome
+    pub elapsed: Duration,
+    /// Why the attempt failed, `None` if it succeeded
+    pub error: Option<String>,
+}
+
+/// Attempt history of a single API call
+#[derive(Debug, Clone)]
+pub struct CallMetrics {
+    pub endpoint: String,
+    pub attempts: Vec<Attempt>,
+    /// Time from the call to its result, backoff included
+    pub elapsed: Duration,
+    pub success: bool,
+}
+
+impl CallMetrics {
+
+    /// Number of attempts after the first one
+    pub fn retries(&self) -> usize {
+        self.attempts.len().saturating_sub(1)
+    }
+}
+
+/// Receiver of per-call metrics, e.g. to alert on elevated retry rates
+///
+/// Called once per API call, after its last attempt; implementations should not block.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{CallMetrics, MetricsSink};
+///
+/// #[derive(Debug)]
+/// struct LogRetries;
+///
+/// impl MetricsSink for LogRetries {
+///     fn record_call(&self, call: &CallMetrics) {
+///         if call.retries() > 0 {
+///             println!("{} needed {} retries in {:?}", call.endpoint, call.retries(), call.elapsed);
+///         }
+///     }
+/// }
+/// ```
+pub trait MetricsSink: Debug + Send + Sync {
+    fn record_call(&self, call: &CallMetrics);
+}
+
+impl FogswapSdk {
+
+    /// Report the attempt history of every API call to `sink`
+    /// # Examples
+    /// ```
+    /// # use fogswap_sdk_rust::{CallMetrics, MetricsSink};
+    /// # #[derive(Debug)]
+    /// # struct LogRetries;
+    /// # impl MetricsSink for LogRetries {
+    /// #     fn record_call(&self, _call: &CallMetrics) {}
+    /// # }
+    /// use std::sync::Arc;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// let sdk = FogswapSdk::new().with_metrics_sink(Arc::new(LogRetries));
+    /// ```
+    pub fn with_metrics_sink(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.metrics = Some(sink);
+        self
+    }
+}
@@ -0,0 +1,244 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use futures::future::join_all;
+
+use crate::rt::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::shutdown::BackgroundTask;
+use crate::FogswapSdk;
+
+/// Settings for latency-based mirror selection
+#[derive(Debug, Clone)]
+pub struct MirrorOptions {
+    /// How often `spawn_mirror_probe` measures every mirror
+    pub probe_interval: Duration,
+    /// Probe requests slower than this count as unhealthy
+    pub probe_timeout: Duration,
+    /// Only switch away from a healthy mirror when another one is faster by
+    /// more than this fraction (0.2 = 20%), to avoid flapping
+    pub hysteresis: f64,
+}
+
+impl Default for MirrorOptions {
+    fn default() -> Self {
+        Self {
+            probe_interval: Duration::from_secs(60),
+            probe_timeout: Duration::from_secs(10),
+            hysteresis: 0.2,
+        }
+    }
+}
+
+/// Latest probe result of one mirror
+#[derive(Debug, Clone)]
+pub struct MirrorStatus {
+    pub url: String,
+    /// Round-trip time of the last probe, `None` if it failed
+    pub latency: Option<Duration>,
+    pub active: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct MirrorSet {
+    options: MirrorOptions,
+    urls: Vec<String>,
+    state: Mutex<MirrorState>,
+}
+
+#[derive(Debug)]
+struct MirrorState {
+    active: usize,
+    latencies: Vec<Option<Duration>>,
+}
+
+impl MirrorSet {
+
+    pub fn new(urls: Vec<String>, options: MirrorOptions) -> Self {
+        let latencies = vec![None; urls.len()];
+        Self { options, urls, state: Mutex::new(MirrorState { active: 0, latencies }) }
+    }
+
+    pub fn active_url(&self) -> &str {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        &self.urls[state.active]
+    }
+
+    /// Fastest healthy mirror other than the active one, or the next one before any probe
+    pub fn alternate_url(&self) -> Option<&str> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let fastest = state.latencies
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != state.active)
+            .filter_map(|(i, l)| l.map(|l| (i, l)))
+            .min_by_key(|(_, l)| *l)
+            .map(|(i, _)| i);
+        let probed = state.latencies.iter().any(Option::is_some);
+        let next = (!probed && self.urls.len() > 1).then(|| (state.active + 1) % self.urls.len());
+        fastest.or(next).map(|i| self.urls[i].as_str())
+    }
+
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    pub fn options(&self) -> &MirrorOptions {
+        &self.options
+    }
+
+    fn record(&self, latencies: Vec<Option<Duration>>) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let current = latencies[state.active];
+        let best = latencies
+            .iter()
+            .enumerate()
+            .filter_map(|(i, l)| l.map(|l| (i, l)))
+            .min_by_key(|(_, l)| *l);
+
+        if let Some((best, best_latency)) = best {
+            let switch = match current {
+                None => true,
+                Some(current) => best_latency.as_secs_f64() < current.as_secs_f64() * (1.0 - self.options.hysteresis),
+            };
+            if switch {
+                state.active = best;
+            }
+        }
+        state.latencies = latencies;
+    }
+
+    fn statuses(&self) -> Vec<MirrorStatus> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        self.urls
+            .iter()
+            .zip(&state.latencies)
+            .enumerate()
+            .map(|(i, (url, latency))| MirrorStatus { url: url.clone(), latency: *latency, active: i == state.active })
+            .collect()
+    }
+}
+
+impl FogswapSdk {
+
+    /// Route requests to the fastest healthy of several base URLs
+    ///
+    /// The first URL is used until a probe has run. Typical use is a clearnet
+    /// and an onion endpoint of the same API.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, MirrorOptions};
+    ///
+    /// # async fn example() {
+    /// let sdk = FogswapSdk::new().with_mirrors(
+    ///     vec!["https://api.fogswap.io/v1".to_string(), "https://api2.fogswap.io/v1".to_string()],
+    ///     MirrorOptions::default()
+    /// );
+    /// let probe = sdk.spawn_mirror_probe();
+    /// # }
+    /// ```
+    pub fn with_mirrors(mut self, urls: Vec<String>, options: MirrorOptions) -> Self {
+        if let Some(first) = urls.first() {
+            self.base_url = first.clone();
+            self.mirrors = Some(std::sync::Arc::new(MirrorSet::new(urls, options)));
+        }
+        self
+    }
+
+    /// Base URL requests are currently sent to
+    pub fn current_base_url(&self) -> String {
+        match &self.mirrors {
+            Some(mirrors) => mirrors.active_url().to_string(),
+            None => self.base_url.clone(),
+        }
+    }
+
+    /// Measure every mirror once and switch to the fastest healthy one
+    ///
+    /// Probes are `HEAD` requests sent through the transport of the client.
+    pub async fn probe_mirrors(&self) -> Vec<MirrorStatus> {
+        let Some(mirrors) = &self.mirrors else {
+            return Vec::new();
+        };
+        let timeout = mirrors.options().probe_timeout;
+        let latencies = join_all(mirrors.urls().iter().map(|url| async move {
+            let req = self.client.head(url).timeout(timeout).build().ok()?;
+            let start = Instant::now();
+            // Never switch to a mirror that requests would refuse
+            match self.execute_direct(req).await {
+                Ok(resp) if !resp.status.is_server_error() => Some(start.elapsed()),
+                _ => None,
+            }
+        }))
+        .await;
+        mirrors.record(latencies);
+        mirrors.statuses()
+    }
+
+    /// Probe mirrors every `probe_interval` until the returned handle is aborted or
+    /// dropped, or the client is shut down
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn_mirror_probe(&self) -> BackgroundTask {
+        let sdk = self.clone();
+        BackgroundTask::new(self.spawn_until_shutdown("fogswap-mirror-probe", async move {
+            let Some(interval) = sdk.mirrors.as_ref().map(|m| m.options().probe_interval) else {
+                return;
+            };
+            loop {
+                sdk.probe_mirrors().await;
+                sdk.clock.sleep(interval).await;
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::testing::MockTransport;
+    use crate::{FogswapSdk, MirrorOptions, MockClock, TransportResponse};
+
+    async fn requests_reach(mock: &MockTransport, count: usize) {
+        while mock.requests().len() < count {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn probes_go_through_the_transport() {
+        let mock = Arc::new(MockTransport::empty());
+        mock.on("/v1", TransportResponse::new(503, ""));
+        mock.on("/v2", TransportResponse::new(200, ""));
+        let sdk = FogswapSdk::new()
+            .with_mirrors(vec!["https://api.example.com/v1".to_string(), "https://mirror.example.com/v2".to_string()], MirrorOptions::default())
+            .with_transport(mock.clone());
+
+        let statuses = sdk.probe_mirrors().await;
+        assert_eq!(mock.requests().len(), 2);
+        assert!(mock.requests().iter().all(|r| r.method == reqwest::Method::HEAD));
+        assert!(statuses[0].latency.is_none());
+        assert!(statuses[1].latency.is_some() && statuses[1].active);
+        assert_eq!(sdk.current_base_url(), "https://mirror.example.com/v2");
+    }
+
+    #[tokio::test]
+    async fn probe_task_waits_on_the_client_clock() {
+        let clock = Arc::new(MockClock::new());
+        let mock = Arc::new(MockTransport::empty());
+        let sdk = FogswapSdk::new()
+            .with_mirrors(vec!["https://api.example.com/v1".to_string(), "https://mirror.example.com/v2".to_string()], MirrorOptions::default())
+            .with_transport(mock.clone())
+            .with_clock(clock.clone());
+
+        let probe = sdk.spawn_mirror_probe();
+        tokio::time::timeout(Duration::from_secs(5), requests_reach(&mock, 2)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(mock.requests().len(), 2);
+
+        clock.advance(MirrorOptions::default().probe_interval);
+        tokio::time::timeout(Duration::from_secs(5), requests_reach(&mock, 4)).await.unwrap();
+        probe.abort();
+    }
+}
@@ -0,0 +1,506 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::FogswapSdkError;
+use crate::resp_structs::TransactionInfo;
+use crate::FogswapSdk;
+
+/// A blockchain network, as named by the API
+///
+/// Parsing is case-insensitive and accepts common aliases (`matic`, `tron`); names
+/// this SDK version does not know are kept as `Other`.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{Asset, Network};
+///
+/// let network: Network = "MATIC".parse().unwrap();
+/// assert_eq!(network, Network::Polygon);
+/// assert!(network.is_evm());
+/// assert_eq!(network.native_symbol(), Some("POL"));
+/// assert_eq!(Asset::native(Network::Xrp), Asset::new("xrp", "XRP"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Network {
+    Btc,
+    Eth,
+    Bsc,
+    Polygon,
+    Arbitrum,
+    Base,
+    Sol,
+    Tron,
+    Ltc,
+    Doge,
+    Xmr,
+    Xrp,
+    Xlm,
+    Ton,
+    /// A network this SDK version does not know yet
+    Other(String),
+}
+
+impl Network {
+
+    /// Name of the network in API requests
+    pub fn as_str(&self) -> &str {
+        match self {
+            Network::Btc => "btc",
+            Network::Eth => "eth",
+            Network::Bsc => "bsc",
+            Network::Polygon => "polygon",
+            Network::Arbitrum => "arbitrum",
+            Network::Base => "base",
+            Network::Sol => "sol",
+            Network::Tron => "trx",
+            Network::Ltc => "ltc",
+            Network::Doge => "doge",
+            Network::Xmr => "xmr",
+            Network::Xrp => "xrp",
+            Network::Xlm => "xlm",
+            Network::Ton => "ton",
+            Network::Other(name) => name,
+        }
+    }
+
+    /// Ticker of the native coin, `None` for unknown networks
+    pub fn native_symbol(&self) -> Option<&'static str> {
+        match self {
+            Network::Btc => Some("BTC"),
+            Network::Eth | Network::Arbitrum | Network::Base => Some("ETH"),
+            Network::Bsc => Some("BNB"),
+            Network::Polygon => Some("POL"),
+            Network::Sol => Some("SOL"),
+            Network::Tron => Some("TRX"),
+            Network::Ltc => Some("LTC"),
+            Network::Doge => Some("DOGE"),
+            Network::Xmr => Some("XMR"),
+            Network::Xrp => Some("XRP"),
+            Network::Xlm => Some("XLM"),
+            Network::Ton => Some("TON"),
+            Network::Other(_) => None,
+        }
+    }
+
+    /// Whether addresses and tokens follow Ethereum conventions (`0x` addresses, EIP-55, EIP-681)
+    pub fn is_evm(&self) -> bool {
+        self.chain_id().is_some()
+    }
+
+    /// EIP-155 chain id of EVM networks
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            Network::Eth => Some(1),
+            Network::Bsc => Some(56),
+            Network::Polygon => Some(137),
+            Network::Arbitrum => Some(42161),
+            Network::Base => Some(8453),
+            _ => None,
+        }
+    }
+
+    /// Whether shared addresses on this network are told apart by an extra id
+    /// (memo, destination tag), so payouts to exchanges need one
+    pub fn requires_extra_id(&self) -> bool {
+        matches!(self, Network::Xrp | Network::Xlm | Network::Ton)
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for Network {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl FromStr for Network {
+    type Err = Infallible;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "btc" => Network::Btc,
+            "eth" => Network::Eth,
+            "bsc" => Network::Bsc,
+            "polygon" | "matic" => Network::Polygon,
+            "arbitrum" => Network::Arbitrum,
+            "base" => Network::Base,
+            "sol" => Network::Sol,
+            "trx" | "tron" => Network::Tron,
+            "ltc" => Network::Ltc,
+            "doge" => Network::Doge,
+            "xmr" => Network::Xmr,
+            "xrp" => Network::Xrp,
+            "xlm" => Network::Xlm,
+            "ton" => Network::Ton,
+            _ => Network::Other(s.to_string()),
+        })
+    }
+}
+
+impl From<&str> for Network {
+    fn from(s: &str) -> Self {
+        let Ok(network) = s.parse();
+        network
+    }
+}
+
+impl Serialize for Network {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Network {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Network::from(s.as_str()))
+    }
+}
+
+/// Address format, explorer and payment URI of a network
+#[derive(Debug, Clone)]
+pub struct NetworkFormat {
+    /// Pattern a payout address must match in full
+    pub address_pattern: Regex,
+    /// Whether payouts need an extra id (memo, destination tag)
+    pub requires_extra_id: bool,
+    /// Explorer URL of a transaction, containing `{hash}`
+    pub explorer_tx_template: Option<String>,
+    /// Scheme of payment URIs, e.g. `bitcoin`
+    pub uri_scheme: Option<String>,
+    /// EIP-155 chain id, added to `ethereum:` URIs
+    pub chain_id: Option<u64>,
+}
+
+impl NetworkFormat {
+
+    /// Create a format from an address regex, anchored at both ends
+    /// # Errors
+    /// * `regex::Error` - If `address_pattern` is not a valid regex
+    pub fn new(address_pattern: &str) -> Result<Self> {
+        Ok(Self {
+            address_pattern: Regex::new(&format!("^(?:{})$", address_pattern))?,
+            requires_extra_id: false,
+            explorer_tx_template: None,
+            uri_scheme: None,
+            chain_id: None,
+        })
+    }
+
+    pub fn with_extra_id(mut self) -> Self {
+        self.requires_extra_id = true;
+        self
+    }
+
+    pub fn with_explorer(mut self, tx_template: &str) -> Self {
+        self.explorer_tx_template = Some(tx_template.to_string());
+        self
+    }
+
+    pub fn with_uri_scheme(mut self, scheme: &str) -> Self {
+        self.uri_scheme = Some(scheme.to_string());
+        self
+    }
+
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+}
+
+/// Per-network formats behind address validation, explorer links and payment URIs
+///
+/// Starts with the built-in networks; more can be registered at runtime, and a
+/// registration replaces any earlier format of the same network. Network names are
+/// matched case-insensitively.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{NetworkFormat, NetworkRegistry};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let registry = NetworkRegistry::default();
+/// registry.register("xlm", NetworkFormat::new("G[A-Z2-7]{55}")?
+///     .with_extra_id()
+///     .with_explorer("https://stellar.expert/explorer/public/tx/{hash}")
+///     .with_uri_scheme("web+stellar"));
+///
+/// assert!(registry.validate_address("XLM", "GBBD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5", Some("12345")).is_ok());
+/// assert!(registry.validate_address("eth", "not-an-address", None).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct NetworkRegistry {
+    formats: RwLock<HashMap<String, NetworkFormat>>,
+}
+
+impl Default for NetworkRegistry {
+    fn default() -> Self {
+        let registry = Self::empty();
+        for (networks, pattern, explorer, scheme) in BUILTIN_FORMATS {
+            let mut format = NetworkFormat::new(pattern).expect("built-in address pattern").with_explorer(explorer);
+            if let Some(scheme) = scheme {
+                format = format.with_uri_scheme(scheme);
+            }
+            for network in *networks {
+                let mut format = format.clone();
+                if let Some(chain_id) = Network::from(*network).chain_id() {
+                    format = format.with_uri_scheme("ethereum").with_chain_id(chain_id);
+                }
+                registry.register(network, format);
+            }
+        }
+        registry
+    }
+}
+
+/// Networks, address pattern, explorer template and URI scheme of the built-in formats
+const BUILTIN_FORMATS: &[(&[&str], &str, &str, Option<&str>)] = &[
+    (&["btc"], "bc1[a-z0-9]{25,87}|[13][a-km-zA-HJ-NP-Z1-9]{25,34}", "https://mempool.space/tx/{hash}", Some("bitcoin")),
+    (&["eth"], "0x[0-9a-fA-F]{40}", "https://etherscan.io/tx/{hash}", Some("ethereum")),
+    (&["bsc"], "0x[0-9a-fA-F]{40}", "https://bscscan.com/tx/{hash}", None),
+    (&["polygon", "matic"], "0x[0-9a-fA-F]{40}", "https://polygonscan.com/tx/{hash}", None),
+    (&["arbitrum"], "0x[0-9a-fA-F]{40}", "https://arbiscan.io/tx/{hash}", None),
+    (&["base"], "0x[0-9a-fA-F]{40}", "https://basescan.org/tx/{hash}", None),
+    (&["sol"], "[1-9A-HJ-NP-Za-km-z]{32,44}", "https://solscan.io/tx/{hash}", Some("solana")),
+    (&["trx", "tron"], "T[1-9A-HJ-NP-Za-km-z]{33}", "https://tronscan.org/#/transaction/{hash}", Some("tron")),
+    (&["ltc"], "ltc1[a-z0-9]{25,87}|[LM3][a-km-zA-HJ-NP-Z1-9]{26,33}", "https://blockchair.com/litecoin/transaction/{hash}", Some("litecoin")),
+    (&["doge"], "D[5-9A-HJ-NP-U][1-9A-HJ-NP-Za-km-z]{32}", "https://blockchair.com/dogecoin/transaction/{hash}", Some("dogecoin")),
+    (&["xmr"], "[48][1-9A-HJ-NP-Za-km-z]{94}|4[1-9A-HJ-NP-Za-km-z]{105}", "https://xmrchain.net/tx/{hash}", Some("monero")),
+];
+
+/// Decimals of the native token of every EVM network, as used by EIP-681 `value`
+const EVM_NATIVE_DECIMALS: u32 = 18;
+
+/// Schemes, besides `monero:`, whose URIs carry a memo as `memo=`
+const MEMO_SCHEMES: &[&str] = &["solana", "web+stellar"];
+
+/// Shared copy of the built-in formats, for lookups without a client
+pub(crate) static BUILTIN: LazyLock<NetworkRegistry> = LazyLock::new(NetworkRegistry::default);
+
+impl NetworkRegistry {
+
+    /// A registry without any network
+    pub fn empty() -> Self {
+        Self { formats: RwLock::new(HashMap::new()) }
+    }
+
+    /// Add or replace the format of `network`
+    pub fn register(&self, network: &str, format: NetworkFormat) {
+        let mut formats = self.formats.write().unwrap_or_else(|e| e.into_inner());
+        formats.insert(network.to_ascii_lowercase(), format);
+    }
+
+    /// Format of `network`, if registered
+    pub fn get(&self, network: &str) -> Option<NetworkFormat> {
+        let formats = self.formats.read().unwrap_or_else(|e| e.into_inner());
+        formats.get(&network.to_ascii_lowercase()).cloned()
+    }
+
+    /// Names of all registered networks
+    pub fn networks(&self) -> Vec<String> {
+        let formats = self.formats.read().unwrap_or_else(|e| e.into_inner());
+        formats.keys().cloned().collect()
+    }
+
+    /// Check a payout address and extra id against the format of `network`
+    ///
+    /// Networks without a registered format are accepted, leaving validation to the API.
+    /// # Errors
+    /// * `FogswapSdkError::InvalidAddress` - If the address does not match, or a required extra id is missing
+    pub fn validate_address(&self, network: &str, address: &str, extra_id: Option<&str>) -> Result<()> {
+        let Some(format) = self.get(network) else {
+            return Ok(());
+        };
+        if !format.address_pattern.is_match(address) {
+            let e = format!("{} is not a valid {} address", address, network);
+            return Err(FogswapSdkError::InvalidAddress(e).into());
+        }
+        if format.requires_extra_id && extra_id.is_none_or(str::is_empty) {
+            let e = format!("{} addresses require an extra id", network);
+            return Err(FogswapSdkError::InvalidAddress(e).into());
+        }
+        Ok(())
+    }
+
+    /// Explorer URL of a transaction on `network`
+    pub fn explorer_tx_url(&self, network: &str, hash: &str) -> Option<String> {
+        let template = self.get(network)?.explorer_tx_template?;
+        Some(template.replace("{hash}", hash))
+    }
+
+    /// Payment URI of `address` on `network`, e.g. `bitcoin:bc1...?amount=0.01`
+    pub fn payment_uri(&self, network: &str, address: &str, amount: Option<f64>) -> Option<String> {
+        self.payment_uri_with_extra_id(network, address, amount, None)
+    }
+
+    /// [`payment_uri`](Self::payment_uri) carrying a memo or destination tag, see
+    /// [`transfer_uri`](Self::transfer_uri)
+    pub fn payment_uri_with_extra_id(&self, network: &str, address: &str, amount: Option<f64>, extra_id: Option<&str>) -> Option<String> {
+        self.transfer_uri(network, None, None, address, amount, extra_id)
+    }
+
+    /// Payment URI sending `amount` of a token to `address`, in the format wallets of
+    /// the network expect
+    ///
+    /// * `monero:` - `tx_amount` and `tx_payment_id`
+    /// * `ethereum:` (EIP-681) - `value` in wei with the chain id, or a `transfer` call
+    ///   of the token contract with `uint256` in the token's smallest unit
+    /// * `solana:` and `web+stellar:` - `amount` and `memo`
+    /// * any other scheme (BIP21 and alike) - `amount` only
+    ///
+    /// `token` is the contract address of a token, `None` for the native coin, and
+    /// `decimals` its decimals. `None` rather than a URI missing part of the transfer:
+    /// a token amount without known decimals, or an extra id the scheme cannot carry.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::NetworkRegistry;
+    ///
+    /// let registry = NetworkRegistry::default();
+    /// let usdt = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+    /// let payin = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+    /// assert_eq!(
+    ///     registry.transfer_uri("eth", Some(usdt), Some(6), payin, Some(12.5), None).as_deref(),
+    ///     Some("ethereum:0xdAC17F958D2ee523a2206206994597C13D831ec7@1/transfer?address=0x742d35Cc6634C0532925a3b844Bc454e4438f44e&uint256=12.5e6"),
+    /// );
+    /// assert!(registry.transfer_uri("eth", Some(usdt), None, payin, Some(12.5), None).is_none());
+    /// assert!(registry.transfer_uri("btc", None, None, "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", Some(0.1), Some("42")).is_none());
+    /// ```
+    pub fn transfer_uri(
+        &self,
+        network: &str,
+        token: Option<&str>,
+        decimals: Option<u32>,
+        address: &str,
+        amount: Option<f64>,
+        extra_id: Option<&str>
+    ) -> Option<String> {
+        let format = self.get(network)?;
+        let scheme = format.uri_scheme.as_deref()?;
+        let extra_id = extra_id.filter(|id| !id.is_empty());
+        // Native coins are given by their ticker, tokens by an address of the network
+        let token = token.filter(|token| format.address_pattern.is_match(token));
+        let mut params = Vec::new();
+        let target = match scheme {
+            "monero" => {
+                params.extend(amount.map(|amount| format!("tx_amount={}", amount)));
+                params.extend(extra_id.map(|id| format!("tx_payment_id={}", id)));
+                address.to_string()
+            }
+            "ethereum" => {
+                let chain = format.chain_id.map(|id| format!("@{}", id)).unwrap_or_default();
+                if extra_id.is_some() {
+                    return None;
+                }
+                match token {
+                    Some(token) => {
+                        params.push(format!("address={}", address));
+                        if let Some(amount) = amount {
+                            params.push(format!("uint256={}e{}", amount, decimals?));
+                        }
+                        format!("{}{}/transfer", token, chain)
+                    }
+                    None => {
+                        params.extend(amount.map(|amount| format!("value={}e{}", amount, EVM_NATIVE_DECIMALS)));
+                        format!("{}{}", address, chain)
+                    }
+                }
+            }
+            _ => {
+                params.extend(amount.map(|amount| format!("amount={}", amount)));
+                if let Some(id) = extra_id {
+                    if !MEMO_SCHEMES.contains(&scheme) {
+                        return None;
+                    }
+                    params.push(format!("memo={}", id));
+                }
+                address.to_string()
+            }
+        };
+        Some(match params.is_empty() {
+            true => format!("{}:{}", scheme, target),
+            false => format!("{}:{}?{}", scheme, target, params.join("&")),
+        })
+    }
+
+    /// Payment URI of the deposit of `tx_info`, with `decimals` of a token deposit,
+    /// see [`transfer_uri`](Self::transfer_uri)
+    pub fn payin_uri(&self, tx_info: &TransactionInfo, decimals: Option<u32>) -> Option<String> {
+        self.transfer_uri(
+            &tx_info.network_from,
+            Some(&tx_info.contract_address_from),
+            decimals,
+            &tx_info.payin_address,
+            Some(tx_info.amount_from),
+            tx_info.payin_extra_id.as_deref(),
+        )
+    }
+}
+
+impl FogswapSdk {
+
+    /// Use `registry` for address validation, explorer links and payment URIs
+    ///
+    /// The registry is shared, so networks registered on it later are seen by the client.
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    /// use fogswap_sdk_rust::{FogswapSdk, NetworkFormat, NetworkRegistry};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let registry = Arc::new(NetworkRegistry::default());
+    /// let sdk = FogswapSdk::new().with_network_registry(registry.clone());
+    /// registry.register("kas", NetworkFormat::new("kaspa:[a-z0-9]{61,63}")?);
+    /// assert!(sdk.networks().get("kas").is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_network_registry(mut self, registry: Arc<NetworkRegistry>) -> Self {
+        self.networks = registry;
+        self
+    }
+
+    /// Network formats used by this client
+    pub fn networks(&self) -> &NetworkRegistry {
+        &self.networks
+    }
+
+    /// Payment URI of the deposit of `tx_info`, including `payin_extra_id` when one is set
+    ///
+    /// Networks registered on this client are known, unlike with
+    /// [`TransactionInfo::payin_uri`], and so are the decimals of tokens in the cached
+    /// token list (`get_token_list_cached`). `None` if the payin network has no URI
+    /// scheme, or the URI could not carry the whole transfer.
+    /// Wallets that ignore the extra id parameter still need it shown separately; see
+    /// [`TransactionInfo::requires_payin_extra_id`].
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tx_info = sdk.get_transaction_info("tx_id").await?;
+    /// if let Some(uri) = sdk.payin_uri(&tx_info) {
+    ///     println!("{}", uri);
+    /// }
+    /// if tx_info.requires_payin_extra_id() {
+    ///     println!("Include memo {} or the deposit is lost", tx_info.payin_extra_id.as_deref().unwrap_or_default());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn payin_uri(&self, tx_info: &TransactionInfo) -> Option<String> {
+        let decimals = self.find_token(&tx_info.network_from, &tx_info.contract_address_from).and_then(|token| token.decimals);
+        self.networks.payin_uri(tx_info, decimals)
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use crate::health::HealthStatus;
+use crate::transport::is_connect_error;
+use crate::FogswapSdk;
+
+/// Connectivity flag shared by every clone of a client
+#[derive(Debug)]
+pub(crate) struct OfflineState {
+    probe_interval: Duration,
+    offline: AtomicBool,
+}
+
+impl OfflineState {
+
+    pub fn new(probe_interval: Duration) -> Self {
+        Self { probe_interval, offline: AtomicBool::new(false) }
+    }
+
+    pub fn probe_interval(&self) -> Duration {
+        self.probe_interval
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::Acquire)
+    }
+}
+
+impl FogswapSdk {
+
+    /// Fail fast with `FogswapSdkError::Offline` after a connection error, instead of
+    /// waiting for a full timeout on every call
+    ///
+    /// While offline, a `HEAD` request probes the base URL every `probe_interval`; the
+    /// first answer brings the client back online. Both transitions are published as
+    /// health changes, so `health_events()` can be used to back-fill state on recovery.
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// let sdk = FogswapSdk::new().with_offline_detection(Duration::from_secs(5));
+    /// if sdk.is_offline() {
+    ///     println!("Fogswap unreachable, retrying in the background");
+    /// }
+    /// ```
+    pub fn with_offline_detection(mut self, probe_interval: Duration) -> Self {
+        self.offline = Some(std::sync::Arc::new(OfflineState::new(probe_interval)));
+        self
+    }
+
+    /// Whether the last request failed to connect and the API has not answered since
+    pub fn is_offline(&self) -> bool {
+        self.offline.as_ref().is_some_and(|state| state.is_offline())
+    }
+
+    /// Mark the client offline after `e` and start probing, if `e` is a connection error
+    pub(crate) fn note_connectivity_error(&self, e: &anyhow::Error) -> bool {
+        let Some(state) = &self.offline else {
+            return false;
+        };
+        let unreachable = e.downcast_ref::<reqwest::Error>().is_some_and(is_connect_error);
+        if !unreachable {
+            return false;
+        }
+        // Only the first caller to notice starts the probe
+        if !state.offline.swap(true, Ordering::AcqRel) {
+            self.health.set(HealthStatus::Unhealthy("offline".to_string()));
+            self.spawn_connectivity_probe();
+        }
+        true
+    }
+
+    fn spawn_connectivity_probe(&self) {
+        let sdk = self.clone();
+        self.spawn_until_shutdown("fogswap-connectivity-probe", async move {
+            let Some(state) = sdk.offline.clone() else {
+                return;
+            };
+            let interval = state.probe_interval();
+            loop {
+                sdk.clock.sleep(interval).await;
+                let probe = sdk.client.head(sdk.current_base_url()).timeout(interval).send().await;
+                if probe.is_ok() {
+                    state.offline.store(false, Ordering::Release);
+                    sdk.health.set(HealthStatus::Healthy);
+                    return;
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,607 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::sync::broadcast;
+
+use crate::clock::Clock;
+use crate::error::FogswapSdkError;
+use crate::precision::AmountTolerance;
+use crate::req_structs::{CreateTransactionRequest, QuoteRequest};
+use crate::resp_structs::{QuoteResponse, TransactionInfo};
+use crate::rt::Instant;
+use crate::status::{StatusSequencer, TransactionStatus};
+use crate::FogswapSdk;
+
+/// Something the orchestrator did on its own during a flow
+#[derive(Debug, Clone)]
+pub enum OrchestratorEvent {
+    /// An expired quote was replaced before creating the transaction
+    QuoteRefreshed { old: Box<QuoteResponse>, new: Box<QuoteResponse> },
+    /// A poll of a confirming transaction saw a new deposit confirmation count
+    Confirming { tx_id: String, seen: u32, required: Option<u32> },
+}
+
+/// High-level swap flow built on top of [`FogswapSdk`]
+///
+/// The orchestrator drives the quote -> create sequence and applies the
+/// configured safety checks before any transaction is created.
+#[derive(Debug, Clone)]
+pub struct SwapOrchestrator {
+    pub sdk: FogswapSdk,
+    /// Maximum allowed relative drop of `amount_to` between the displayed
+    /// quote and the re-quote taken right before creation (0.01 = 1%)
+    pub slippage_tolerance: Option<f64>,
+    /// Overall budget shared by every call, poll and wait of one flow
+    pub deadline: Option<Duration>,
+    /// Maximum implied USD loss between `convert_usd.from` and `convert_usd.to` (0.05 = 5%)
+    pub max_value_loss: Option<f64>,
+    /// How many times an expired quote is replaced before giving up
+    pub max_requotes: u32,
+    events: broadcast::Sender<OrchestratorEvent>,
+}
+
+impl SwapOrchestrator {
+
+    /// Create a new orchestrator with no slippage guard
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, SwapOrchestrator};
+    ///
+    /// let orchestrator = SwapOrchestrator::new(FogswapSdk::new())
+    ///     .with_slippage_tolerance(0.01);
+    /// ```
+    pub fn new(sdk: FogswapSdk) -> Self {
+        Self { sdk, slippage_tolerance: None, deadline: None, max_value_loss: None, max_requotes: 0, events: broadcast::Sender::new(16) }
+    }
+
+    /// Abort creation when `amount_to` drops by more than `tolerance`
+    /// (relative, 0.01 = 1%) since the displayed quote
+    pub fn with_slippage_tolerance(mut self, tolerance: f64) -> Self {
+        self.slippage_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Refuse to create a transaction whose quote implies a USD loss above `max_loss`
+    /// (relative, 0.05 = 5%), protecting against fat-fingered pairs and broken rates
+    pub fn with_max_value_loss(mut self, max_loss: f64) -> Self {
+        self.max_value_loss = Some(max_loss);
+        self
+    }
+
+    /// Re-quote up to `max_requotes` times when the displayed quote's `valid_until`
+    /// has passed, instead of creating the transaction at a stale rate
+    ///
+    /// Every replacement is published as `OrchestratorEvent::QuoteRefreshed`. With a
+    /// slippage tolerance, a new quote beyond it aborts with `SlippageExceeded`.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, OrchestratorEvent, SwapOrchestrator};
+    ///
+    /// # async fn example() {
+    /// let orchestrator = SwapOrchestrator::new(FogswapSdk::new())
+    ///     .with_slippage_tolerance(0.01)
+    ///     .with_auto_requote(3);
+    /// let mut events = orchestrator.subscribe();
+    /// tokio::spawn(async move {
+    ///     while let Ok(OrchestratorEvent::QuoteRefreshed { old, new }) = events.recv().await {
+    ///         println!("Rate refreshed: {} -> {}", old.amount_to, new.amount_to);
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    pub fn with_auto_requote(mut self, max_requotes: u32) -> Self {
+        self.max_requotes = max_requotes;
+        self
+    }
+
+    /// Receive the events of every flow run by this orchestrator and its clones
+    pub fn subscribe(&self) -> broadcast::Receiver<OrchestratorEvent> {
+        self.events.subscribe()
+    }
+
+    /// Give every flow an overall budget ("complete or give up within 2 hours")
+    ///
+    /// Requests, polls and waits share the budget; running out of it returns
+    /// `FogswapSdkError::DeadlineExceeded` carrying the last known `TransactionInfo`.
+    /// A create request in flight is not interrupted, so a swap created past the
+    /// deadline is still reported there.
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Create a transaction for a quote previously shown to the user
+    ///
+    /// When a slippage tolerance is configured the pair is re-quoted
+    /// immediately before `create_transaction`.
+    /// # Arguments
+    /// * `displayed` - The quote the user agreed to
+    /// * `payout_address` - The address to receive the tokens
+    /// * `payout_extra_id` - The extra id for the payout
+    /// * `is_use_xmr` - Whether to use XMR for the transaction
+    /// # Returns
+    /// * `TransactionInfo` - The information about the transaction
+    /// # Errors
+    /// * `FogswapSdkError::SlippageExceeded` - If the re-quoted `amount_to` moved beyond the tolerance
+    /// * `FogswapSdkError::QuoteExpired` - If auto-requote is enabled and the quote could not be refreshed
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, FogswapSdk, QuoteRequest, SwapOrchestrator, TxType};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let req = QuoteRequest::new(&Asset::new("sol", "SOL"), &Asset::new("eth", "ETH"), 1.0).tx_type(TxType::Standard);
+    /// let quote = sdk.get_quote_with(&req).await?;
+    /// let orchestrator = SwapOrchestrator::new(sdk).with_slippage_tolerance(0.005);
+    /// let tx_info = orchestrator.create_from_quote(
+    ///     &quote,
+    ///     "YOUR_RECEIVE_ADDRESS_HERE",
+    ///     &None,
+    ///     None
+    /// ).await?;
+    /// println!("Transaction ID: {}", tx_info.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_from_quote(
+        &self,
+        displayed: &QuoteResponse,
+        payout_address: &str,
+        payout_extra_id: &Option<String>,
+        is_use_xmr: Option<bool>
+    ) -> Result<TransactionInfo> {
+        let budget = Budget::start(self.deadline, self.sdk.clock.clone());
+        self.create_within(&budget, displayed, payout_address, payout_extra_id, is_use_xmr).await
+    }
+
+    /// Poll a transaction until it reaches a terminal status
+    /// # Arguments
+    /// * `tx_id` - The id of the transaction
+    /// * `poll_interval` - Delay between two `get_transaction_info` calls
+    /// # Errors
+    /// * `FogswapSdkError::DeadlineExceeded` - If the configured deadline runs out first
+    /// * `FogswapSdkError::Cancelled` - If the token of the client's `with_cancellation` is cancelled
+    pub async fn wait_for_completion(&self, tx_id: &str, poll_interval: Duration) -> Result<TransactionInfo> {
+        let budget = Budget::start(self.deadline, self.sdk.clock.clone());
+        self.poll_within(&budget, tx_id, poll_interval, None).await
+    }
+
+    /// Create a transaction from a displayed quote and wait for it to complete,
+    /// all within a single deadline
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::{Asset, FogswapSdk, FogswapSdkError, QuoteRequest, SwapOrchestrator, TxType};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let req = QuoteRequest::new(&Asset::new("sol", "SOL"), &Asset::new("eth", "ETH"), 1.0).tx_type(TxType::Standard);
+    /// let quote = sdk.get_quote_with(&req).await?;
+    /// let orchestrator = SwapOrchestrator::new(sdk).with_deadline(Duration::from_secs(2 * 60 * 60));
+    /// match orchestrator.execute(&quote, "YOUR_RECEIVE_ADDRESS_HERE", &None, None, Duration::from_secs(30)).await {
+    ///     Ok(tx_info) => println!("Finished with status {}", tx_info.status),
+    ///     Err(e) => if let Some(FogswapSdkError::DeadlineExceeded { last_known }) = e.downcast_ref() {
+    ///         println!("Gave up, last known: {:?}", last_known);
+    ///     },
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute(
+        &self,
+        displayed: &QuoteResponse,
+        payout_address: &str,
+        payout_extra_id: &Option<String>,
+        is_use_xmr: Option<bool>,
+        poll_interval: Duration
+    ) -> Result<TransactionInfo> {
+        let budget = Budget::start(self.deadline, self.sdk.clock.clone());
+        let tx_info = self.create_within(&budget, displayed, payout_address, payout_extra_id, is_use_xmr).await?;
+        let id = tx_info.id.clone();
+        self.poll_within(&budget, &id, poll_interval, Some(tx_info)).await
+    }
+
+    async fn create_within(
+        &self,
+        budget: &Budget,
+        displayed: &QuoteResponse,
+        payout_address: &str,
+        payout_extra_id: &Option<String>,
+        is_use_xmr: Option<bool>
+    ) -> Result<TransactionInfo> {
+        if let Some(max_loss) = self.max_value_loss {
+            check_value_loss(displayed, max_loss)?;
+        }
+        let requotes = self.refresh_expired(budget, displayed, is_use_xmr).await?;
+        // A refreshed quote was just checked against the tolerance
+        if let Some(tolerance) = self.slippage_tolerance && requotes == 0 {
+            let fresh = budget.run(None, self.requote(displayed, is_use_xmr)).await?;
+            check_slippage_with(displayed.amount_to, fresh.amount_to, tolerance, &self.sdk.tolerance)?;
+            if let Some(max_loss) = self.max_value_loss {
+                check_value_loss(&fresh, max_loss)?;
+            }
+        }
+
+        let req = quote_create_request(displayed, payout_address, payout_extra_id, is_use_xmr);
+        // Never cut short: the API may already have created the swap, which the
+        // deadline error then reports as the last known state
+        let tx_info = self.sdk.create_transaction_with(&req).await?;
+        budget.check(Some(&tx_info))?;
+        Ok(tx_info)
+    }
+
+    /// Replace `displayed` while it is expired, as allowed by `max_requotes`;
+    /// returns the number of re-quotes
+    async fn refresh_expired(&self, budget: &Budget, displayed: &QuoteResponse, is_use_xmr: Option<bool>) -> Result<u32> {
+        if self.max_requotes == 0 {
+            return Ok(0);
+        }
+        let mut current = displayed.clone();
+        let mut requotes = 0;
+        while current.is_expired_at(self.sdk.clock.unix_time()) {
+            if requotes == self.max_requotes {
+                return Err(FogswapSdkError::QuoteExpired { requotes }.into());
+            }
+            let fresh = budget.run(None, self.requote(displayed, is_use_xmr)).await?;
+            if let Some(tolerance) = self.slippage_tolerance {
+                check_slippage_with(displayed.amount_to, fresh.amount_to, tolerance, &self.sdk.tolerance)?;
+            }
+            if let Some(max_loss) = self.max_value_loss {
+                check_value_loss(&fresh, max_loss)?;
+            }
+            let old = std::mem::replace(&mut current, fresh.clone());
+            // Nobody listening is fine
+            let _ = self.events.send(OrchestratorEvent::QuoteRefreshed { old: Box::new(old), new: Box::new(fresh) });
+            requotes += 1;
+        }
+        Ok(requotes)
+    }
+
+    async fn requote(&self, quote: &QuoteResponse, is_use_xmr: Option<bool>) -> Result<QuoteResponse> {
+        self.sdk.get_quote_with(&requote_request(quote, is_use_xmr)).await
+    }
+
+    async fn poll_within(
+        &self,
+        budget: &Budget,
+        tx_id: &str,
+        poll_interval: Duration,
+        mut last_known: Option<TransactionInfo>
+    ) -> Result<TransactionInfo> {
+        let sequencer = StatusSequencer::default();
+        if let Some(tx_info) = &last_known {
+            sequencer.observe(tx_id, tx_info.status.clone());
+        }
+        loop {
+            let tx_info = budget.run(last_known.as_ref(), self.sdk.get_transaction_info(tx_id)).await?;
+            // An out-of-date poll result is ignored, keeping the newer last known state
+            if sequencer.observe(tx_id, tx_info.status.clone()).is_empty()
+                && sequencer.last_status(tx_id).as_ref() != Some(&tx_info.status) {
+                self.sdk.until_shutdown(budget.sleep(poll_interval, last_known.as_ref())).await?;
+                continue;
+            }
+            if tx_info.status.is_terminal() {
+                return Ok(tx_info);
+            }
+            if tx_info.status == TransactionStatus::Confirming
+                && let Some(seen) = tx_info.confirmations
+                && last_known.as_ref().is_none_or(|last| last.confirmations != Some(seen)) {
+                let required = tx_info.confirmations_required;
+                // Nobody listening is fine
+                let _ = self.events.send(OrchestratorEvent::Confirming { tx_id: tx_id.to_string(), seen, required });
+            }
+            last_known = Some(tx_info);
+            self.sdk.until_shutdown(budget.sleep(poll_interval, last_known.as_ref())).await?;
+        }
+    }
+}
+
+/// The quote request that produced `quote`; `is_use_xmr` overrides the quoted route
+fn requote_request(quote: &QuoteResponse, is_use_xmr: Option<bool>) -> QuoteRequest {
+    QuoteRequest {
+        amount_from: quote.amount_from,
+        network_from: quote.network_from.clone(),
+        contract_address_from: quote.contract_address_from.clone(),
+        network_to: quote.network_to.clone(),
+        contract_address_to: quote.contract_address_to.clone(),
+        tx_type: Some(quote.tx_type.clone()),
+        is_use_xmr: is_use_xmr.or(quote.is_use_xmr),
+        partner_id: quote.partner_fee.as_ref().map(|fee| fee.partner_id.clone()),
+        fee_bps: quote.partner_fee.as_ref().map(|fee| fee.fee_bps),
+        ..Default::default()
+    }
+}
+
+/// The transaction of `quote`, priced and routed as quoted
+fn quote_create_request(
+    quote: &QuoteResponse,
+    payout_address: &str,
+    payout_extra_id: &Option<String>,
+    is_use_xmr: Option<bool>
+) -> CreateTransactionRequest {
+    let requote = requote_request(quote, is_use_xmr);
+    CreateTransactionRequest {
+        network_from: requote.network_from,
+        contract_address_from: requote.contract_address_from,
+        network_to: requote.network_to,
+        contract_address_to: requote.contract_address_to,
+        amount_from: requote.amount_from,
+        payout_address: payout_address.to_string(),
+        payout_extra_id: payout_extra_id.clone(),
+        tx_type: requote.tx_type,
+        is_use_xmr: requote.is_use_xmr,
+        partner_id: requote.partner_id,
+        fee_bps: requote.fee_bps,
+        ..Default::default()
+    }
+}
+
+/// Check that the USD value received is not lower than the USD value sent by more than `max_loss`
+///
+/// Quotes without both USD values pass, since the loss cannot be computed.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::orchestrator::check_value_loss;
+/// use fogswap_sdk_rust::{Asset, FogswapSdk, QuoteRequest, TxType};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let sdk = FogswapSdk::new();
+/// let req = QuoteRequest::new(&Asset::new("sol", "SOL"), &Asset::new("eth", "ETH"), 1.0).tx_type(TxType::Standard);
+/// let quote = sdk.get_quote_with(&req).await?;
+/// check_value_loss(&quote, 0.05)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn check_value_loss(quote: &QuoteResponse, max_loss: f64) -> Result<()> {
+    if let (Some(usd_from), Some(usd_to)) = (quote.convert_usd.from, quote.convert_usd.to)
+        && usd_from > 0.0
+        && (usd_from - usd_to) / usd_from > max_loss {
+        return Err(FogswapSdkError::ValueLossExceeded { usd_from, usd_to }.into());
+    }
+    Ok(())
+}
+
+/// Statuses after which a transaction no longer changes
+pub fn is_terminal_status(status: &str) -> bool {
+    let Ok(status) = status.parse::<TransactionStatus>();
+    status.is_terminal()
+}
+
+/// Remaining time of one orchestrated flow
+struct Budget {
+    until: Option<Instant>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Budget {
+
+    fn start(deadline: Option<Duration>, clock: Arc<dyn Clock>) -> Self {
+        Self { until: deadline.map(|d| clock.now() + d), clock }
+    }
+
+    fn exceeded(last_known: Option<&TransactionInfo>) -> anyhow::Error {
+        FogswapSdkError::DeadlineExceeded { last_known: last_known.cloned().map(Box::new) }.into()
+    }
+
+    async fn run<T>(&self, last_known: Option<&TransactionInfo>, fut: impl Future<Output = Result<T>>) -> Result<T> {
+        let Some(until) = self.until else {
+            return fut.await;
+        };
+        tokio::select! {
+            biased;
+            result = fut => result,
+            _ = self.clock.sleep_until(until) => Err(Self::exceeded(last_known)),
+        }
+    }
+
+    /// Fail with `DeadlineExceeded` if the budget has run out
+    fn check(&self, last_known: Option<&TransactionInfo>) -> Result<()> {
+        match self.until {
+            Some(until) if self.clock.now() >= until => Err(Self::exceeded(last_known)),
+            _ => Ok(()),
+        }
+    }
+
+    async fn sleep(&self, duration: Duration, last_known: Option<&TransactionInfo>) -> Result<()> {
+        match self.until {
+            Some(until) if self.clock.now() + duration >= until => {
+                self.clock.sleep_until(until).await;
+                Err(Self::exceeded(last_known))
+            }
+            _ => {
+                self.clock.sleep(duration).await;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Check that `actual` has not dropped below `expected` by more than `tolerance`
+pub fn check_slippage(expected: f64, actual: f64, tolerance: f64) -> Result<()> {
+    check_slippage_with(expected, actual, tolerance, &AmountTolerance::default())
+}
+
+/// [`check_slippage`], comparing `actual` against the slippage floor within `amounts`
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::AmountTolerance;
+/// use fogswap_sdk_rust::orchestrator::check_slippage_with;
+///
+/// // 1% below 100.0, off by float noise only
+/// assert!(check_slippage_with(100.0, 98.99999999999, 0.01, &AmountTolerance::relative(1e-9)).is_ok());
+/// assert!(check_slippage_with(100.0, 98.9, 0.01, &AmountTolerance::relative(1e-9)).is_err());
+/// ```
+pub fn check_slippage_with(expected: f64, actual: f64, tolerance: f64, amounts: &AmountTolerance) -> Result<()> {
+    if expected > 0.0 && !amounts.at_least(actual, expected * (1.0 - tolerance)) {
+        return Err(FogswapSdkError::SlippageExceeded { expected, actual }.into());
+    }
+    Ok(())
+}
+
+impl FogswapSdk {
+
+    /// Create a transaction for a quote, re-quoting first if it has expired
+    ///
+    /// The transaction keeps the route (`tx_type`, `is_use_xmr`) and partner fee of the
+    /// quote. A fresh quote is only accepted when its `amount_to` is within
+    /// `max_slippage_bps` (basis points, 100 = 1%) of the original one. For repeated
+    /// re-quotes and progress events use `SwapOrchestrator::with_auto_requote`.
+    /// # Arguments
+    /// * `quote` - The quote the user agreed to
+    /// * `payout_address` - The address to receive the tokens
+    /// * `max_slippage_bps` - Maximum drop of `amount_to` accepted on re-quote, in basis points
+    /// # Errors
+    /// * `FogswapSdkError::SlippageExceeded` - If the quote expired and the new rate is beyond `max_slippage_bps`
+    /// * Any error of `get_quote_with` or `create_transaction_with`
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, FogswapSdk};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let quote = sdk.get_quote_for(&Asset::native("btc"), &Asset::native("xmr"), 0.1).await?;
+    /// // ... the user confirms, maybe after the quote expired
+    /// let tx_info = sdk.create_transaction_from_quote(&quote, "YOUR_RECEIVE_ADDRESS_HERE", 100).await?;
+    /// println!("{}", tx_info);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_transaction_from_quote(
+        &self,
+        quote: &QuoteResponse,
+        payout_address: &str,
+        max_slippage_bps: u32
+    ) -> Result<TransactionInfo> {
+        if quote.is_expired_at(self.clock.unix_time()) {
+            let fresh = self.get_quote_with(&requote_request(quote, None)).await?;
+            let tolerance = f64::from(max_slippage_bps) / 10_000.0;
+            check_slippage_with(quote.amount_to, fresh.amount_to, tolerance, &self.tolerance)?;
+        }
+        self.create_transaction_with(&quote_create_request(quote, payout_address, &None, None)).await
+    }
+
+    /// Quote and create a swap, refusing it if the created rate is worse than quoted
+    ///
+    /// The `amount_to` of the created transaction is compared against the quote;
+    /// beyond `max_slippage_bps` (basis points, 50 = 0.5%) an error is returned
+    /// instead of the deposit instructions, and the unfunded transaction expires.
+    /// # Arguments
+    /// * `req` - The swap to create
+    /// * `max_slippage_bps` - Maximum drop of `amount_to` from the quote, in basis points
+    /// # Errors
+    /// * `FogswapSdkError::SlippageExceeded` - If the transaction pays out less than allowed
+    /// * Any error of `get_quote_with` or `create_transaction_with`
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, CreateTransactionRequest, FogswapSdk};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let req = CreateTransactionRequest::builder()
+    ///     .assets(&Asset::native("btc"), &Asset::native("xmr"), 0.1)
+    ///     .payout_address("YOUR_RECEIVE_ADDRESS_HERE")
+    ///     .build();
+    /// let tx_info = sdk.swap_with_max_slippage(&req, 50).await?;
+    /// println!("{}", tx_info);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn swap_with_max_slippage(&self, req: &CreateTransactionRequest, max_slippage_bps: u32) -> Result<TransactionInfo> {
+        let quote = self.get_quote_with(&QuoteRequest {
+            amount_from: req.amount_from,
+            network_from: req.network_from.clone(),
+            contract_address_from: req.contract_address_from.clone(),
+            network_to: req.network_to.clone(),
+            contract_address_to: req.contract_address_to.clone(),
+            tx_type: req.tx_type.clone(),
+            is_use_xmr: req.is_use_xmr,
+            promo_code: req.promo_code.clone(),
+            partner_id: req.partner_id.clone(),
+            fee_bps: req.fee_bps,
+            ..Default::default()
+        }).await?;
+        let tx_info = self.create_transaction_with(req).await?;
+        let tolerance = f64::from(max_slippage_bps) / 10_000.0;
+        check_slippage_with(quote.amount_to, tx_info.amount_to, tolerance, &self.tolerance)?;
+        Ok(tx_info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use std::time::Duration;
+
+    use futures::future::BoxFuture;
+
+    use crate::error::FogswapSdkError;
+    use crate::orchestrator::SwapOrchestrator;
+    use crate::testing::MockTransport;
+    use crate::{FogswapSdk, MockClock, QuoteResponse, TransactionStatus, Transport, TransportResponse};
+
+    /// Creates taking 20 seconds of the mock clock
+    #[derive(Debug)]
+    struct SlowCreate {
+        mock: MockTransport,
+        clock: Arc<MockClock>,
+    }
+
+    impl Transport for SlowCreate {
+        fn execute(&self, req: reqwest::Request) -> BoxFuture<'_, anyhow::Result<TransportResponse>> {
+            if req.url().path().ends_with("/transaction/create") {
+                self.clock.advance(Duration::from_secs(20));
+            }
+            self.mock.execute(req)
+        }
+    }
+
+    #[tokio::test]
+    async fn created_transaction_keeps_route_and_partner_fee_of_the_quote() {
+        let mock = Arc::new(MockTransport::new());
+        let sdk = FogswapSdk::new().with_transport(mock.clone());
+        let mut quote = MockTransport::sample_quote();
+        quote["tx_type"] = json!("private");
+        quote["is_use_xmr"] = json!(true);
+        quote["partner_fee"] = json!({ "partner_id": "brand-a", "fee_bps": 40, "amount": 0.0002 });
+        // Expired, so the pair is re-quoted first
+        quote["valid_until"] = json!(1);
+        let quote: QuoteResponse = serde_json::from_value(quote).unwrap();
+
+        sdk.create_transaction_from_quote(&quote, "0x742d35Cc6634C0532925a3b844Bc454e4438f44e", 100).await.unwrap();
+
+        let requests = mock.requests();
+        let requote = requests.iter().find(|r| r.endpoint == "/transaction/quote").unwrap();
+        assert_eq!(requote.query["is_use_xmr"], "true");
+        assert_eq!(requote.query["partner_id"], "brand-a");
+        let create = requests.iter().find(|r| r.endpoint == "/transaction/create").unwrap();
+        let body = create.body.as_ref().unwrap();
+        assert_eq!(body["network_from"], "sol");
+        assert_eq!(body["contract_address_to"], "ETH");
+        assert_eq!(body["amount_from"], 1.0);
+        assert_eq!(body["tx_type"], "private");
+        assert_eq!(body["is_use_xmr"], true);
+        assert_eq!(body["partner_id"], "brand-a");
+        assert_eq!(body["fee_bps"], 40);
+    }
+
+    #[tokio::test]
+    async fn deadline_during_create_reports_the_created_transaction() {
+        let clock = Arc::new(MockClock::new());
+        let transport = Arc::new(SlowCreate { mock: MockTransport::new(), clock: clock.clone() });
+        let sdk = FogswapSdk::new().with_transport(transport).with_clock(clock.clone());
+        let quote: QuoteResponse = serde_json::from_value(MockTransport::sample_quote()).unwrap();
+        let orchestrator = SwapOrchestrator::new(sdk).with_deadline(Duration::from_secs(10));
+
+        let e = orchestrator.execute(&quote, "0x742d35Cc6634C0532925a3b844Bc454e4438f44e", &None, None, Duration::from_secs(1)).await.unwrap_err();
+        let Some(FogswapSdkError::DeadlineExceeded { last_known: Some(last_known) }) = e.downcast_ref() else {
+            panic!("expected a deadline error with the created transaction, got {:#}", e);
+        };
+        assert_eq!(last_known.id, "tx_1");
+        assert_eq!(last_known.status, TransactionStatus::Waiting);
+    }
+}
@@ -0,0 +1,456 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::endpoint::parse_result;
+use crate::req_structs::{ListTxParams, TokenListFilter};
+use crate::resp_structs::{Paginated, TokenList, TransactionInfo};
+use crate::rt::Instant;
+use crate::FogswapSdk;
+
+pub use crate::resp_structs::PageCursor;
+
+/// Transactions per page when `ListTxParams::limit` is unset
+const DEFAULT_TX_PAGE_SIZE: u32 = 50;
+
+/// Limits of a paginated fetch
+#[derive(Debug, Clone)]
+pub struct PageOptions {
+    /// Entries requested per page
+    pub page_size: u32,
+    /// Stop after this many requests
+    pub max_pages: Option<u32>,
+    /// Stop after this many entries
+    pub max_items: Option<usize>,
+    /// Where to start, e.g. the last cursor persisted by an earlier run
+    pub start: PageCursor,
+    /// Minimum time between two page requests, on top of any client rate limit
+    pub min_interval: Option<Duration>,
+}
+
+impl Default for PageOptions {
+    fn default() -> Self {
+        Self { page_size: 50, max_pages: None, max_items: None, start: PageCursor::default(), min_interval: None }
+    }
+}
+
+impl PageOptions {
+
+    pub fn new(page_size: u32) -> Self {
+        Self { page_size, ..Default::default() }
+    }
+
+    pub fn max_pages(mut self, max_pages: u32) -> Self {
+        self.max_pages = Some(max_pages);
+        self
+    }
+
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    pub fn resume_from(mut self, cursor: PageCursor) -> Self {
+        self.start = cursor;
+        self
+    }
+
+    pub fn min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = Some(interval);
+        self
+    }
+}
+
+/// Entries of one page and where the listing continues
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor to resume from after this page, `None` once the listing is exhausted
+    pub next: Option<PageCursor>,
+}
+
+/// Items of a paginated listing, fetching pages lazily as the stream is polled
+///
+/// Built on any page fetcher with [`PageStream::new`], or on a GET list endpoint
+/// with [`FogswapSdk::paginate`]. The stream ends after the last page.
+pub struct PageStream<'a, T> {
+    inner: BoxStream<'a, Result<T>>,
+}
+
+impl<'a, T: Send + 'a> PageStream<'a, T> {
+
+    /// Stream the items of the pages `fetch` returns, starting at `start`
+    ///
+    /// `fetch` requests the page of the cursor it is given; the `skip` entries of
+    /// the first page are dropped.
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use fogswap_sdk_rust::{PageCursor, PageStream, Paginated};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let pages = PageStream::new(PageCursor::default(), 2, |cursor: PageCursor| async move {
+    ///     let items: Vec<u32> = match cursor.page {
+    ///         1 => vec![1, 2],
+    ///         2 => vec![3],
+    ///         _ => unreachable!(),
+    ///     };
+    ///     Ok(Paginated::from(items))
+    /// });
+    /// assert_eq!(pages.try_collect::<Vec<_>>().await?, vec![1, 2, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new<F, Fut>(start: PageCursor, page_size: u32, fetch: F) -> Self
+    where
+        F: FnMut(PageCursor) -> Fut + Send + 'a,
+        Fut: Future<Output = Result<Paginated<T>>> + Send + 'a,
+    {
+        let page_size = page_size.max(1);
+        let inner = futures::stream::try_unfold((Some(start), fetch), move |(cursor, mut fetch)| async move {
+            let Some(cursor) = cursor else {
+                return Ok::<_, anyhow::Error>(None);
+            };
+            let page = fetch(cursor).await?;
+            let next = page.next_cursor(cursor, page_size);
+            let items = page.items.into_iter().skip(cursor.skip).map(Ok::<_, anyhow::Error>);
+            Ok(Some((futures::stream::iter(items), (next, fetch))))
+        })
+        .try_flatten()
+        .boxed();
+        Self { inner }
+    }
+}
+
+impl<T> Stream for PageStream<'_, T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+struct PagerState {
+    cursor: Option<PageCursor>,
+    pages: u32,
+    items: usize,
+    last_request: Option<Instant>,
+}
+
+impl FogswapSdk {
+
+    /// Stream the items of a paginated GET endpoint, from page `start`
+    ///
+    /// `page` and `limit` are added to `params`; the response `result` is decoded as
+    /// a [`Paginated`], so endpoints answering with a bare array work too.
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If a page cannot be retrieved
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    /// use fogswap_sdk_rust::{FogswapSdk, PageCursor};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct RatePoint { time: i64, rate: f64 }
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let params = json!({ "pair": "sol_eth" });
+    /// let mut points = std::pin::pin!(sdk.paginate::<RatePoint>("/market/rates/history", params, 100, PageCursor::default()));
+    /// while let Some(point) = points.try_next().await? {
+    ///     println!("{}: {}", point.time, point.rate);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn paginate<T>(&self, endpoint: &str, params: Value, page_size: u32, start: PageCursor) -> PageStream<'_, T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let endpoint = endpoint.to_string();
+        let page_size = page_size.max(1);
+        PageStream::new(start, page_size, move |cursor| {
+            let (endpoint, mut params) = (endpoint.clone(), params.clone());
+            async move {
+                if let Value::Object(fields) = &mut params {
+                    fields.insert("page".to_string(), cursor.page.into());
+                    fields.insert("limit".to_string(), page_size.into());
+                }
+                let resp = self.send_request(reqwest::Method::GET, &endpoint, Some(params)).await?;
+                parse_result(&endpoint, resp)
+            }
+        })
+    }
+
+    /// Fetch the pages `fetch` returns within `options`
+    ///
+    /// `fetch` requests the page of the cursor it is given, with the page size of
+    /// `options`. Every page request waits for `min_interval` since the previous one,
+    /// on top of the client rate limit (`with_rate_limit`) and request queue that calls
+    /// made by `fetch` pass through. The stream ends when the listing is exhausted or a
+    /// cap is reached; the `next` cursor of the last page yielded resumes exactly where
+    /// it stopped.
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use fogswap_sdk_rust::{FogswapSdk, PageOptions, Paginated};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let sdk = FogswapSdk::new();
+    /// let pages = sdk.pages(PageOptions::new(2).max_items(3), |cursor, page_size| async move {
+    ///     let first = (cursor.page - 1) * page_size;
+    ///     Ok(Paginated::from((first..first + page_size).collect::<Vec<u32>>()))
+    /// });
+    /// let pages: Vec<_> = pages.try_collect().await?;
+    /// assert_eq!(pages[1].items, vec![2]);
+    /// assert_eq!(pages[1].next.map(|next| (next.page, next.skip)), Some((2, 1)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pages<'a, T, F, Fut>(&'a self, options: PageOptions, fetch: F) -> impl Stream<Item = Result<Page<T>>> + 'a
+    where
+        T: 'a,
+        F: FnMut(PageCursor, u32) -> Fut + 'a,
+        Fut: Future<Output = Result<Paginated<T>>> + 'a,
+    {
+        let page_size = options.page_size.max(1);
+        let state = PagerState { cursor: Some(options.start), pages: 0, items: 0, last_request: None };
+        futures::stream::try_unfold((state, fetch), move |(mut state, mut fetch)| {
+            let options = options.clone();
+            async move {
+                let Some(cursor) = state.cursor else {
+                    return Ok::<_, anyhow::Error>(None);
+                };
+                if options.max_pages.is_some_and(|max| state.pages >= max)
+                    || options.max_items.is_some_and(|max| state.items >= max) {
+                    return Ok(None);
+                }
+                if let (Some(interval), Some(last)) = (options.min_interval, state.last_request) {
+                    self.clock.sleep_until(last + interval).await;
+                }
+                state.last_request = Some(self.clock.now());
+
+                let page = fetch(cursor, page_size).await?;
+                state.pages += 1;
+                let next = page.next_cursor(cursor, page_size);
+                let fetched = page.items.len();
+
+                let remaining = options.max_items.map_or(usize::MAX, |max| max - state.items);
+                let items: Vec<T> = page.items.into_iter().skip(cursor.skip).take(remaining).collect();
+                state.items += items.len();
+                let consumed = cursor.skip + items.len();
+
+                state.cursor = if consumed < fetched {
+                    Some(PageCursor { page: cursor.page, skip: consumed })
+                } else {
+                    next
+                };
+                Ok(Some((Page { items, next: state.cursor }, (state, fetch))))
+            }
+        })
+    }
+
+    /// Fetch the token list page by page within `options`, see [`FogswapSdk::pages`]
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If a page cannot be retrieved
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use fogswap_sdk_rust::{FogswapSdk, PageCursor, PageOptions, TokenListFilter};
+    ///
+    /// # async fn example(saved: Option<PageCursor>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_rate_limit(2.0, 1);
+    /// let options = PageOptions::new(20).max_pages(10).max_items(500).resume_from(saved.unwrap_or_default());
+    /// let mut pages = std::pin::pin!(sdk.token_list_pages(TokenListFilter::default(), options));
+    /// while let Some(page) = pages.try_next().await? {
+    ///     println!("{} networks, resume at {:?}", page.items.len(), page.next);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn token_list_pages(
+        &self,
+        filter: TokenListFilter,
+        options: PageOptions
+    ) -> impl futures::Stream<Item = Result<Page<TokenList>>> + '_ {
+        self.pages(options, move |cursor, page_size| {
+            let filter = TokenListFilter { page: Some(cursor.page), limit: Some(page_size), ..filter.clone() };
+            async move { Ok(Paginated::from(self.get_token_list_filtered(filter).await?)) }
+        })
+    }
+
+    /// Fetch the transactions matching `params` page by page within `options`, see
+    /// [`FogswapSdk::pages`]
+    ///
+    /// For long histories: cap the fetch with `max_pages` / `max_items`, pace it with
+    /// `min_interval`, persist the `next` cursor and pass it to `resume_from` in the
+    /// next run. `options` sets the page and page size; those of `params` are ignored.
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If a page cannot be retrieved
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use futures::TryStreamExt;
+    /// use fogswap_sdk_rust::{FogswapSdk, ListTxParams, PageCursor, PageOptions};
+    ///
+    /// # async fn example(saved: Option<PageCursor>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_api_key("YOUR_API_KEY");
+    /// let options = PageOptions::new(100)
+    ///     .max_pages(20)
+    ///     .min_interval(Duration::from_millis(500))
+    ///     .resume_from(saved.unwrap_or_default());
+    /// let mut pages = std::pin::pin!(sdk.transaction_pages(ListTxParams::default(), options));
+    /// while let Some(page) = pages.try_next().await? {
+    ///     println!("{} transactions, resume at {:?}", page.items.len(), page.next);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transaction_pages(
+        &self,
+        params: ListTxParams,
+        options: PageOptions
+    ) -> impl futures::Stream<Item = Result<Page<TransactionInfo>>> + '_ {
+        self.pages(options, move |cursor, page_size| {
+            let params = ListTxParams { page: Some(cursor.page), limit: Some(page_size), ..params.clone() };
+            async move {
+                let listed = self.list_transactions(&params).await?;
+                Ok(Paginated { items: listed.items, total: None, has_more: Some(listed.next.is_some()) })
+            }
+        })
+    }
+
+    /// List one page of the transactions created with this client's credentials, newest first
+    /// # Arguments
+    /// * `params` - Filters and page; `page` defaults to 1 and `limit` to 50
+    /// # Returns
+    /// * `Page<TransactionInfo>` - The transactions and the cursor of the next page
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the transactions cannot be retrieved
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, ListTxParams, TransactionStatus};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_api_key("YOUR_API_KEY");
+    /// let page = sdk.list_transactions(&ListTxParams {
+    ///     status: Some(TransactionStatus::Finished),
+    ///     limit: Some(20),
+    ///     ..Default::default()
+    /// }).await?;
+    /// for tx_info in &page.items {
+    ///     println!("{}: {} {}", tx_info.id, tx_info.amount_to, tx_info.contract_address_to);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_transactions(&self, params: &ListTxParams) -> Result<Page<TransactionInfo>> {
+        let endpoint = "/transaction/list";
+        let limit = params.limit.unwrap_or(DEFAULT_TX_PAGE_SIZE).max(1);
+        let page = params.page.unwrap_or(1).max(1);
+        let params = ListTxParams { limit: Some(limit), page: Some(page), ..params.clone() };
+
+        let resp = self.send_request(reqwest::Method::GET, endpoint, Some(serde_json::to_value(&params)?)).await?;
+        let listed: Paginated<TransactionInfo> = parse_result(endpoint, resp)?;
+        let next = listed.next_cursor(PageCursor { page, skip: 0 }, limit);
+        Ok(Page { items: listed.items, next })
+    }
+
+    /// Stream every transaction matching `params`, requesting pages lazily from `params.page`
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use fogswap_sdk_rust::{FogswapSdk, ListTxParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_api_key("YOUR_API_KEY");
+    /// let params = ListTxParams { created_after: Some(1_700_000_000), ..Default::default() };
+    /// let mut txs = std::pin::pin!(sdk.iter_transactions(params));
+    /// while let Some(tx_info) = txs.try_next().await? {
+    ///     println!("{} {}", tx_info.id, tx_info.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_transactions(&self, params: ListTxParams) -> PageStream<'_, TransactionInfo> {
+        let start = PageCursor { page: params.page.unwrap_or(1).max(1), skip: 0 };
+        let limit = params.limit.unwrap_or(DEFAULT_TX_PAGE_SIZE).max(1);
+        PageStream::new(start, limit, move |cursor| {
+            let params = ListTxParams { page: Some(cursor.page), limit: Some(limit), ..params.clone() };
+            async move {
+                let listed = self.list_transactions(&params).await?;
+                Ok(Paginated { items: listed.items, total: None, has_more: Some(listed.next.is_some()) })
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::TryStreamExt;
+    use serde_json::{json, Value};
+
+    use crate::testing::MockTransport;
+    use crate::{FogswapSdk, ListTxParams, PageOptions, TransportResponse};
+
+    fn page(ids: &[&str]) -> TransportResponse {
+        let txs: Vec<Value> = ids.iter().map(|id| {
+            let mut tx = MockTransport::sample_transaction_info();
+            tx["id"] = json!(id);
+            tx
+        }).collect();
+        TransportResponse::result(json!(txs))
+    }
+
+    fn ids(pages: &[crate::Page<crate::TransactionInfo>]) -> Vec<String> {
+        pages.iter().flat_map(|page| page.items.iter().map(|tx| tx.id.clone())).collect()
+    }
+
+    #[tokio::test]
+    async fn transaction_pages_stop_at_caps_and_resume() {
+        let mock = Arc::new(MockTransport::new());
+        mock.once("/transaction/list", page(&["a", "b"]))
+            .once("/transaction/list", page(&["c", "d"]))
+            // Resumed run: page 2 again, then the short last page
+            .once("/transaction/list", page(&["c", "d"]))
+            .once("/transaction/list", page(&["e"]));
+        let sdk = FogswapSdk::new().with_transport(mock.clone());
+
+        let first: Vec<_> = sdk.transaction_pages(ListTxParams::default(), PageOptions::new(2).max_items(3))
+            .try_collect().await.unwrap();
+        assert_eq!(ids(&first), ["a", "b", "c"]);
+        let saved = first.last().unwrap().next.unwrap();
+        assert_eq!((saved.page, saved.skip), (2, 1));
+
+        let rest: Vec<_> = sdk.transaction_pages(ListTxParams::default(), PageOptions::new(2).resume_from(saved))
+            .try_collect().await.unwrap();
+        assert_eq!(ids(&rest), ["d", "e"]);
+        assert!(rest.last().unwrap().next.is_none());
+
+        let pages: Vec<_> = mock.requests().iter().map(|r| r.query["page"].clone()).collect();
+        assert_eq!(pages, ["1", "2", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn transaction_pages_stop_at_max_pages() {
+        let mock = Arc::new(MockTransport::new());
+        mock.on("/transaction/list", page(&["a", "b"]));
+        let sdk = FogswapSdk::new().with_transport(mock.clone());
+
+        let pages: Vec<_> = sdk.transaction_pages(ListTxParams::default(), PageOptions::new(2).max_pages(2))
+            .try_collect().await.unwrap();
+        assert_eq!(ids(&pages), ["a", "b", "a", "b"]);
+        assert_eq!(mock.requests().len(), 2);
+    }
+}
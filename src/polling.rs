@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::{Stream, TryStreamExt};
+
+use crate::error::FogswapSdkError;
+use crate::resp_structs::TransactionInfo;
+use crate::rt::Instant;
+use crate::status::{StatusSequencer, TransactionStatus};
+use crate::FogswapSdk;
+
+/// Pace and limit of transaction status polling
+#[derive(Debug, Clone)]
+pub struct PollOptions {
+    /// Delay between two polls, and after every status change
+    pub interval: Duration,
+    /// Give up after this long, `None` to poll until a final status
+    pub timeout: Option<Duration>,
+    /// Factor applied to the delay after every poll without a change (1.0 = fixed pace)
+    pub backoff: f64,
+    /// Upper bound of the delay when backing off
+    pub max_interval: Duration,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(10),
+            timeout: None,
+            backoff: 1.0,
+            max_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+impl PollOptions {
+
+    fn next_interval(&self, interval: Duration) -> Duration {
+        interval.mul_f64(self.backoff.max(1.0)).min(self.max_interval.max(self.interval))
+    }
+}
+
+/// Progress of one `watch_transaction` stream
+struct Watch {
+    started: Instant,
+    last: Option<TransactionInfo>,
+    sequencer: StatusSequencer,
+    done: bool,
+}
+
+impl FogswapSdk {
+
+    /// Poll a transaction and emit it whenever its status changes
+    ///
+    /// The first item is the current state. Out-of-date poll results (a status behind
+    /// one already emitted) and repeated unknown statuses are skipped, as by
+    /// `StatusSequencer`. The stream ends after a final status, or
+    /// with `FogswapSdkError::DeadlineExceeded` once `options.timeout` has run out, and
+    /// with `FogswapSdkError::Shutdown` or `FogswapSdkError::Cancelled` if the client
+    /// is shut down or cancelled, even between two polls.
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use fogswap_sdk_rust::{FogswapSdk, PollOptions};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let mut updates = std::pin::pin!(sdk.watch_transaction("S7ZulO3j16", PollOptions::default()));
+    /// while let Some(tx_info) = updates.try_next().await? {
+    ///     println!("{}", tx_info.status.description());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch_transaction<'a>(
+        &'a self,
+        tx_id: &'a str,
+        options: PollOptions
+    ) -> impl Stream<Item = Result<TransactionInfo>> + 'a {
+        let watch = Watch { started: self.clock.now(), last: None, sequencer: StatusSequencer::default(), done: false };
+        futures::stream::try_unfold(watch, move |mut watch| {
+            let options = options.clone();
+            async move {
+                if watch.done {
+                    return Ok(None);
+                }
+                let mut interval = options.interval;
+                let mut polled = false;
+                loop {
+                    if watch.last.is_some() || polled {
+                        if let Some(timeout) = options.timeout
+                            && self.clock.now().saturating_duration_since(watch.started) + interval > timeout {
+                            let last_known = watch.last.map(Box::new);
+                            return Err(FogswapSdkError::DeadlineExceeded { last_known }.into());
+                        }
+                        self.until_shutdown(async { self.clock.sleep(interval).await; Ok(()) }).await?;
+                        interval = options.next_interval(interval);
+                    }
+                    polled = true;
+
+                    let info = self.get_transaction_info(tx_id).await?;
+                    if !watch.sequencer.observe(tx_id, info.status.clone()).is_empty() {
+                        watch.done = info.status.is_terminal();
+                        watch.last = Some(info.clone());
+                        return Ok(Some((info, watch)));
+                    }
+                }
+            }
+        })
+    }
+
+    /// Poll a transaction until it reaches one of `targets` or a final status
+    ///
+    /// A poll that finds the transaction already past a target, e.g. exchanging while
+    /// waiting for confirming, returns as well.
+    /// # Arguments
+    /// * `tx_id` - The id of the transaction
+    /// * `targets` - Statuses to wait for
+    /// * `options` - Poll pace and timeout
+    /// # Errors
+    /// * `FogswapSdkError::DeadlineExceeded` - If `options.timeout` runs out first
+    /// * `FogswapSdkError::Cancelled` - If the token of `with_cancellation` is cancelled while waiting
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::{FogswapSdk, PollOptions, TransactionStatus};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tx_info = sdk.wait_for_status("S7ZulO3j16", &[TransactionStatus::Confirming], PollOptions {
+    ///     interval: Duration::from_secs(5),
+    ///     timeout: Some(Duration::from_secs(30 * 60)),
+    ///     ..Default::default()
+    /// }).await?;
+    /// println!("Deposit seen, status {}", tx_info.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all, fields(tx_id = %tx_id)))]
+    pub async fn wait_for_status(
+        &self,
+        tx_id: &str,
+        targets: &[TransactionStatus],
+        options: PollOptions
+    ) -> Result<TransactionInfo> {
+        let mut updates = std::pin::pin!(self.watch_transaction(tx_id, options));
+        while let Some(info) = updates.try_next().await? {
+            if targets.iter().any(|target| info.status.is_at_or_past(target)) || info.status.is_terminal() {
+                return Ok(info);
+            }
+        }
+        unreachable!("the watch stream only ends after a final status or an error")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use crate::testing::MockTransport;
+    use crate::{FogswapSdk, PollOptions, TransactionStatus, TransportResponse};
+
+    fn info(status: &str) -> TransportResponse {
+        let mut info = MockTransport::sample_transaction_info();
+        info["status"] = json!(status);
+        TransportResponse::result(info)
+    }
+
+    #[tokio::test]
+    async fn wait_for_status_returns_when_a_poll_skips_the_target() {
+        let mock = Arc::new(MockTransport::new());
+        mock.once("/transaction/info", info("waiting"))
+            .once("/transaction/info", info("exchanging"))
+            .on("/transaction/info", info("finished"));
+        let sdk = FogswapSdk::new().with_transport(mock.clone());
+
+        let options = PollOptions { interval: Duration::from_millis(10), ..Default::default() };
+        let tx_info = sdk.wait_for_status("tx_1", &[TransactionStatus::Confirming], options).await.unwrap();
+
+        assert_eq!(tx_info.status, TransactionStatus::Exchanging);
+        assert_eq!(mock.requests().iter().filter(|r| r.endpoint == "/transaction/info").count(), 2);
+    }
+}
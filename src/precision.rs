@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+
+use crate::resp_structs::{TokenInfo, TokenList, TransactionInfo};
+use crate::FogswapSdk;
+
+/// Maximum number of decimal places of amounts sent to the API
+///
+/// Amounts are rounded down, so the API never sees more precision than the asset
+/// supports and never more than the caller holds.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::AmountPrecision;
+///
+/// let precision = AmountPrecision::with_default(8).with_token("eth", "USDT", 6);
+/// assert_eq!(precision.round_down("eth", "usdt", 1.23456789), 1.234567);
+/// assert_eq!(precision.round_down("btc", "BTC", 0.123456789), 0.12345678);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AmountPrecision {
+    /// Decimal places of tokens without their own entry, `None` to keep their amounts unchanged
+    pub default_decimals: Option<u32>,
+    /// Decimal places per network and lowercased contract address
+    pub token_decimals: HashMap<(String, String), u32>,
+}
+
+impl AmountPrecision {
+
+    pub fn with_default(decimals: u32) -> Self {
+        Self { default_decimals: Some(decimals), token_decimals: HashMap::new() }
+    }
+
+    pub fn with_token(mut self, network: &str, contract_address: &str, decimals: u32) -> Self {
+        self.token_decimals.insert(Self::key(network, contract_address), decimals);
+        self
+    }
+
+    /// Take the decimals of every token of `tokens` that reports them
+    pub fn with_token_list(mut self, tokens: &[TokenList]) -> Self {
+        for token in tokens.iter().flat_map(|list| &list.tokens) {
+            if let Some(decimals) = token.decimals {
+                self.token_decimals.insert(Self::key(&token.network, &token.contract_address), decimals);
+            }
+        }
+        self
+    }
+
+    pub fn decimals(&self, network: &str, contract_address: &str) -> Option<u32> {
+        self.token_decimals.get(&Self::key(network, contract_address)).copied().or(self.default_decimals)
+    }
+
+    /// `amount` cut to the decimal places of the token
+    pub fn round_down(&self, network: &str, contract_address: &str, amount: f64) -> f64 {
+        match self.decimals(network, contract_address) {
+            Some(decimals) => round_down(amount, decimals),
+            None => amount,
+        }
+    }
+
+    fn key(network: &str, contract_address: &str) -> (String, String) {
+        (network.to_string(), contract_address.to_lowercase())
+    }
+}
+
+/// Cut `amount` to `decimals` decimal places
+///
+/// Works on the shortest decimal representation of the float, so `0.3` stays
+/// `0.3` instead of becoming `0.29999...` through binary multiplication.
+pub fn round_down(amount: f64, decimals: u32) -> f64 {
+    let repr = amount.to_string();
+    let Some((int, frac)) = repr.split_once('.') else {
+        return amount;
+    };
+    if frac.len() <= decimals as usize {
+        return amount;
+    }
+    format!("{}.{}", int, &frac[..decimals as usize]).parse().unwrap_or(amount)
+}
+
+/// How amounts are written for display
+///
+/// Amounts are cut to the token's decimal places (rounding down, so a UI never shows
+/// more than will be received) and trailing zeros are dropped.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::AmountFormat;
+/// use fogswap_sdk_rust::resp_structs::TokenInfo;
+///
+/// let usdt: TokenInfo = serde_json::from_value(serde_json::json!({ "token": "USDT", "decimals": 6 })).unwrap();
+/// assert_eq!(AmountFormat::default().format(0.000001234567, &usdt), "0.000001 USDT");
+///
+/// let de = AmountFormat::default().decimal_separator(',').group_separator('.').without_symbol();
+/// assert_eq!(de.format(1234567.5, &usdt), "1.234.567,5");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AmountFormat {
+    pub decimal_separator: char,
+    /// Separator of thousands in the integer part, `None` for no grouping
+    pub group_separator: Option<char>,
+    /// Append the token symbol
+    pub symbol: bool,
+    /// Decimal places of tokens that do not report theirs
+    pub default_decimals: u32,
+}
+
+impl Default for AmountFormat {
+    fn default() -> Self {
+        Self { decimal_separator: '.', group_separator: None, symbol: true, default_decimals: 8 }
+    }
+}
+
+impl AmountFormat {
+
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    pub fn group_separator(mut self, separator: char) -> Self {
+        self.group_separator = Some(separator);
+        self
+    }
+
+    pub fn without_symbol(mut self) -> Self {
+        self.symbol = false;
+        self
+    }
+
+    /// `amount` of `token`, at the token's precision
+    pub fn format(&self, amount: f64, token: &TokenInfo) -> String {
+        let number = self.format_number(amount, token.decimals.unwrap_or(self.default_decimals));
+        if self.symbol {
+            format!("{} {}", number, token.token)
+        } else {
+            number
+        }
+    }
+
+    /// `amount` with at most `decimals` decimal places and no symbol
+    pub fn format_number(&self, amount: f64, decimals: u32) -> String {
+        if !amount.is_finite() {
+            return amount.to_string();
+        }
+        let repr = format!("{:.*}", decimals as usize, round_down(amount, decimals));
+        let (int, frac) = repr.split_once('.').unwrap_or((&repr, ""));
+        let (sign, digits) = int.strip_prefix('-').map_or(("", int), |digits| ("-", digits));
+        let mut out = sign.to_string();
+        for (i, digit) in digits.chars().enumerate() {
+            if let Some(separator) = self.group_separator
+                && i > 0 && (digits.len() - i) % 3 == 0 {
+                out.push(separator);
+            }
+            out.push(digit);
+        }
+        let frac = frac.trim_end_matches('0');
+        if !frac.is_empty() {
+            out.push(self.decimal_separator);
+            out.push_str(frac);
+        }
+        out
+    }
+
+    /// Token of `contract_address` on `network` in `tokens`, written with its symbol, or
+    /// at the default precision followed by `contract_address` when it is not listed
+    fn format_listed(&self, amount: f64, network: &str, contract_address: &str, tokens: &[TokenList]) -> String {
+        let token = tokens.iter()
+            .filter(|list| list.network.eq_ignore_ascii_case(network))
+            .find_map(|list| list.find(contract_address));
+        match token {
+            Some(token) => self.format(amount, token),
+            None if self.symbol => format!("{} {}", self.format_number(amount, self.default_decimals), contract_address),
+            None => self.format_number(amount, self.default_decimals),
+        }
+    }
+}
+
+/// `amount` of `token` at the token's precision, followed by its symbol
+pub fn format_amount(amount: f64, token: &TokenInfo) -> String {
+    AmountFormat::default().format(amount, token)
+}
+
+impl TransactionInfo {
+
+    /// `amount_from` with its symbol, at the precision the token list gives the token
+    pub fn display_amount_from(&self, tokens: &[TokenList]) -> String {
+        self.display_amount_from_with(tokens, &AmountFormat::default())
+    }
+
+    /// `amount_to` with its symbol, at the precision the token list gives the token
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tokens = sdk.get_token_list().await?;
+    /// let tx_info = sdk.get_transaction_info("S7ZulO3j16").await?;
+    /// println!("You receive {}", tx_info.display_amount_to(&tokens));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn display_amount_to(&self, tokens: &[TokenList]) -> String {
+        self.display_amount_to_with(tokens, &AmountFormat::default())
+    }
+
+    pub fn display_amount_from_with(&self, tokens: &[TokenList], format: &AmountFormat) -> String {
+        format.format_listed(self.amount_from, &self.network_from, &self.contract_address_from, tokens)
+    }
+
+    pub fn display_amount_to_with(&self, tokens: &[TokenList], format: &AmountFormat) -> String {
+        format.format_listed(self.amount_to, &self.network_to, &self.contract_address_to, tokens)
+    }
+}
+
+/// Slack allowed when comparing two amounts
+///
+/// Two amounts are equal when they differ by at most `absolute` or by at most
+/// `relative` times the larger of the two, so float noise from the API or from
+/// arithmetic does not flip a comparison.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::AmountTolerance;
+///
+/// let tolerance = AmountTolerance::relative(1e-6);
+/// assert!(tolerance.eq(0.1 + 0.2, 0.3));
+/// assert!(tolerance.at_least(99.99999999, 100.0));
+/// assert!(!tolerance.at_least(99.9, 100.0));
+/// assert!(!AmountTolerance::exact().eq(0.1 + 0.2, 0.3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmountTolerance {
+    /// Allowed difference as a share of the larger amount (1e-9 = one billionth)
+    pub relative: f64,
+    /// Allowed difference in token units
+    pub absolute: f64,
+}
+
+impl Default for AmountTolerance {
+    fn default() -> Self {
+        Self { relative: 1e-9, absolute: 0.0 }
+    }
+}
+
+impl AmountTolerance {
+
+    /// Exact f64 comparisons
+    pub fn exact() -> Self {
+        Self { relative: 0.0, absolute: 0.0 }
+    }
+
+    pub fn relative(relative: f64) -> Self {
+        Self { relative, absolute: 0.0 }
+    }
+
+    pub fn absolute(absolute: f64) -> Self {
+        Self { relative: 0.0, absolute }
+    }
+
+    pub fn with_absolute(mut self, absolute: f64) -> Self {
+        self.absolute = absolute;
+        self
+    }
+
+    /// Largest difference still considered equal when comparing `a` and `b`
+    pub fn allowance(&self, a: f64, b: f64) -> f64 {
+        self.absolute.max(self.relative * a.abs().max(b.abs()))
+    }
+
+    pub fn eq(&self, a: f64, b: f64) -> bool {
+        (a - b).abs() <= self.allowance(a, b)
+    }
+
+    /// `actual` is not below `min`, or below it by no more than the allowance
+    pub fn at_least(&self, actual: f64, min: f64) -> bool {
+        actual >= min - self.allowance(actual, min)
+    }
+
+    /// `actual` is not above `max`, or above it by no more than the allowance
+    pub fn at_most(&self, actual: f64, max: f64) -> bool {
+        actual <= max + self.allowance(actual, max)
+    }
+
+    /// Hashable form of `amount`, equal for amounts within the relative or absolute tolerance
+    ///
+    /// Amounts are bucketed, so two amounts that straddle a bucket boundary can still
+    /// get different keys.
+    pub fn key(&self, amount: f64) -> String {
+        if self.absolute > 0.0 {
+            return format!("{}", (amount / self.absolute).round());
+        }
+        if self.relative > 0.0 {
+            let digits = (-self.relative.log10()).floor().clamp(0.0, 16.0) as usize;
+            return format!("{:.*e}", digits, amount);
+        }
+        amount.to_string()
+    }
+}
+
+impl FogswapSdk {
+
+    /// Cut `amount_from` of quotes and created transactions to the decimal places of the source token
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{AmountPrecision, FogswapSdk};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tokens = sdk.get_token_list().await?;
+    /// let sdk = sdk.with_amount_precision(AmountPrecision::with_default(8).with_token_list(&tokens));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_amount_precision(mut self, precision: AmountPrecision) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Tolerance of amount comparisons: the `min_amount_to` floor, pair limits,
+    /// orchestrator slippage checks and duplicate-create keys
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{AmountTolerance, FogswapSdk};
+    ///
+    /// let sdk = FogswapSdk::new().with_amount_tolerance(AmountTolerance::relative(1e-8).with_absolute(1e-12));
+    /// ```
+    pub fn with_amount_tolerance(mut self, tolerance: AmountTolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
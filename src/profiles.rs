@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::time::Duration;
+
+use crate::FogswapSdk;
+
+/// Group of endpoints sharing a timeout and retry profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndpointClass {
+    Quote,
+    TokenList,
+    Create,
+    Info,
+    Other,
+}
+
+impl EndpointClass {
+    pub fn from_path(path: &str) -> Self {
+        match path {
+            "/transaction/quote" => EndpointClass::Quote,
+            "/market/tokens" => EndpointClass::TokenList,
+            "/transaction/create" => EndpointClass::Create,
+            "/transaction/info" => EndpointClass::Info,
+            _ => EndpointClass::Other,
+        }
+    }
+}
+
+/// Timeout and retry behaviour of one endpoint class
+#[derive(Debug, Clone)]
+pub struct RequestProfile {
+    /// Timeout of a single attempt, `None` for no timeout
+    pub timeout: Option<Duration>,
+    /// Number of retries after a network error or 5xx response
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for every further retry
+    pub backoff: Duration,
+}
+
+/// Request profiles per endpoint class
+///
+/// Defaults: quotes fail fast (2s, one retry), the token list may take longer
+/// (15s, two retries) and creation is never retried.
+#[derive(Debug, Clone)]
+pub struct RequestProfiles {
+    profiles: HashMap<EndpointClass, RequestProfile>,
+}
+
+impl Default for RequestProfiles {
+    fn default() -> Self {
+        let profile = |timeout: u64, max_retries: u32| RequestProfile {
+            timeout: Some(Duration::from_secs(timeout)),
+            max_retries,
+            backoff: Duration::from_millis(250),
+        };
+        let profiles = HashMap::from([
+            (EndpointClass::Quote, profile(2, 1)),
+            (EndpointClass::TokenList, profile(15, 2)),
+            (EndpointClass::Create, profile(30, 0)),
+            (EndpointClass::Info, profile(10, 2)),
+            (EndpointClass::Other, profile(10, 0)),
+        ]);
+        Self { profiles }
+    }
+}
+
+impl RequestProfiles {
+
+    pub fn get(&self, class: EndpointClass) -> &RequestProfile {
+        self.profiles
+            .get(&class)
+            .or_else(|| self.profiles.get(&EndpointClass::Other))
+            .unwrap_or(&NO_RETRY)
+    }
+
+    pub fn set(&mut self, class: EndpointClass, profile: RequestProfile) {
+        self.profiles.insert(class, profile);
+    }
+
+    /// Apply `options` to every endpoint class
+    pub fn apply(&mut self, options: &RequestOptions) {
+        for profile in self.profiles.values_mut() {
+            if let Some(timeout) = options.timeout {
+                profile.timeout = Some(timeout);
+            }
+            if let Some(retries) = options.retries {
+                profile.max_retries = retries;
+            }
+        }
+    }
+}
+
+/// Timeout and retries applied on top of every endpoint profile
+///
+/// `None` fields keep the profile's own value.
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Timeout of a single attempt
+    pub timeout: Option<Duration>,
+    /// Number of retries after a retryable failure
+    pub retries: Option<u32>,
+}
+
+const NO_RETRY: RequestProfile = RequestProfile { timeout: None, max_retries: 0, backoff: Duration::ZERO };
+
+/// What is retried, and how backoff delays are spread
+///
+/// The number of retries and the base backoff come from each endpoint's `RequestProfile`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Response statuses retried, besides network errors
+    pub retryable_statuses: Vec<u16>,
+    /// Also retry every POST request, even without an idempotency key; a retried
+    /// create may then create the transaction twice
+    pub retry_posts: bool,
+    /// Also retry POST requests carrying an idempotency key, which the API creates
+    /// at most once; off by default, like `retry_posts`
+    pub retry_idempotent_posts: bool,
+    /// Random fraction (0.0 to 1.0) of every delay added or removed, so clients
+    /// failing together do not retry in lockstep
+    pub jitter: f64,
+    /// Upper bound of a single delay
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retryable_statuses: (500..600).collect(),
+            retry_posts: false,
+            retry_idempotent_posts: false,
+            jitter: 0.2,
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+
+    pub fn is_retryable_status(&self, status: u16) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    pub fn allows(&self, method: &reqwest::Method) -> bool {
+        *method != reqwest::Method::POST || self.retry_posts
+    }
+
+    /// Like `allows`, also letting through POSTs made safe by an idempotency key
+    /// when `retry_idempotent_posts` is set
+    pub(crate) fn allows_request(&self, method: &reqwest::Method, payload: &Option<serde_json::Value>) -> bool {
+        self.allows(method) || (self.retry_idempotent_posts
+            && payload.as_ref().is_some_and(|p| p.get("idempotency_key").is_some_and(|key| !key.is_null())))
+    }
+
+    /// Delay before retry number `retry` (0-based): `base` doubled per retry, capped and jittered
+    pub fn backoff(&self, base: Duration, retry: u32) -> Duration {
+        let delay = base.saturating_mul(2u32.saturating_pow(retry)).min(self.max_backoff);
+        // Uniform in [-1, 1), from the randomly seeded std hasher
+        let unit = RandomState::new().hash_one(retry) as f64 / u64::MAX as f64 * 2.0 - 1.0;
+        delay.mul_f64((1.0 + self.jitter.clamp(0.0, 1.0) * unit).max(0.0))
+    }
+}
+
+impl FogswapSdk {
+
+    /// Override the timeout and retry profile of an endpoint class
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::{EndpointClass, FogswapSdk, RequestProfile};
+    ///
+    /// let sdk = FogswapSdk::new().with_request_profile(EndpointClass::Quote, RequestProfile {
+    ///     timeout: Some(Duration::from_millis(1500)),
+    ///     max_retries: 0,
+    ///     backoff: Duration::ZERO,
+    /// });
+    /// ```
+    pub fn with_request_profile(mut self, class: EndpointClass, profile: RequestProfile) -> Self {
+        self.profiles.set(class, profile);
+        self
+    }
+
+    /// Give every endpoint class the same attempt timeout
+    ///
+    /// An attempt that runs out fails with `FogswapSdkError::Timeout`, and is retried
+    /// like a network error.
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// let sdk = FogswapSdk::new().with_timeout(Duration::from_secs(3));
+    /// ```
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_request_options(RequestOptions { timeout: Some(timeout), retries: None })
+    }
+
+    /// Override the timeout and retries of every endpoint class
+    ///
+    /// Clones share their caches and connections, so a clone carrying its own
+    /// options is a cheap way to tune a single call.
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::{Asset, FogswapSdk, FogswapSdkError, QuoteRequest, RequestOptions};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let req = QuoteRequest::new(&Asset::native("sol"), &Asset::native("eth"), 0.5);
+    /// let fast = sdk.clone().with_request_options(RequestOptions {
+    ///     timeout: Some(Duration::from_millis(500)),
+    ///     retries: Some(0),
+    /// });
+    /// match fast.get_quote_with(&req).await {
+    ///     Ok(quote) => println!("{}", quote.amount_to),
+    ///     Err(e) if matches!(e.downcast_ref(), Some(FogswapSdkError::Timeout { .. })) => println!("too slow, skipping"),
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_request_options(mut self, options: RequestOptions) -> Self {
+        self.profiles.apply(&options);
+        self
+    }
+
+    /// Choose which failures are retried and how backoff delays are spread
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, RetryPolicy};
+    ///
+    /// let sdk = FogswapSdk::new().with_retry_policy(RetryPolicy {
+    ///     retryable_statuses: vec![429, 502, 503, 504],
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use crate::testing::MockTransport;
+    use crate::{CreateTransactionRequest, EndpointClass, FogswapSdk, RequestProfile, RetryPolicy, TransportResponse};
+
+    #[tokio::test]
+    async fn keyed_creates_are_retried_only_with_the_opt_in() {
+        let retrying = RequestProfile { timeout: None, max_retries: 1, backoff: Duration::ZERO };
+        let req = CreateTransactionRequest {
+            network_from: "sol".to_string(),
+            contract_address_from: "SOL".to_string(),
+            network_to: "eth".to_string(),
+            contract_address_to: "ETH".to_string(),
+            amount_from: 1.0,
+            payout_address: "0xpayout".to_string(),
+            ..Default::default()
+        };
+        let creates = |mock: &MockTransport| mock.requests().into_iter().filter(|r| r.endpoint == "/transaction/create").collect::<Vec<_>>();
+
+        let mock = Arc::new(MockTransport::new());
+        mock.once("/transaction/create", TransportResponse::json(500, &json!({ "message": "try again" })));
+        let sdk = FogswapSdk::new().with_transport(mock.clone()).with_request_profile(EndpointClass::Create, retrying.clone());
+        assert!(sdk.create_transaction_with(&req).await.is_err());
+        assert_eq!(creates(&mock).len(), 1);
+
+        let mock = Arc::new(MockTransport::new());
+        mock.once("/transaction/create", TransportResponse::json(500, &json!({ "message": "try again" })));
+        let sdk = FogswapSdk::new()
+            .with_transport(mock.clone())
+            .with_request_profile(EndpointClass::Create, retrying)
+            .with_retry_policy(RetryPolicy { retry_idempotent_posts: true, ..Default::default() });
+        sdk.create_transaction_with(&req).await.unwrap();
+        let sent = creates(&mock);
+        assert_eq!(sent.len(), 2);
+        let key = |i: usize| sent[i].body.as_ref().unwrap()["idempotency_key"].clone();
+        assert!(key(0).is_string());
+        assert_eq!(key(0), key(1));
+    }
+}
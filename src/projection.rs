@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::StreamExt;
+
+use crate::client_metadata::TrackedTransaction;
+use crate::req_structs::Pair;
+use crate::resp_structs::TransactionInfo;
+use crate::status::TransactionStatus;
+use crate::FogswapSdk;
+
+/// Which tracked transactions `tracked_transactions` returns
+#[derive(Debug, Clone, Default)]
+pub struct TrackedFilter {
+    pub status: Option<TransactionStatus>,
+    pub pair: Option<Pair>,
+    /// Only transactions created at most this long ago
+    pub max_age: Option<Duration>,
+    /// Only transactions created at least this long ago
+    pub min_age: Option<Duration>,
+}
+
+impl TrackedFilter {
+
+    fn matches(&self, info: &TransactionInfo, now: i64) -> bool {
+        let age = now.saturating_sub(info.created_at);
+        self.status.as_ref().is_none_or(|s| *s == info.status)
+            && self.pair.as_ref().is_none_or(|p| {
+                p.network_from == info.network_from
+                    && p.contract_address_from.eq_ignore_ascii_case(&info.contract_address_from)
+                    && p.network_to == info.network_to
+                    && p.contract_address_to.eq_ignore_ascii_case(&info.contract_address_to)
+            })
+            && self.max_age.is_none_or(|max| age <= max.as_secs() as i64)
+            && self.min_age.is_none_or(|min| age >= min.as_secs() as i64)
+    }
+}
+
+/// Status change seen by `reconcile_statuses`
+#[derive(Debug, Clone)]
+pub struct StatusChange {
+    pub tx_id: String,
+    /// Tracked status before the refresh, `None` if the transaction was not tracked
+    pub from: Option<TransactionStatus>,
+    pub to: TransactionStatus,
+}
+
+/// Outcome of `FogswapSdk::reconcile_statuses`
+#[derive(Debug, Default)]
+pub struct StatusReconcileReport {
+    pub changed: Vec<StatusChange>,
+    /// Transactions whose status did not change
+    pub unchanged: Vec<String>,
+    /// Transactions that could not be refreshed, with the reason
+    pub failed: Vec<(String, String)>,
+}
+
+/// Maximum number of lookups in flight during `reconcile_statuses`
+const RECONCILE_CONCURRENCY: usize = 8;
+
+/// Latest known state of every transaction this client created or looked up
+#[derive(Debug, Default)]
+pub(crate) struct TransactionProjection {
+    latest: Mutex<HashMap<String, TransactionInfo>>,
+}
+
+impl TransactionProjection {
+
+    pub fn update(&self, info: &TransactionInfo) {
+        let mut latest = self.latest.lock().unwrap_or_else(|e| e.into_inner());
+        latest.insert(info.id.clone(), info.clone());
+    }
+
+    fn query(&self, filter: &TrackedFilter, now: i64) -> Vec<TransactionInfo> {
+        let latest = self.latest.lock().unwrap_or_else(|e| e.into_inner());
+        let mut infos: Vec<TransactionInfo> = latest.values().filter(|info| filter.matches(info, now)).cloned().collect();
+        infos.sort_by_key(|info| std::cmp::Reverse(info.created_at));
+        infos
+    }
+
+    fn status(&self, tx_id: &str) -> Option<TransactionStatus> {
+        let latest = self.latest.lock().unwrap_or_else(|e| e.into_inner());
+        latest.get(tx_id).map(|info| info.status.clone())
+    }
+
+    fn remove(&self, tx_id: &str) {
+        let mut latest = self.latest.lock().unwrap_or_else(|e| e.into_inner());
+        latest.remove(tx_id);
+    }
+}
+
+impl FogswapSdk {
+
+    /// Keep the latest state of every transaction created or looked up through this
+    /// client (and its clones) in memory, queryable with `tracked_transactions`
+    pub fn with_transaction_tracking(mut self) -> Self {
+        self.projection = Some(std::sync::Arc::default());
+        self
+    }
+
+    pub(crate) fn track(&self, info: &TransactionInfo) {
+        if let Some(projection) = &self.projection {
+            projection.update(info);
+        }
+        #[cfg(feature = "store")]
+        if let Some(journal) = &self.journal {
+            // The call succeeded; a journal failure must not turn it into an error
+            let _ = journal.record(info);
+        }
+    }
+
+    /// Tracked transactions matching `filter`, newest first
+    ///
+    /// Empty unless `with_transaction_tracking()` is enabled. States are as of the last
+    /// `create_transaction*` or `get_transaction_info*` call that returned them.
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::{FogswapSdk, TrackedFilter, TransactionStatus};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_transaction_tracking();
+    /// let stuck = sdk.tracked_transactions(&TrackedFilter {
+    ///     status: Some(TransactionStatus::Exchanging),
+    ///     min_age: Some(Duration::from_secs(3600)),
+    ///     ..Default::default()
+    /// })?;
+    /// println!("{}", serde_json::to_string_pretty(&stuck)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tracked_transactions(&self, filter: &TrackedFilter) -> Result<Vec<TrackedTransaction>> {
+        let Some(projection) = &self.projection else {
+            return Ok(Vec::new());
+        };
+        projection.query(filter, self.clock.unix_time())
+            .into_iter()
+            .map(|info| {
+                let client_metadata = self.client_metadata(&info.id)?;
+                Ok(TrackedTransaction { info, client_metadata })
+            })
+            .collect()
+    }
+
+    /// JSON snapshot of every tracked transaction, newest first
+    pub fn export_tracked_transactions(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self.tracked_transactions(&TrackedFilter::default())?)?)
+    }
+
+    /// Stop tracking a transaction, e.g. once it has been archived
+    pub fn untrack_transaction(&self, tx_id: &str) {
+        if let Some(projection) = &self.projection {
+            projection.remove(tx_id);
+        }
+    }
+
+    /// Refresh the status of many transactions and report which ones changed
+    ///
+    /// Transactions are looked up with bounded concurrency, since the API has no bulk
+    /// lookup. Refreshed states replace the tracked ones, so the previous status is
+    /// only known for transactions tracked by `with_transaction_tracking()`; untracked
+    /// ones are reported as changed with `from: None`.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, TrackedFilter};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_transaction_tracking();
+    /// let ids: Vec<String> = sdk.tracked_transactions(&TrackedFilter::default())?
+    ///     .into_iter()
+    ///     .filter(|tx| !tx.info.status.is_terminal())
+    ///     .map(|tx| tx.info.id)
+    ///     .collect();
+    /// let report = sdk.reconcile_statuses(&ids).await;
+    /// for change in report.changed {
+    ///     println!("{}: {:?} -> {}", change.tx_id, change.from, change.to);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn reconcile_statuses<S: AsRef<str>>(&self, ids: &[S]) -> StatusReconcileReport {
+        let results: Vec<(String, Option<TransactionStatus>, Result<TransactionInfo>)> = futures::stream::iter(ids)
+            .map(|id| async move {
+                let id = id.as_ref();
+                let before = self.projection.as_ref().and_then(|projection| projection.status(id));
+                (id.to_string(), before, self.get_transaction_info(id).await)
+            })
+            .buffer_unordered(RECONCILE_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut report = StatusReconcileReport::default();
+        for (tx_id, from, result) in results {
+            match result {
+                Ok(info) if from.as_ref() == Some(&info.status) => report.unchanged.push(tx_id),
+                Ok(info) => report.changed.push(StatusChange { tx_id, from, to: info.status }),
+                Err(e) => report.failed.push((tx_id, e.to_string())),
+            }
+        }
+        report
+    }
+}
@@ -0,0 +1,54 @@
+use std::io::Cursor;
+
+use anyhow::Result;
+use image::{ImageFormat, Luma};
+use qrcode::render::svg;
+use qrcode::QrCode;
+
+use crate::resp_structs::TransactionInfo;
+
+/// Smallest side of a rendered QR code, in pixels
+const MIN_QR_SIZE: u32 = 256;
+
+impl TransactionInfo {
+
+    /// Data of the deposit QR code: the payment URI, or the bare payin address
+    /// for networks without one
+    fn payin_qr_code(&self) -> Result<QrCode> {
+        let data = self.payin_uri().unwrap_or_else(|| self.payin_address.clone());
+        Ok(QrCode::new(data.as_bytes())?)
+    }
+
+    /// PNG image of the deposit QR code, see [`payin_uri`](Self::payin_uri)
+    /// # Errors
+    /// * `qrcode::types::QrError` - If the data does not fit a QR code
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tx_info = FogswapSdk::new().get_transaction_info("S7ZulO3j16").await?;
+    /// std::fs::write("deposit.png", tx_info.payin_qr_png()?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn payin_qr_png(&self) -> Result<Vec<u8>> {
+        let image = self.payin_qr_code()?
+            .render::<Luma<u8>>()
+            .min_dimensions(MIN_QR_SIZE, MIN_QR_SIZE)
+            .build();
+        let mut png = Cursor::new(Vec::new());
+        image.write_to(&mut png, ImageFormat::Png)?;
+        Ok(png.into_inner())
+    }
+
+    /// SVG document of the deposit QR code, see [`payin_uri`](Self::payin_uri)
+    /// # Errors
+    /// * `qrcode::types::QrError` - If the data does not fit a QR code
+    pub fn payin_qr_svg(&self) -> Result<String> {
+        Ok(self.payin_qr_code()?
+            .render::<svg::Color>()
+            .min_dimensions(MIN_QR_SIZE, MIN_QR_SIZE)
+            .build())
+    }
+}
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::degraded;
+use crate::endpoint::parse_result;
+use crate::req_structs::{Asset, Pair};
+use crate::FogswapSdk;
+
+/// Current market rate of a pair, without fees
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RateInfo {
+    #[serde(flatten)]
+    pub pair: Pair,
+    /// Units of the destination token per unit of the source token
+    #[serde(deserialize_with = "crate::amount::number_or_string")]
+    pub rate: f64,
+    /// Unix time the rate was observed, when the API reports it
+    #[serde(default)]
+    pub updated_at: Option<i64>,
+}
+
+/// USD price of a token
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct UsdPrice {
+    #[serde(deserialize_with = "crate::amount::number_or_string")]
+    usd: f64,
+}
+
+impl FogswapSdk {
+
+    /// Get the current market rates of `pairs` in one request
+    ///
+    /// Rates are indicative; quote a swap before creating it.
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the rates cannot be retrieved
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, Pair};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let rates = sdk.get_rates(&[Pair::new("btc", "BTC", "xmr", "XMR"), Pair::new("sol", "SOL", "eth", "ETH")]).await?;
+    /// for rate in rates {
+    ///     println!("1 {} = {} {}", rate.pair.contract_address_from, rate.rate, rate.pair.contract_address_to);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_rates(&self, pairs: &[Pair]) -> Result<Vec<RateInfo>> {
+        let endpoint = "/market/rates";
+
+        let resp = self.send_request(reqwest::Method::POST, endpoint, Some(json!({ "pairs": pairs }))).await?;
+
+        let rates: Vec<RateInfo> = parse_result(endpoint, resp)?;
+        for rate in &rates {
+            self.remember(&degraded::rate_key(&rate.pair), rate);
+        }
+        Ok(rates)
+    }
+
+    /// Get the current USD price of one unit of `asset`
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the price cannot be retrieved
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, FogswapSdk};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let usd = sdk.get_usd_price(&Asset::native("sol")).await?;
+    /// println!("SOL: ${:.2}", usd);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_usd_price(&self, asset: &Asset) -> Result<f64> {
+        let endpoint = "/market/price";
+
+        let resp = self.send_request(
+            reqwest::Method::GET,
+            endpoint,
+            Some(json!({
+                "network": asset.network,
+                "contract_address": asset.contract_address,
+            }))
+        ).await?;
+
+        let price: UsdPrice = parse_result(endpoint, resp)?;
+        self.remember(&degraded::price_key(asset), &price.usd);
+        Ok(price.usd)
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::error::FogswapSdkError;
+use crate::networks::{NetworkRegistry, BUILTIN};
+use crate::resp_structs::{TransactionInfo, TxType};
+use crate::status::TransactionStatus;
+use crate::FogswapSdk;
+
+/// Summary of a swap in a final state, for user-facing receipts and support tickets
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SwapReceipt {
+    pub id: String,
+    pub status: TransactionStatus,
+    pub tx_type: TxType,
+
+    pub network_from: String,
+    pub contract_address_from: String,
+    pub amount_from: f64,
+    pub payin_hash: Option<String>,
+    pub payin_explorer_url: Option<String>,
+
+    pub network_to: String,
+    pub contract_address_to: String,
+    pub amount_to: f64,
+    pub payout_address: String,
+    pub payout_hash: Option<String>,
+    pub payout_explorer_url: Option<String>,
+
+    /// USD value at execution, as reported by the API
+    pub usd_value: Option<f64>,
+    pub network_fee: Option<f64>,
+    pub service_fee: Option<f64>,
+    pub promo_discount: Option<f64>,
+
+    pub created_at: i64,
+    pub finished_at: Option<i64>,
+    /// Seconds from creation to the final status
+    pub duration_secs: Option<i64>,
+}
+
+impl SwapReceipt {
+
+    /// Build a receipt, or `None` if the transaction has not reached a final status
+    pub fn from_transaction(info: &TransactionInfo) -> Option<Self> {
+        Self::from_transaction_with_registry(info, &BUILTIN)
+    }
+
+    /// Like `from_transaction`, with explorer links taken from `registry`
+    pub fn from_transaction_with_registry(info: &TransactionInfo, registry: &NetworkRegistry) -> Option<Self> {
+        Self::build(info, |network, hash| registry.explorer_tx_url(network, hash))
+    }
+
+    /// Like `from_transaction`, with explorer URL templates overriding the built-in ones
+    ///
+    /// Templates are keyed by network and contain `{hash}`, e.g. `https://etherscan.io/tx/{hash}`.
+    pub fn from_transaction_with_explorers(info: &TransactionInfo, explorers: &HashMap<String, String>) -> Option<Self> {
+        Self::build(info, |network, hash| match explorers.get(network) {
+            Some(template) => Some(template.replace("{hash}", hash)),
+            None => BUILTIN.explorer_tx_url(network, hash),
+        })
+    }
+
+    fn build(info: &TransactionInfo, explorer: impl Fn(&str, &str) -> Option<String>) -> Option<Self> {
+        if !info.status.is_terminal() {
+            return None;
+        }
+        let link = |network: &str, hash: &Option<String>| explorer(network, hash.as_deref()?);
+        Some(Self {
+            id: info.id.clone(),
+            status: info.status.clone(),
+            tx_type: info.tx_type.clone(),
+            network_from: info.network_from.clone(),
+            contract_address_from: info.contract_address_from.clone(),
+            amount_from: info.amount_from,
+            payin_hash: info.payin_hash.clone(),
+            payin_explorer_url: link(&info.network_from, &info.payin_hash),
+            network_to: info.network_to.clone(),
+            contract_address_to: info.contract_address_to.clone(),
+            amount_to: info.amount_to,
+            payout_address: info.payout_address.clone(),
+            payout_hash: info.payout_hash.clone(),
+            payout_explorer_url: link(&info.network_to, &info.payout_hash),
+            usd_value: info.convert_usd,
+            network_fee: info.network_fee,
+            service_fee: info.service_fee,
+            promo_discount: info.promo_discount,
+            created_at: info.created_at,
+            finished_at: info.finished_at,
+            duration_secs: info.finished_at.map(|finished| finished - info.created_at),
+        })
+    }
+}
+
+impl FogswapSdk {
+
+    /// Get the receipt of a swap in a final state
+    /// # Arguments
+    /// * `id` - The ID of the transaction
+    /// # Returns
+    /// * `SwapReceipt` - Hashes, explorer links, amounts, fees and timing
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the transaction cannot be retrieved
+    /// * `FogswapSdkError::NotFinal` - If the transaction is not final yet
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let receipt = sdk.get_receipt("your_transaction_id").await?;
+    /// println!("{}", serde_json::to_string_pretty(&receipt)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_receipt(&self, id: &str) -> Result<SwapReceipt> {
+        let info = self.get_transaction_info(id).await?;
+        SwapReceipt::from_transaction_with_registry(&info, &self.networks).ok_or_else(|| {
+            FogswapSdkError::NotFinal { id: info.id.clone(), status: info.status.clone() }.into()
+        })
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::transport::{execute_reqwest, Transport, TransportResponse};
+
+/// Whether a [`ReplayTransport`] talks to the API or serves a cassette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Send requests and write every interaction to the cassette, replacing its content
+    Record,
+    /// Answer from the cassette without any network access
+    Replay,
+}
+
+/// Request and response saved in a cassette
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub method: String,
+    /// URL path, e.g. `/v1/transaction/info`
+    pub endpoint: String,
+    /// Query parameters of GET requests or JSON body of POST requests, without ignored fields
+    pub payload: Value,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Response body, when it is JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<Value>,
+    /// Response body, when it is not JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl Interaction {
+
+    fn response(&self) -> Result<TransportResponse> {
+        let body = match (&self.json, &self.text) {
+            (Some(json), _) => json.to_string(),
+            (None, Some(text)) => text.clone(),
+            (None, None) => String::new(),
+        };
+        let status = StatusCode::from_u16(self.status)
+            .with_context(|| format!("invalid status {} recorded for {} {}", self.status, self.method, self.endpoint))?;
+        let mut resp = TransportResponse::new(status.as_u16(), body);
+        for (name, value) in &self.headers {
+            resp = resp.with_header(HeaderName::from_bytes(name.as_bytes())?, HeaderValue::from_str(value)?);
+        }
+        Ok(resp)
+    }
+}
+
+/// VCR-style [`Transport`]: record real API interactions once, then replay them offline
+///
+/// Interactions are matched on method, endpoint and payload. Identical requests, e.g.
+/// the polls of `wait_for_status`, get the recorded responses in order, the last one
+/// repeating. Request headers are never saved, and `idempotency_key` fields are left out
+/// of payloads so that replayed creates match, as is the query parameter of an API key
+/// sent with `ApiKeyPlacement::Query`; add other fields carrying secrets or random values
+/// with `ignore_field`. A request without a recorded interaction fails.
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use fogswap_sdk_rust::FogswapSdk;
+/// use fogswap_sdk_rust::replay::ReplayTransport;
+/// use fogswap_sdk_rust::testing::MockTransport;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cassette = std::env::temp_dir().join(format!("fogswap-{}.json", uuid::Uuid::new_v4()));
+///
+/// // Records through the reqwest client by default; a mock stands in for the live API here
+/// let recorder = ReplayTransport::record(&cassette).through(Arc::new(MockTransport::new()));
+/// let sdk = FogswapSdk::new().with_transport(Arc::new(recorder));
+/// let recorded = sdk.get_transaction_info("tx_1").await?;
+///
+/// let sdk = FogswapSdk::new().with_transport(Arc::new(ReplayTransport::replay(&cassette)?));
+/// assert_eq!(sdk.get_transaction_info("tx_1").await?.id, recorded.id);
+/// assert!(sdk.get_transaction_info("tx_2").await.is_err());
+/// # std::fs::remove_file(&cassette)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ReplayTransport {
+    mode: ReplayMode,
+    path: PathBuf,
+    ignored: Vec<String>,
+    /// Query parameters carrying the API key, as told by the SDK
+    secret_params: Mutex<Vec<String>>,
+    client: Client,
+    inner: Option<Arc<dyn Transport>>,
+    interactions: Mutex<Vec<Interaction>>,
+    /// Responses already served per interaction key, in replay mode
+    served: Mutex<HashMap<String, usize>>,
+}
+
+impl ReplayTransport {
+
+    /// Transport in `mode` on the cassette at `path`
+    /// # Errors
+    /// * If replaying and the cassette cannot be read, or holds a response that is not valid HTTP
+    pub fn new(mode: ReplayMode, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let interactions = match mode {
+            ReplayMode::Record => Vec::new(),
+            ReplayMode::Replay => {
+                let text = std::fs::read_to_string(&path).with_context(|| format!("reading cassette {}", path.display()))?;
+                let interactions: Vec<Interaction> = serde_json::from_str(&text).with_context(|| format!("parsing cassette {}", path.display()))?;
+                for interaction in &interactions {
+                    interaction.response().with_context(|| format!("loading cassette {}", path.display()))?;
+                }
+                interactions
+            }
+        };
+        Ok(Self {
+            mode,
+            path,
+            ignored: vec!["idempotency_key".to_string()],
+            secret_params: Mutex::default(),
+            client: Client::new(),
+            inner: None,
+            interactions: Mutex::new(interactions),
+            served: Mutex::default(),
+        })
+    }
+
+    /// Record to the cassette at `path`, created on the first request
+    pub fn record(path: impl AsRef<Path>) -> Self {
+        Self::new(ReplayMode::Record, path).expect("record mode does not read the cassette")
+    }
+
+    /// Replay the cassette at `path`
+    /// # Errors
+    /// * If the cassette cannot be read
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(ReplayMode::Replay, path)
+    }
+
+    /// Leave the top-level payload field `name` out of matching and of the cassette
+    pub fn ignore_field(mut self, name: &str) -> Self {
+        self.ignored.push(name.to_string());
+        self
+    }
+
+    /// Record with `client` instead of a default `reqwest` client
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Record the responses of `transport` instead of sending requests
+    pub fn through(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.inner = Some(transport);
+        self
+    }
+
+    pub fn mode(&self) -> ReplayMode {
+        self.mode
+    }
+
+    /// Interactions recorded or loaded so far
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.interactions.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Query parameters or JSON body of `req`, without ignored fields
+    fn payload(&self, req: &reqwest::Request) -> Value {
+        let mut payload = match req.body().and_then(|body| body.as_bytes()) {
+            Some(bytes) => serde_json::from_slice(bytes).unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned())),
+            None => {
+                let query: Map<String, Value> = req.url().query_pairs().map(|(k, v)| (k.into_owned(), Value::String(v.into_owned()))).collect();
+                if query.is_empty() { Value::Null } else { Value::Object(query) }
+            }
+        };
+        if let Value::Object(fields) = &mut payload {
+            let secret = self.secret_params.lock().unwrap_or_else(|e| e.into_inner());
+            fields.retain(|name, _| !self.ignored.contains(name) && !secret.contains(name));
+        }
+        payload
+    }
+
+    fn key(method: &str, endpoint: &str, payload: &Value) -> String {
+        format!("{} {} {}", method, endpoint, payload)
+    }
+
+    fn replay_response(&self, method: &str, endpoint: &str, payload: &Value) -> Result<TransportResponse> {
+        let key = Self::key(method, endpoint, payload);
+        let interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+        let matching: Vec<&Interaction> = interactions.iter()
+            .filter(|i| i.method == method && i.endpoint == endpoint && i.payload == *payload)
+            .collect();
+        let Some(last) = matching.last() else {
+            anyhow::bail!("no recorded interaction for {} {} with {} in {}", method, endpoint, payload, self.path.display());
+        };
+        let mut served = self.served.lock().unwrap_or_else(|e| e.into_inner());
+        let count = served.entry(key).or_default();
+        let interaction = matching.get(*count).unwrap_or(last);
+        *count += 1;
+        interaction.response()
+    }
+
+    async fn record_response(&self, method: String, endpoint: String, payload: Value, req: reqwest::Request) -> Result<TransportResponse> {
+        let resp = match &self.inner {
+            Some(transport) => transport.execute(req).await?,
+            None => execute_reqwest(&self.client, req).await?,
+        };
+        let (json, text) = match serde_json::from_slice::<Value>(&resp.body) {
+            Ok(json) => (Some(json), None),
+            Err(_) if resp.body.is_empty() => (None, None),
+            Err(_) => (None, Some(String::from_utf8_lossy(&resp.body).into_owned())),
+        };
+        let headers = resp.headers.iter()
+            .filter(|(name, _)| *name != reqwest::header::SET_COOKIE)
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let interaction = Interaction { method, endpoint, payload, status: resp.status.as_u16(), headers, json, text };
+
+        let mut interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+        interactions.push(interaction);
+        // Saved on every request, so a panicking test keeps what it recorded
+        let text = serde_json::to_string_pretty(&*interactions)?;
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, text).with_context(|| format!("writing cassette {}", self.path.display()))?;
+        Ok(resp)
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn execute(&self, req: reqwest::Request) -> BoxFuture<'_, Result<TransportResponse>> {
+        let method = req.method().to_string();
+        let endpoint = req.url().path().to_string();
+        let payload = self.payload(&req);
+        match self.mode {
+            ReplayMode::Replay => {
+                let resp = self.replay_response(&method, &endpoint, &payload);
+                Box::pin(async move { resp })
+            }
+            ReplayMode::Record => Box::pin(self.record_response(method, endpoint, payload, req)),
+        }
+    }
+
+    fn secret_query_param(&self, name: &str) {
+        let mut secret = self.secret_params.lock().unwrap_or_else(|e| e.into_inner());
+        if !secret.iter().any(|known| known == name) {
+            secret.push(name.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use crate::replay::ReplayTransport;
+    use crate::testing::MockTransport;
+    use crate::{ApiKeyPlacement, FogswapSdk};
+
+    fn cassette() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("fogswap-{}.json", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn cassette_with_invalid_status_fails_to_load() {
+        let path = cassette();
+        let recorded = json!([{ "method": "GET", "endpoint": "/v1/transaction/info", "payload": { "id": "tx_1" }, "status": 42 }]);
+        std::fs::write(&path, recorded.to_string()).unwrap();
+
+        let err = ReplayTransport::replay(&path).unwrap_err();
+        assert!(format!("{:#}", err).contains("invalid status 42"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn query_api_key_is_not_recorded() {
+        let path = cassette();
+        let recorder = ReplayTransport::record(&path).through(Arc::new(MockTransport::new()));
+        let sdk = FogswapSdk::new()
+            .with_api_key_in("SECRET_KEY_123", ApiKeyPlacement::Query("access".to_string()))
+            .with_transport(Arc::new(recorder));
+        sdk.get_transaction_info("tx_1").await.unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(!text.contains("SECRET_KEY_123"));
+        assert!(!text.contains("access"));
+
+        // Told after the transport, the key is left out of matching as well
+        let replayer = ReplayTransport::replay(&path).unwrap();
+        let sdk = FogswapSdk::new()
+            .with_transport(Arc::new(replayer))
+            .with_api_key_in("OTHER_KEY", ApiKeyPlacement::Query("access".to_string()));
+        assert_eq!(sdk.get_transaction_info("tx_1").await.unwrap().id, "tx_1");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,647 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::client_metadata::ClientMetadata;
+use crate::error::FogswapSdkError;
+use crate::networks::Network;
+use crate::precision::{AmountPrecision, AmountTolerance};
+use crate::resp_structs::{TokenInfo, TxType};
+use crate::status::TransactionStatus;
+
+/// Privacy of a swap, in place of the `tx_type` / `is_use_xmr` pair
+///
+/// `Private` hides the link between deposit and payout; `XmrRouted` also routes the
+/// swap through Monero, for an extra fee. Routing through XMR only exists for private
+/// swaps, so the pair `tx_type: standard, is_use_xmr: true` is refused before sending.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{Asset, PrivacyMode, QuoteRequest, TxType};
+///
+/// let req = QuoteRequest::new(&Asset::native("btc"), &Asset::native("eth"), 0.1).privacy(PrivacyMode::XmrRouted);
+/// assert_eq!(req.tx_type, Some(TxType::Private));
+/// assert_eq!(req.is_use_xmr, Some(true));
+/// assert_eq!(req.privacy_mode().unwrap(), PrivacyMode::XmrRouted);
+///
+/// let standard = QuoteRequest::new(&Asset::native("btc"), &Asset::native("eth"), 0.1).tx_type(TxType::Standard);
+/// let invalid = QuoteRequest { is_use_xmr: Some(true), ..standard };
+/// assert!(invalid.privacy_mode().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyMode {
+    #[default]
+    None,
+    Private,
+    XmrRouted,
+}
+
+impl PrivacyMode {
+
+    pub fn tx_type(self) -> TxType {
+        match self {
+            PrivacyMode::None => TxType::Standard,
+            PrivacyMode::Private | PrivacyMode::XmrRouted => TxType::Private,
+        }
+    }
+
+    /// `is_use_xmr` to send; unset for standard swaps
+    pub fn is_use_xmr(self) -> Option<bool> {
+        match self {
+            PrivacyMode::None => None,
+            PrivacyMode::Private => Some(false),
+            PrivacyMode::XmrRouted => Some(true),
+        }
+    }
+
+    /// Mode of a `tx_type` / `is_use_xmr` pair
+    /// # Errors
+    /// * `FogswapSdkError::InvalidRequest` - If XMR routing is asked for a standard swap, or the type is unknown
+    pub fn from_parts(tx_type: Option<&TxType>, is_use_xmr: Option<bool>) -> anyhow::Result<Self> {
+        match (tx_type, is_use_xmr) {
+            (None | Some(TxType::Standard), None | Some(false)) => Ok(PrivacyMode::None),
+            (Some(TxType::Private), None | Some(false)) => Ok(PrivacyMode::Private),
+            (Some(TxType::Private), Some(true)) => Ok(PrivacyMode::XmrRouted),
+            (None | Some(TxType::Standard), Some(true)) => {
+                Err(FogswapSdkError::InvalidRequest("routing through XMR requires a private swap".to_string()).into())
+            }
+            (Some(TxType::Unknown), _) => Err(FogswapSdkError::InvalidRequest("unknown transaction type".to_string()).into()),
+        }
+    }
+
+    /// Mode applied to a quote or transaction, as reported by the API
+    pub(crate) fn applied(tx_type: &TxType, is_use_xmr: Option<bool>) -> Self {
+        match (tx_type, is_use_xmr) {
+            (TxType::Private, Some(true)) => PrivacyMode::XmrRouted,
+            (TxType::Private, _) => PrivacyMode::Private,
+            _ => PrivacyMode::None,
+        }
+    }
+}
+
+/// Parameters of a single `get_quote` call
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{Asset, QuoteRequest, TxType};
+///
+/// let req = QuoteRequest::new(&Asset::native("sol"), &Asset::native("eth"), 1.0).tx_type(TxType::Private);
+/// let json = serde_json::to_value(&req).unwrap();
+/// assert_eq!(json["tx_type"], "private");
+/// let back: QuoteRequest = serde_json::from_value(json).unwrap();
+/// assert_eq!(back.tx_type, Some(TxType::Private));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct QuoteRequest {
+    /// Amount to send; ignored when `amount_to` is set
+    pub amount_from: f64,
+    /// Amount to receive, for a reverse quote that computes the required `amount_from`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_to: Option<f64>,
+    pub network_from: String,
+    pub contract_address_from: String,
+    pub network_to: String,
+    pub contract_address_to: String,
+    pub tx_type: Option<TxType>,
+    pub is_use_xmr: Option<bool>,
+    /// Promotional code for reduced fees
+    pub promo_code: Option<String>,
+    /// Partner credited with the affiliate fee; defaults to the client `with_partner_id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_id: Option<String>,
+    /// Affiliate fee in basis points on top of the service fee; defaults to the client
+    /// `with_partner_fee_bps`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_bps: Option<u32>,
+    /// Fields sent as-is, for API parameters this SDK version does not know yet;
+    /// they override typed fields of the same name
+    #[serde(flatten, default)]
+    pub extra_params: HashMap<String, Value>,
+}
+
+impl QuoteRequest {
+
+    /// Quote swapping `amount_from` of `from` into `to`; other fields start unset
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, PrivacyMode, QuoteRequest};
+    ///
+    /// let req = QuoteRequest::new(&Asset::new("sol", "SOL"), &Asset::new("eth", "ETH"), 1.0)
+    ///     .privacy(PrivacyMode::XmrRouted);
+    /// ```
+    pub fn new(from: &Asset, to: &Asset, amount_from: f64) -> Self {
+        Self {
+            amount_from,
+            network_from: from.network.clone(),
+            contract_address_from: from.contract_address.clone(),
+            network_to: to.network.clone(),
+            contract_address_to: to.contract_address.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Quote receiving exactly `amount_to` of `to`; the response tells the `amount_from` to deposit
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, QuoteRequest};
+    ///
+    /// let req = QuoteRequest::receiving(&Asset::new("sol", "SOL"), &Asset::new("eth", "USDT"), 100.0);
+    /// assert!(req.is_reverse());
+    /// ```
+    pub fn receiving(from: &Asset, to: &Asset, amount_to: f64) -> Self {
+        Self { amount_to: Some(amount_to), ..Self::new(from, to, 0.0) }
+    }
+
+    /// Whether this quotes a fixed amount to receive rather than a fixed amount to send
+    pub fn is_reverse(&self) -> bool {
+        self.amount_to.is_some()
+    }
+
+    /// Request as sent by the async and blocking clients: checked, with the client's
+    /// partner defaults filled in and amounts cut to `precision`
+    pub(crate) fn prepared(&self, precision: &AmountPrecision, partner_id: Option<&String>, fee_bps: Option<u32>) -> anyhow::Result<Self> {
+        self.privacy_mode()?;
+        let mut req = self.clone();
+        req.partner_id = req.partner_id.or_else(|| partner_id.cloned());
+        req.fee_bps = req.fee_bps.or(fee_bps);
+        req.amount_from = precision.round_down(&req.network_from, &req.contract_address_from, req.amount_from);
+        req.amount_to = req.amount_to.map(|amount_to| precision.round_down(&req.network_to, &req.contract_address_to, amount_to));
+        Ok(req)
+    }
+
+    /// Query parameters, sending either `amount_from` or `amount_to`
+    pub(crate) fn params(&self) -> serde_json::Result<Value> {
+        let mut params = serde_json::to_value(self)?;
+        if self.is_reverse()
+            && let Value::Object(params) = &mut params
+            && !self.extra_params.contains_key("amount_from") {
+            params.remove("amount_from");
+        }
+        Ok(params)
+    }
+
+    pub fn tx_type(mut self, tx_type: TxType) -> Self {
+        self.tx_type = Some(tx_type);
+        self
+    }
+
+    /// Set `tx_type` and `is_use_xmr` together
+    pub fn privacy(mut self, mode: PrivacyMode) -> Self {
+        self.tx_type = Some(mode.tx_type());
+        self.is_use_xmr = mode.is_use_xmr();
+        self
+    }
+
+    #[deprecated(note = "use `privacy(PrivacyMode::XmrRouted)`")]
+    pub fn use_xmr(mut self, is_use_xmr: bool) -> Self {
+        self.is_use_xmr = Some(is_use_xmr);
+        self
+    }
+
+    /// Privacy asked for by `tx_type` and `is_use_xmr`
+    /// # Errors
+    /// * `FogswapSdkError::InvalidRequest` - If the pair is not a valid combination
+    pub fn privacy_mode(&self) -> anyhow::Result<PrivacyMode> {
+        PrivacyMode::from_parts(self.tx_type.as_ref(), self.is_use_xmr)
+    }
+
+    pub fn promo_code(mut self, promo_code: &str) -> Self {
+        self.promo_code = Some(promo_code.to_string());
+        self
+    }
+
+    /// Credit `fee_bps` basis points to `partner_id` for this quote
+    pub fn partner_fee(mut self, partner_id: &str, fee_bps: u32) -> Self {
+        self.partner_id = Some(partner_id.to_string());
+        self.fee_bps = Some(fee_bps);
+        self
+    }
+
+    pub fn extra_param(mut self, name: &str, value: Value) -> Self {
+        self.extra_params.insert(name.to_string(), value);
+        self
+    }
+}
+
+/// Parameters of a single `create_transaction` call
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{Asset, CreateTransactionRequest, TxType};
+///
+/// for tx_type in [TxType::Standard, TxType::Private] {
+///     let req = CreateTransactionRequest::builder()
+///         .assets(&Asset::native("sol"), &Asset::native("eth"), 1.0)
+///         .payout_address("0x2a65Aca4D5fC5B5C859090a6c34d164135398226")
+///         .tx_type(tx_type.clone())
+///         .build();
+///     let json = serde_json::to_value(&req).unwrap();
+///     assert_eq!(json["tx_type"], tx_type.to_string());
+///     let back: CreateTransactionRequest = serde_json::from_value(json).unwrap();
+///     assert_eq!(back.tx_type, Some(tx_type));
+/// }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CreateTransactionRequest {
+    pub network_from: String,
+    pub contract_address_from: String,
+    pub network_to: String,
+    pub contract_address_to: String,
+    pub amount_from: f64,
+    pub payout_address: String,
+    pub payout_extra_id: Option<String>,
+    /// Address the deposit is returned to if the swap fails, on `network_from`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund_extra_id: Option<String>,
+    pub tx_type: Option<TxType>,
+    pub is_use_xmr: Option<bool>,
+    /// Client-generated reference used to recover the swap after a crash
+    pub external_id: Option<String>,
+    /// Key making creation safe to retry: the API creates at most one transaction per key.
+    /// Generated when missing; set it yourself to retry a create whose outcome is unknown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// Promotional code for reduced fees
+    pub promo_code: Option<String>,
+    /// Partner credited with the affiliate fee; defaults to the client `with_partner_id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_id: Option<String>,
+    /// Affiliate fee in basis points on top of the service fee; defaults to the client
+    /// `with_partner_fee_bps`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_bps: Option<u32>,
+    /// Least `amount_to` accepted; creation is refused when a fresh quote is below it
+    pub min_amount_to: Option<f64>,
+    /// Kept locally under the created transaction id, never sent to the API
+    pub client_metadata: Option<ClientMetadata>,
+    /// Fields sent as-is, for API parameters this SDK version does not know yet;
+    /// they override typed fields of the same name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra_params: HashMap<String, Value>,
+}
+
+impl CreateTransactionRequest {
+
+    /// Start a request whose pair, amount and payout address are checked at compile time
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{CreateTransactionRequest, Pair};
+    ///
+    /// let req = CreateTransactionRequest::builder()
+    ///     .swap(&Pair::new("sol", "SOL", "eth", "ETH"), 0.5)
+    ///     .payout_address("YOUR_RECEIVE_ADDRESS_HERE")
+    ///     .external_id("order-1042")
+    ///     .build();
+    /// ```
+    ///
+    /// Forgetting the payout address does not compile:
+    /// ```compile_fail
+    /// use fogswap_sdk_rust::{CreateTransactionRequest, Pair};
+    ///
+    /// let req = CreateTransactionRequest::builder()
+    ///     .swap(&Pair::new("sol", "SOL", "eth", "ETH"), 0.5)
+    ///     .build();
+    /// ```
+    pub fn builder() -> CreateTransactionBuilder<Missing, Missing> {
+        CreateTransactionBuilder { req: Self::default(), _state: PhantomData }
+    }
+
+    /// Privacy asked for by `tx_type` and `is_use_xmr`
+    /// # Errors
+    /// * `FogswapSdkError::InvalidRequest` - If the pair is not a valid combination
+    pub fn privacy_mode(&self) -> anyhow::Result<PrivacyMode> {
+        PrivacyMode::from_parts(self.tx_type.as_ref(), self.is_use_xmr)
+    }
+
+    /// Quote of this swap, compared with `min_amount_to` before creating it
+    pub(crate) fn min_received_quote(&self) -> QuoteRequest {
+        QuoteRequest {
+            amount_from: self.amount_from,
+            network_from: self.network_from.clone(),
+            contract_address_from: self.contract_address_from.clone(),
+            network_to: self.network_to.clone(),
+            contract_address_to: self.contract_address_to.clone(),
+            tx_type: self.tx_type.clone(),
+            is_use_xmr: self.is_use_xmr,
+            promo_code: self.promo_code.clone(),
+            partner_id: self.partner_id.clone(),
+            fee_bps: self.fee_bps,
+            ..Default::default()
+        }
+    }
+
+    /// Refuse a swap quoted below `min_amount_to` by more than `tolerance`
+    pub(crate) fn check_min_received(&self, quoted: f64, tolerance: &AmountTolerance) -> anyhow::Result<()> {
+        match self.min_amount_to {
+            Some(min_amount_to) if !tolerance.at_least(quoted, min_amount_to) => {
+                Err(FogswapSdkError::BelowMinimumReceived { min_amount_to, quoted }.into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Body of `/transaction/create`, the same for the async and blocking clients
+    ///
+    /// `amount_from` is cut to `precision`; `partner_id` and `fee_bps` fall back to the
+    /// client defaults. `extra_params` override typed fields of the same name.
+    pub(crate) fn payload(&self, precision: &AmountPrecision, partner_id: Option<&String>, fee_bps: Option<u32>) -> Value {
+        let mut payload = json!({
+            "network_from": self.network_from,
+            "contract_address_from": self.contract_address_from,
+            "amount_from": precision.round_down(&self.network_from, &self.contract_address_from, self.amount_from),
+            "network_to": self.network_to,
+            "contract_address_to": self.contract_address_to,
+            "payout_address": self.payout_address,
+            "payout_extra_id": self.payout_extra_id,
+            "refund_address": self.refund_address,
+            "refund_extra_id": self.refund_extra_id,
+            "tx_type": self.tx_type,
+            "is_use_xmr": self.is_use_xmr,
+            "external_id": self.external_id,
+            "idempotency_key": self.idempotency_key,
+            "promo_code": self.promo_code,
+            "partner_id": self.partner_id.as_ref().or(partner_id),
+            "fee_bps": self.fee_bps.or(fee_bps),
+            "min_amount_to": self.min_amount_to,
+        });
+        if let Some(fields) = payload.as_object_mut() {
+            fields.extend(self.extra_params.clone());
+        }
+        payload
+    }
+}
+
+/// A required `CreateTransactionBuilder` field that is not set yet
+#[derive(Debug)]
+pub struct Missing;
+
+/// A required `CreateTransactionBuilder` field that is set
+#[derive(Debug)]
+pub struct Set;
+
+/// Builder of a `CreateTransactionRequest`, tracking the pair (`P`) and payout address (`A`)
+///
+/// `build()` only exists once both are `Set`.
+#[derive(Debug)]
+pub struct CreateTransactionBuilder<P, A> {
+    req: CreateTransactionRequest,
+    _state: PhantomData<(P, A)>,
+}
+
+impl<A> CreateTransactionBuilder<Missing, A> {
+
+    /// Swap `amount_from` of the source token of `pair`
+    pub fn swap(mut self, pair: &Pair, amount_from: f64) -> CreateTransactionBuilder<Set, A> {
+        self.req.network_from = pair.network_from.clone();
+        self.req.contract_address_from = pair.contract_address_from.clone();
+        self.req.network_to = pair.network_to.clone();
+        self.req.contract_address_to = pair.contract_address_to.clone();
+        self.req.amount_from = amount_from;
+        CreateTransactionBuilder { req: self.req, _state: PhantomData }
+    }
+
+    /// Swap `amount_from` of `from` into `to`
+    pub fn assets(self, from: &Asset, to: &Asset, amount_from: f64) -> CreateTransactionBuilder<Set, A> {
+        self.swap(&Pair::from_assets(from, to), amount_from)
+    }
+}
+
+impl<P> CreateTransactionBuilder<P, Missing> {
+
+    pub fn payout_address(mut self, address: &str) -> CreateTransactionBuilder<P, Set> {
+        self.req.payout_address = address.to_string();
+        CreateTransactionBuilder { req: self.req, _state: PhantomData }
+    }
+}
+
+impl<P, A> CreateTransactionBuilder<P, A> {
+
+    pub fn payout_extra_id(mut self, extra_id: &str) -> Self {
+        self.req.payout_extra_id = Some(extra_id.to_string());
+        self
+    }
+
+    /// Return the deposit to `address` on the source network if the swap fails
+    pub fn refund_address(mut self, address: &str) -> Self {
+        self.req.refund_address = Some(address.to_string());
+        self
+    }
+
+    pub fn refund_extra_id(mut self, extra_id: &str) -> Self {
+        self.req.refund_extra_id = Some(extra_id.to_string());
+        self
+    }
+
+    pub fn tx_type(mut self, tx_type: TxType) -> Self {
+        self.req.tx_type = Some(tx_type);
+        self
+    }
+
+    /// Set `tx_type` and `is_use_xmr` together
+    pub fn privacy(mut self, mode: PrivacyMode) -> Self {
+        self.req.tx_type = Some(mode.tx_type());
+        self.req.is_use_xmr = mode.is_use_xmr();
+        self
+    }
+
+    #[deprecated(note = "use `privacy(PrivacyMode::XmrRouted)`")]
+    pub fn use_xmr(mut self, is_use_xmr: bool) -> Self {
+        self.req.is_use_xmr = Some(is_use_xmr);
+        self
+    }
+
+    pub fn external_id(mut self, external_id: &str) -> Self {
+        self.req.external_id = Some(external_id.to_string());
+        self
+    }
+
+    pub fn idempotency_key(mut self, key: &str) -> Self {
+        self.req.idempotency_key = Some(key.to_string());
+        self
+    }
+
+    pub fn promo_code(mut self, promo_code: &str) -> Self {
+        self.req.promo_code = Some(promo_code.to_string());
+        self
+    }
+
+    /// Credit `fee_bps` basis points to `partner_id` for this transaction
+    pub fn partner_fee(mut self, partner_id: &str, fee_bps: u32) -> Self {
+        self.req.partner_id = Some(partner_id.to_string());
+        self.req.fee_bps = Some(fee_bps);
+        self
+    }
+
+    pub fn min_amount_to(mut self, min_amount_to: f64) -> Self {
+        self.req.min_amount_to = Some(min_amount_to);
+        self
+    }
+
+    pub fn client_metadata(mut self, metadata: ClientMetadata) -> Self {
+        self.req.client_metadata = Some(metadata);
+        self
+    }
+
+    pub fn extra_param(mut self, name: &str, value: Value) -> Self {
+        self.req.extra_params.insert(name.to_string(), value);
+        self
+    }
+}
+
+impl CreateTransactionBuilder<Set, Set> {
+
+    pub fn build(self) -> CreateTransactionRequest {
+        self.req
+    }
+}
+
+/// Server-side filters for the token list
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::TokenListFilter;
+///
+/// let filter = TokenListFilter {
+///     network: Some("sol".to_string()),
+///     search: Some("USDC".to_string()),
+///     only_native: Some(false),
+///     page: Some(2),
+///     limit: Some(20),
+/// };
+/// let json = serde_json::to_value(&filter).unwrap();
+/// assert_eq!(json["network"], "sol");
+/// assert_eq!(json["only_native"], false);
+/// let back: TokenListFilter = serde_json::from_value(json).unwrap();
+/// assert_eq!(back.search.as_deref(), Some("USDC"));
+/// assert_eq!((back.page, back.limit), (Some(2), Some(20)));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TokenListFilter {
+    /// Only return tokens of this network
+    pub network: Option<String>,
+    /// Match on token symbol or contract address
+    pub search: Option<String>,
+    /// Only return native tokens
+    pub only_native: Option<bool>,
+    /// 1-based page number
+    pub page: Option<u32>,
+    /// Number of networks per page
+    pub limit: Option<u32>,
+}
+
+/// Filters and page of `list_transactions`
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{ListTxParams, TransactionStatus};
+///
+/// let params = ListTxParams {
+///     status: Some(TransactionStatus::Finished),
+///     created_after: Some(1_700_000_000),
+///     limit: Some(50),
+///     page: Some(1),
+/// };
+/// let json = serde_json::to_value(&params).unwrap();
+/// assert_eq!(json["status"], "finished");
+/// let back: ListTxParams = serde_json::from_value(json).unwrap();
+/// assert_eq!(back.status, Some(TransactionStatus::Finished));
+/// assert_eq!(back.created_after, Some(1_700_000_000));
+/// assert_eq!((back.limit, back.page), (Some(50), Some(1)));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListTxParams {
+    /// Only return transactions in this status
+    pub status: Option<TransactionStatus>,
+    /// Only return transactions created at or after this Unix time
+    pub created_after: Option<i64>,
+    /// Number of transactions per page
+    pub limit: Option<u32>,
+    /// 1-based page number
+    pub page: Option<u32>,
+}
+
+/// A token on a network
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct Asset {
+    pub network: String,
+    pub contract_address: String,
+}
+
+impl Asset {
+    pub fn new(network: impl AsRef<str>, contract_address: &str) -> Self {
+        Self { network: network.as_ref().to_string(), contract_address: contract_address.to_string() }
+    }
+
+    /// Native coin of `network`, identified by its ticker like in the token list
+    ///
+    /// Networks whose coin is not known here use the upper-cased network name; take the
+    /// entry with `is_native` from the token list when in doubt.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, Network};
+    ///
+    /// assert_eq!(Asset::native("sol"), Asset::new("sol", "SOL"));
+    /// assert_eq!(Asset::native(Network::Bsc), Asset::new("bsc", "BNB"));
+    /// ```
+    pub fn native(network: impl AsRef<str>) -> Self {
+        let network = network.as_ref().to_ascii_lowercase();
+        let ticker = Network::from(network.as_str())
+            .native_symbol()
+            .map_or_else(|| network.to_ascii_uppercase(), str::to_string);
+        Self { network, contract_address: ticker }
+    }
+
+    /// The network of this asset, parsed
+    pub fn network_kind(&self) -> Network {
+        Network::from(self.network.as_str())
+    }
+}
+
+impl From<&TokenInfo> for Asset {
+    fn from(token: &TokenInfo) -> Self {
+        Self::new(&token.network, &token.contract_address)
+    }
+}
+
+/// A swap direction between two assets
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::Pair;
+///
+/// let pair = Pair::new("sol", "SOL", "eth", "ETH");
+/// let json = serde_json::to_value(&pair).unwrap();
+/// assert_eq!(json["contract_address_to"], "ETH");
+/// assert_eq!(serde_json::from_value::<Pair>(json).unwrap(), pair);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct Pair {
+    pub network_from: String,
+    pub contract_address_from: String,
+    pub network_to: String,
+    pub contract_address_to: String,
+}
+
+impl Pair {
+    pub fn new(network_from: &str, contract_address_from: &str, network_to: &str, contract_address_to: &str) -> Self {
+        Self {
+            network_from: network_from.to_string(),
+            contract_address_from: contract_address_from.to_string(),
+            network_to: network_to.to_string(),
+            contract_address_to: contract_address_to.to_string(),
+        }
+    }
+
+    pub fn from_assets(from: &Asset, to: &Asset) -> Self {
+        Self::new(&from.network, &from.contract_address, &to.network, &to.contract_address)
+    }
+
+    /// Asset sent
+    pub fn from_asset(&self) -> Asset {
+        Asset::new(&self.network_from, &self.contract_address_from)
+    }
+
+    /// Asset received
+    pub fn to_asset(&self) -> Asset {
+        Asset::new(&self.network_to, &self.contract_address_to)
+    }
+}
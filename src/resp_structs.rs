@@ -1,47 +1,266 @@
+use std::fmt;
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use crate::req_structs::PrivacyMode;
+use crate::status::TransactionStatus;
+
+// Response types ignore unknown fields and default the ones the API may omit, so a
+// server-side schema change does not break decoding for every client.
+
+/// Envelope of every Fogswap API response: either `result` or `error` is set
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiResponse<T> {
+    pub result: Option<T>,
+    pub error: Option<ApiError>,
+}
+
+/// Error reported in a response envelope
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiError {
+    /// Machine-readable code, a string or a number depending on the endpoint
+    #[serde(default)]
+    pub code: Option<serde_json::Value>,
+    #[serde(default)]
+    pub message: String,
+}
+
+/// Acknowledgement of a call whose `result` carries no data, e.g. a cancellation
+///
+/// Accepts any `result`, including null or none at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct Ack;
+
+impl<'de> Deserialize<'de> for Ack {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde::de::IgnoredAny::deserialize(deserializer)?;
+        Ok(Ack)
+    }
+}
+
+/// Position in a paginated listing, to persist between runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageCursor {
+    /// 1-based page to request next
+    pub page: u32,
+    /// Entries of that page already handed out
+    pub skip: usize,
+}
+
+impl Default for PageCursor {
+    fn default() -> Self {
+        Self { page: 1, skip: 0 }
+    }
+}
+
+/// One page of a list endpoint
+///
+/// Decodes both a bare array and an object with `items` (or `data`) and the
+/// optional `total` and `has_more` paging fields.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{PageCursor, Paginated};
+///
+/// let page: Paginated<u32> = serde_json::from_str(r#"{"items": [1, 2], "has_more": true}"#).unwrap();
+/// assert_eq!(page.next_cursor(PageCursor::default(), 50), Some(PageCursor { page: 2, skip: 0 }));
+///
+/// let page: Paginated<u32> = serde_json::from_str("[1, 2]").unwrap();
+/// assert_eq!(page.next_cursor(PageCursor::default(), 50), None);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "PaginatedWire<T>")]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    /// Entries across every page, when reported
+    pub total: Option<u64>,
+    /// Whether pages follow this one, when reported
+    pub has_more: Option<bool>,
+}
+
+impl<T> Paginated<T> {
+
+    /// Cursor of the page after the one requested at `cursor`, `None` after the last page
+    ///
+    /// Without `has_more`, a page shorter than `page_size` is the last one.
+    pub fn next_cursor(&self, cursor: PageCursor, page_size: u32) -> Option<PageCursor> {
+        let more = self.has_more.unwrap_or(self.items.len() >= page_size as usize);
+        more.then_some(PageCursor { page: cursor.page + 1, skip: 0 })
+    }
+}
+
+impl<T> From<Vec<T>> for Paginated<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self { items, total: None, has_more: None }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PaginatedWire<T> {
+    List(Vec<T>),
+    Envelope {
+        #[serde(alias = "data")]
+        items: Vec<T>,
+        #[serde(default)]
+        total: Option<u64>,
+        #[serde(default)]
+        has_more: Option<bool>,
+    },
+}
+
+impl<T> From<PaginatedWire<T>> for Paginated<T> {
+    fn from(wire: PaginatedWire<T>) -> Self {
+        match wire {
+            PaginatedWire::List(items) => items.into(),
+            PaginatedWire::Envelope { items, total, has_more } => Self { items, total, has_more },
+        }
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize,Clone)]
 pub struct TokenList{
     pub network: String,
+    #[serde(default)]
     pub network_image: String,
+    #[serde(default)]
     pub tokens: Vec<TokenInfo>,
 }
 
+impl TokenList {
+
+    /// Token of this network with the given symbol or contract address, case-insensitively
+    pub fn find(&self, symbol_or_contract: &str) -> Option<&TokenInfo> {
+        self.tokens.iter().find(|t| t.is(symbol_or_contract))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize,Clone)]
 pub struct TokenInfo {
     pub token: String,
+    #[serde(default)]
     pub network: String,
+    #[serde(default)]
     pub contract_address: String,
+    #[serde(default)]
     pub image: String,
+    #[serde(default)]
     pub is_native: bool,
+    /// Decimal places of the token, when the API reports them
+    #[serde(default)]
+    pub decimals: Option<u32>,
+}
+
+impl TokenInfo {
+
+    /// Whether the symbol or contract address equals `symbol_or_contract`, case-insensitively
+    pub fn is(&self, symbol_or_contract: &str) -> bool {
+        self.token.eq_ignore_ascii_case(symbol_or_contract) || self.contract_address.eq_ignore_ascii_case(symbol_or_contract)
+    }
+
+    /// Whether the symbol contains `query` or the contract address equals it, case-insensitively
+    pub fn matches(&self, query: &str) -> bool {
+        self.token.to_ascii_lowercase().contains(&query.to_ascii_lowercase()) || self.contract_address.eq_ignore_ascii_case(query)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize,Clone)]
 pub struct QuoteResponse {
     pub network_from: String,
     pub contract_address_from: String,
+    #[serde(deserialize_with = "crate::amount::number_or_string")]
     pub amount_from: f64,
     pub network_to: String,
     pub contract_address_to: String,
+    #[serde(deserialize_with = "crate::amount::number_or_string")]
     pub amount_to: f64,
+    #[serde(default)]
     pub convert_usd: ConvertUsd,
+    #[serde(default)]
     pub tx_type: TxType,
+    /// Discount applied by a promo code, in percent
+    pub promo_discount: Option<f64>,
+    /// Service fee already deducted from `amount_to`, in destination units
+    #[serde(default, deserialize_with = "crate::amount::opt_number_or_string")]
+    pub service_fee: Option<f64>,
+    /// Whether the quoted route goes through XMR, see `privacy_mode`
+    #[serde(default)]
+    pub is_use_xmr: Option<bool>,
+    /// Extra fee of the privacy route, already deducted from `amount_to`, in destination units
+    #[serde(default, deserialize_with = "crate::amount::opt_number_or_string")]
+    pub privacy_fee: Option<f64>,
+    /// Unix time after which a fixed-rate quote is no longer honoured
+    #[serde(default, alias = "expires_at")]
+    pub valid_until: Option<i64>,
+    /// Affiliate fee applied, when the quote was requested with one
+    #[serde(default)]
+    pub partner_fee: Option<PartnerFee>,
+    /// Response fields without a typed counterpart in this SDK version, as a JSON object
+    #[serde(flatten)]
+    pub raw: Value,
+}
+
+impl QuoteResponse {
+
+    /// Privacy route the quote was made for
+    pub fn privacy_mode(&self) -> PrivacyMode {
+        PrivacyMode::applied(&self.tx_type, self.is_use_xmr)
+    }
+
+    /// `valid_until` as a point in time
+    pub fn expires_at(&self) -> Option<crate::rt::SystemTime> {
+        let secs = u64::try_from(self.valid_until?).ok()?;
+        crate::rt::UNIX_EPOCH.checked_add(std::time::Duration::from_secs(secs))
+    }
+
+    /// Whether `valid_until` has passed
+    pub fn is_expired(&self) -> bool {
+        let now = crate::rt::SystemTime::now().duration_since(crate::rt::UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+        self.is_expired_at(now)
+    }
+
+    /// Whether `valid_until` has passed at `now`, in Unix seconds
+    pub fn is_expired_at(&self, now: i64) -> bool {
+        self.valid_until.is_some_and(|until| now >= until)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize,Clone)]
+pub struct PairLimits {
+    #[serde(deserialize_with = "crate::amount::number_or_string")]
+    pub min_amount: f64,
+    #[serde(default, deserialize_with = "crate::amount::opt_number_or_string")]
+    pub max_amount: Option<f64>,
+}
+
+/// Affiliate fee credited to a partner, already deducted from `amount_to`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PartnerFee {
+    pub partner_id: String,
+    pub fee_bps: u32,
+    /// Fee in destination units
+    #[serde(deserialize_with = "crate::amount::number_or_string")]
+    pub amount: f64,
+    #[serde(default, deserialize_with = "crate::amount::opt_number_or_string")]
+    pub amount_usd: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ConvertUsd {
+    #[serde(default, deserialize_with = "crate::amount::opt_number_or_string")]
     pub from: Option<f64>,
+    #[serde(default, deserialize_with = "crate::amount::opt_number_or_string")]
     pub to: Option<f64>,
 }
 
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize,Clone)]
 pub struct TransactionInfo {
     pub id: String,
+    #[serde(default)]
     pub created_at: i64,
+    #[serde(default)]
     pub tx_type: TxType,
 
     pub network_from: String,
@@ -50,7 +269,9 @@ pub struct TransactionInfo {
     pub contract_address_to: String,
     pub network_to: String,
 
+    #[serde(deserialize_with = "crate::amount::number_or_string")]
     pub amount_from: f64,
+    #[serde(deserialize_with = "crate::amount::number_or_string")]
     pub amount_to: f64,
 
     pub payin_address: String,
@@ -61,23 +282,265 @@ pub struct TransactionInfo {
     pub payout_extra_id: Option<String>,
     pub payout_hash: Option<String>,
 
+    /// Address the deposit is returned to if the swap fails
+    #[serde(default)]
+    pub refund_address: Option<String>,
+    #[serde(default)]
+    pub refund_extra_id: Option<String>,
+    /// Hash of the refund, once the deposit was returned
+    #[serde(default)]
+    pub refund_hash: Option<String>,
+
+    #[serde(default, deserialize_with = "crate::amount::opt_number_or_string")]
     pub convert_usd: Option<f64>,
     
+    pub status: TransactionStatus,
+
+    /// Discount applied by a promo code, in percent
+    pub promo_discount: Option<f64>,
+
+    /// Timestamp at which the transaction reached its final status
+    pub finished_at: Option<i64>,
+    /// Network fee charged on the payout, in destination units
+    #[serde(default, deserialize_with = "crate::amount::opt_number_or_string")]
+    pub network_fee: Option<f64>,
+    /// Service fee charged on the payout, in destination units
+    #[serde(default, deserialize_with = "crate::amount::opt_number_or_string")]
+    pub service_fee: Option<f64>,
+
+    /// Whether the swap is routed through XMR, see `privacy_mode`
+    #[serde(default)]
+    pub is_use_xmr: Option<bool>,
+    /// Extra fee of the privacy route, already deducted from `amount_to`, in destination units
+    #[serde(default, deserialize_with = "crate::amount::opt_number_or_string")]
+    pub privacy_fee: Option<f64>,
+
+    /// Deposit confirmations observed so far, while confirming
+    #[serde(default)]
+    pub confirmations: Option<u32>,
+    /// Deposit confirmations the network requires, when the API reports them
+    #[serde(default, alias = "required_confirmations")]
+    pub confirmations_required: Option<u32>,
+    /// Estimated time until the payout, in seconds, when the API reports it
+    #[serde(default, alias = "eta")]
+    pub eta_seconds: Option<u64>,
+
+    /// Set while the transaction is on hold until `set_emergency_action` is called
+    #[serde(default)]
+    pub emergency: Option<Emergency>,
+
+    /// Key the transaction was created with, see `CreateTransactionRequest::idempotency_key`
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+
+    /// Affiliate fee applied, when the transaction was created with one
+    #[serde(default)]
+    pub partner_fee: Option<PartnerFee>,
+
+    /// Response fields without a typed counterpart in this SDK version, as a JSON object
+    #[serde(flatten)]
+    pub raw: Value,
+}
+
+/// Availability of the API, as reported by its status endpoint
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ServiceStatus {
+    /// e.g. `operational`, `degraded` or `maintenance`
+    #[serde(default)]
     pub status: String,
+    #[serde(default)]
+    pub maintenance: bool,
+    /// Notice to show to users, if any
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Unix time the API is expected back, during maintenance
+    #[serde(default)]
+    pub estimated_return: Option<i64>,
 }
 
+impl ServiceStatus {
 
-#[derive(Debug, Serialize, Deserialize,Clone)]
+    /// Whether swaps can be made
+    pub fn is_available(&self) -> bool {
+        !self.maintenance && self.status != "maintenance"
+    }
+}
+
+/// Why a transaction is on hold and what was chosen so far
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Emergency {
+    /// e.g. `amount_mismatch` or `kyt`
+    #[serde(default)]
+    pub reason: String,
+    /// `refund` or `continue`, once chosen
+    #[serde(default)]
+    pub choice: Option<String>,
+}
+
+impl TransactionInfo {
+
+    /// Privacy route applied to the swap
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::testing::MockTransport;
+    /// use fogswap_sdk_rust::{PrivacyMode, TransactionInfo};
+    /// use serde_json::json;
+    ///
+    /// let mut tx = MockTransport::sample_transaction_info();
+    /// tx["tx_type"] = json!("private");
+    /// tx["is_use_xmr"] = json!(true);
+    /// tx["privacy_fee"] = json!("0.0004");
+    /// let tx_info: TransactionInfo = serde_json::from_value(tx).unwrap();
+    /// assert_eq!(tx_info.privacy_mode(), PrivacyMode::XmrRouted);
+    /// assert_eq!(tx_info.privacy_fee, Some(0.0004));
+    /// ```
+    pub fn privacy_mode(&self) -> PrivacyMode {
+        PrivacyMode::applied(&self.tx_type, self.is_use_xmr)
+    }
+
+    /// Whether the deposit must carry `payin_extra_id` (memo, destination tag)
+    ///
+    /// A deposit without it cannot be attributed to the swap, so UIs should make the
+    /// user acknowledge the extra id before showing the payin address.
+    pub fn requires_payin_extra_id(&self) -> bool {
+        self.payin_extra_id.as_deref().is_some_and(|id| !id.is_empty())
+    }
+
+    /// Payment URI of the deposit, with amount and extra id, for the built-in networks
+    ///
+    /// BIP21 for Bitcoin-like networks, EIP-681 for EVM networks and the Monero URI
+    /// scheme; `None` if the payin network has none, for token deposits (whose decimals
+    /// are unknown here) and when the scheme cannot carry the extra id. Use
+    /// `FogswapSdk::payin_uri` for networks registered at runtime and token deposits.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tx_info = FogswapSdk::new().get_transaction_info("S7ZulO3j16").await?;
+    /// if let Some(uri) = tx_info.payin_uri() {
+    ///     println!("{}", uri);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn payin_uri(&self) -> Option<String> {
+        crate::networks::BUILTIN.payin_uri(self, None)
+    }
+
+    /// Whether the transaction waits for `set_emergency_action`
+    pub fn needs_emergency_action(&self) -> bool {
+        self.emergency.as_ref().is_some_and(|emergency| emergency.choice.is_none())
+    }
+
+    /// `eta_seconds` as a duration
+    pub fn eta(&self) -> Option<std::time::Duration> {
+        self.eta_seconds.map(std::time::Duration::from_secs)
+    }
+
+    /// Share of the swap done, from 0.0 when waiting for the deposit to 1.0 once final
+    ///
+    /// Confirmations fill the range of the `confirming` status when the required count
+    /// is known. Meant for progress bars: every final status, failed ones too, is 1.0.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::testing::MockTransport;
+    /// use fogswap_sdk_rust::TransactionInfo;
+    /// use serde_json::json;
+    ///
+    /// let mut tx = MockTransport::sample_transaction_info();
+    /// tx["status"] = json!("confirming");
+    /// tx["confirmations"] = json!(2);
+    /// tx["required_confirmations"] = json!(10);
+    /// tx["eta_seconds"] = json!(720);
+    /// let tx_info: TransactionInfo = serde_json::from_value(tx).unwrap();
+    /// assert!((tx_info.progress() - 0.18).abs() < 1e-9);
+    /// println!("estimated {} minutes remaining, {}/{} confirmations",
+    ///     tx_info.eta_seconds.unwrap() / 60, tx_info.confirmations.unwrap(), tx_info.confirmations_required.unwrap());
+    /// ```
+    pub fn progress(&self) -> f64 {
+        match self.status {
+            TransactionStatus::Waiting => 0.0,
+            TransactionStatus::Confirming => match (self.confirmations, self.confirmations_required) {
+                (Some(seen), Some(required)) if required > 0 => 0.1 + 0.4 * (f64::from(seen) / f64::from(required)).min(1.0),
+                _ => 0.1,
+            },
+            TransactionStatus::Exchanging => 0.6,
+            TransactionStatus::Sending => 0.85,
+            TransactionStatus::Unknown(_) => 0.5,
+            TransactionStatus::Finished
+            | TransactionStatus::Failed
+            | TransactionStatus::Refunded
+            | TransactionStatus::Expired => 1.0,
+        }
+    }
+
+    /// Hash of the refund, when the deposit was returned
+    pub fn refund(&self) -> Option<&str> {
+        if self.status != TransactionStatus::Refunded {
+            return None;
+        }
+        self.refund_hash.as_deref()
+    }
+}
+
+/// Deposit instructions, with the extra id on its own line when required
+impl fmt::Display for TransactionInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Transaction {} ({})", self.id, self.status)?;
+        write!(f, "Send {} {} on {} to {}", self.amount_from, self.contract_address_from, self.network_from, self.payin_address)?;
+        if self.requires_payin_extra_id() {
+            write!(f, "\nREQUIRED memo / extra id: {} (deposits without it are lost)", self.payin_extra_id.as_deref().unwrap_or_default())?;
+        }
+        write!(f, "\nReceive {} {} on {} at {}", self.amount_to, self.contract_address_to, self.network_to, self.payout_address)?;
+        if self.status == TransactionStatus::Refunded {
+            write!(f, "\nRefunded to {}", self.refund_address.as_deref().unwrap_or("the refund address"))?;
+            if let Some(hash) = &self.refund_hash {
+                write!(f, " ({})", hash)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+/// Kind of swap, sent and received in lowercase (`"standard"`, `"private"`)
+///
+/// The capitalized names written by earlier SDK versions are still accepted.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::TxType;
+///
+/// for tx_type in [TxType::Standard, TxType::Private] {
+///     let json = serde_json::to_value(&tx_type).unwrap();
+///     assert_eq!(json, tx_type.to_string());
+///     assert_eq!(serde_json::from_value::<TxType>(json).unwrap(), tx_type);
+///     assert_eq!(TxType::try_from(tx_type.to_string().as_str()).unwrap(), tx_type);
+/// }
+/// assert_eq!(serde_json::to_value(TxType::Private).unwrap(), "private");
+/// assert_eq!(serde_json::from_str::<TxType>("\"Private\"").unwrap(), TxType::Private);
+/// assert_eq!(serde_json::from_str::<TxType>("\"fixed\"").unwrap(), TxType::Unknown);
+/// assert!(TxType::try_from("fixed").is_err());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
 pub enum TxType {
+    #[default]
+    #[serde(alias = "Standard")]
     Standard,
+    #[serde(alias = "Private")]
     Private,
+    /// A type added to the API after this SDK version; never valid in a request
+    #[serde(other)]
+    Unknown,
 }
 
-impl ToString for TxType {
-    fn to_string(&self) -> String {
+impl fmt::Display for TxType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TxType::Standard => "standard".to_string(),
-            TxType::Private => "private".to_string(),
+            TxType::Standard => write!(f, "standard"),
+            TxType::Private => write!(f, "private"),
+            TxType::Unknown => write!(f, "unknown"),
         }
     }
 }
@@ -91,4 +554,11 @@ impl FromStr for TxType {
             _ => Err(anyhow::anyhow!("Invalid tx type")),
         }
     }
+}
+
+impl TryFrom<&str> for TxType {
+    type Error = anyhow::Error;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
 }
\ No newline at end of file
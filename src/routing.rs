@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::error::FogswapSdkError;
+use crate::orchestrator::check_value_loss;
+use crate::req_structs::{Asset, CreateTransactionRequest, Pair, PrivacyMode, QuoteRequest};
+use crate::resp_structs::TransactionInfo;
+use crate::resp_structs::{QuoteResponse, TxType};
+use crate::FogswapSdk;
+
+/// Relative importance of the criteria routes are ranked by
+#[derive(Debug, Clone)]
+pub struct RouteWeights {
+    pub net_received: f64,
+    pub privacy: f64,
+    /// Only used for routes with a known ETA, see `RoutePlanner::with_eta`
+    pub eta: f64,
+}
+
+impl Default for RouteWeights {
+    fn default() -> Self {
+        Self { net_received: 1.0, privacy: 0.0, eta: 0.0 }
+    }
+}
+
+/// A quoted way of doing a swap, ready to execute
+#[derive(Debug, Clone)]
+pub struct RouteOption {
+    pub tx_type: TxType,
+    pub is_use_xmr: Option<bool>,
+    pub quote: QuoteResponse,
+    /// `amount_to` minus the payout network fee
+    pub net_received: f64,
+    /// 0 for standard, 1 for private, 2 for private through XMR
+    pub privacy_level: u8,
+    pub eta: Option<Duration>,
+    /// Weighted score between 0 and the sum of the weights, higher is better
+    pub score: f64,
+}
+
+impl RouteOption {
+
+    pub fn privacy_mode(&self) -> PrivacyMode {
+        PrivacyMode::applied(&self.tx_type, self.is_use_xmr)
+    }
+
+    /// Request creating the transaction of this route, with the quoted amount as floor
+    pub fn to_request(&self, payout_address: &str) -> CreateTransactionRequest {
+        CreateTransactionRequest {
+            network_from: self.quote.network_from.clone(),
+            contract_address_from: self.quote.contract_address_from.clone(),
+            network_to: self.quote.network_to.clone(),
+            contract_address_to: self.quote.contract_address_to.clone(),
+            amount_from: self.quote.amount_from,
+            payout_address: payout_address.to_string(),
+            tx_type: Some(self.tx_type.clone()),
+            is_use_xmr: self.is_use_xmr,
+            min_amount_to: Some(self.quote.amount_to),
+            ..Default::default()
+        }
+    }
+}
+
+/// Standard and private quotes of the same swap
+#[derive(Debug, Clone)]
+pub struct QuoteComparison {
+    pub standard: QuoteResponse,
+    pub private: QuoteResponse,
+}
+
+impl QuoteComparison {
+
+    pub fn get(&self, tx_type: &TxType) -> &QuoteResponse {
+        match tx_type {
+            TxType::Private => &self.private,
+            TxType::Standard | TxType::Unknown => &self.standard,
+        }
+    }
+
+    pub fn amount_to(&self, tx_type: &TxType) -> f64 {
+        self.get(tx_type).amount_to
+    }
+
+    /// USD value of `amount_to`, if the API priced the pair
+    pub fn usd_to(&self, tx_type: &TxType) -> Option<f64> {
+        self.get(tx_type).convert_usd.to
+    }
+
+    /// The quote paying out the most; private wins a tie
+    pub fn best_by_output(&self) -> &QuoteResponse {
+        if self.standard.amount_to > self.private.amount_to { &self.standard } else { &self.private }
+    }
+
+    /// Relative amount given up by going private (0.01 = 1% less than standard)
+    pub fn privacy_cost(&self) -> f64 {
+        if self.standard.amount_to > 0.0 {
+            (self.standard.amount_to - self.private.amount_to) / self.standard.amount_to
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Limits of multi-hop route search, see `FogswapSdk::plan_route`
+#[derive(Debug, Clone)]
+pub struct RouteOptions {
+    /// Most swaps chained in a route; 1 only allows the direct pair
+    pub max_hops: usize,
+    /// Assets a route may pass through, in the order they are tried
+    pub intermediate_whitelist: Vec<Asset>,
+}
+
+impl Default for RouteOptions {
+    fn default() -> Self {
+        Self {
+            max_hops: 2,
+            intermediate_whitelist: vec![Asset::native("btc"), Asset::native("eth"), Asset::new("eth", "USDT")],
+        }
+    }
+}
+
+/// A swap done as a chain of swaps, each paying out into the next one
+#[derive(Debug, Clone)]
+pub struct RoutePlan {
+    /// Quote of every hop; the `amount_from` of a hop is the `amount_to` of the previous one
+    pub hops: Vec<QuoteResponse>,
+}
+
+impl RoutePlan {
+
+    pub fn amount_from(&self) -> f64 {
+        self.hops.first().map_or(0.0, |hop| hop.amount_from)
+    }
+
+    /// Quoted amount received at the end of the chain
+    pub fn amount_to(&self) -> f64 {
+        self.hops.last().map_or(0.0, |hop| hop.amount_to)
+    }
+
+    /// Assets passed through, without the source and destination
+    pub fn intermediates(&self) -> Vec<Asset> {
+        self.hops.iter().skip(1).map(|hop| Asset::new(&hop.network_from, &hop.contract_address_from)).collect()
+    }
+}
+
+/// Every way of doing a swap this SDK offers
+static CANDIDATES: [(TxType, Option<bool>, u8); 3] = [
+    (TxType::Standard, None, 0),
+    (TxType::Private, Some(false), 1),
+    (TxType::Private, Some(true), 2),
+];
+
+/// Quotes a swap over every transaction type and XMR option and ranks the results
+#[derive(Debug, Clone)]
+pub struct RoutePlanner {
+    pub sdk: FogswapSdk,
+    pub weights: RouteWeights,
+    /// Drop routes whose quote implies a USD loss above this (0.05 = 5%)
+    pub max_value_loss: Option<f64>,
+    etas: HashMap<TxType, Duration>,
+}
+
+impl RoutePlanner {
+
+    pub fn new(sdk: FogswapSdk) -> Self {
+        Self { sdk, weights: RouteWeights::default(), max_value_loss: None, etas: HashMap::new() }
+    }
+
+    pub fn with_weights(mut self, weights: RouteWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    pub fn with_max_value_loss(mut self, max_loss: f64) -> Self {
+        self.max_value_loss = Some(max_loss);
+        self
+    }
+
+    /// Expected duration of swaps of `tx_type`, as observed by the caller
+    pub fn with_eta(mut self, tx_type: TxType, eta: Duration) -> Self {
+        self.etas.insert(tx_type, eta);
+        self
+    }
+
+    /// Quote every route of `pair` concurrently and rank the viable ones, best first
+    ///
+    /// Routes that fail to quote or exceed `max_value_loss` are left out.
+    /// # Errors
+    /// * `FogswapSdkError::NoRoute` - If no route is viable
+    /// * Any error of `get_network_fee`
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, Pair, RoutePlanner, RouteWeights};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let planner = RoutePlanner::new(FogswapSdk::new()).with_weights(RouteWeights {
+    ///     net_received: 1.0,
+    ///     privacy: 0.5,
+    ///     eta: 0.0,
+    /// });
+    /// let routes = planner.plan(&Pair::new("sol", "SOL", "eth", "ETH"), 0.5).await?;
+    /// let best = &routes[0];
+    /// println!("{} receives {} (score {:.2})", best.tx_type, best.net_received, best.score);
+    /// let tx_info = planner.sdk.create_transaction_with(&best.to_request("YOUR_RECEIVE_ADDRESS_HERE")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn plan(&self, pair: &Pair, amount_from: f64) -> Result<Vec<RouteOption>> {
+        let quotes = futures::future::join_all(CANDIDATES.iter().map(|(tx_type, is_use_xmr, _)| {
+            let req = QuoteRequest {
+                amount_from,
+                network_from: pair.network_from.clone(),
+                contract_address_from: pair.contract_address_from.clone(),
+                network_to: pair.network_to.clone(),
+                contract_address_to: pair.contract_address_to.clone(),
+                tx_type: Some(tx_type.clone()),
+                is_use_xmr: *is_use_xmr,
+                ..Default::default()
+            };
+            async move { self.sdk.get_quote_with(&req).await }
+        }));
+        let (quotes, network_fee) = tokio::join!(
+            quotes,
+            self.sdk.get_network_fee(&pair.network_to, &pair.contract_address_to),
+        );
+        let network_fee = network_fee?.fee;
+
+        let mut routes: Vec<RouteOption> = CANDIDATES.iter().zip(quotes)
+            .filter_map(|((tx_type, is_use_xmr, privacy_level), quote)| {
+                let quote = quote.ok()?;
+                if let Some(max_loss) = self.max_value_loss && check_value_loss(&quote, max_loss).is_err() {
+                    return None;
+                }
+                Some(RouteOption {
+                    tx_type: tx_type.clone(),
+                    is_use_xmr: *is_use_xmr,
+                    net_received: (quote.amount_to - network_fee).max(0.0),
+                    quote,
+                    privacy_level: *privacy_level,
+                    eta: self.etas.get(tx_type).copied(),
+                    score: 0.0,
+                })
+            })
+            .collect();
+        if routes.is_empty() {
+            let e = "no route could be quoted".to_string();
+            return Err(FogswapSdkError::NoRoute(e).into());
+        }
+
+        self.score(&mut routes);
+        routes.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(routes)
+    }
+
+    /// Score each criterion relative to the best route, then weight them
+    fn score(&self, routes: &mut [RouteOption]) {
+        let best_net = routes.iter().map(|r| r.net_received).fold(0.0, f64::max);
+        let fastest = routes.iter().filter_map(|r| r.eta).min();
+        let max_privacy = CANDIDATES.iter().map(|(_, _, level)| *level).max().unwrap_or(1) as f64;
+        for route in routes {
+            let net = if best_net > 0.0 { route.net_received / best_net } else { 0.0 };
+            let privacy = route.privacy_level as f64 / max_privacy;
+            let eta = match (fastest, route.eta) {
+                (Some(fastest), Some(eta)) if !eta.is_zero() => fastest.as_secs_f64() / eta.as_secs_f64(),
+                _ => 0.0,
+            };
+            route.score = self.weights.net_received * net + self.weights.privacy * privacy + self.weights.eta * eta;
+        }
+    }
+}
+
+impl FogswapSdk {
+
+    /// Quote a swap as both standard and private transaction, concurrently
+    ///
+    /// The `tx_type` of `req` is ignored. For XMR routing, network fees and
+    /// weighted ranking use `RoutePlanner`.
+    /// # Errors
+    /// * Any error of `get_quote_with`, for either type
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, FogswapSdk, QuoteRequest};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let comparison = sdk.compare_quotes(&QuoteRequest::new(&Asset::native("btc"), &Asset::native("xmr"), 0.1)).await?;
+    /// let best = comparison.best_by_output();
+    /// println!("{} pays {} (private costs {:.2}%)", best.tx_type, best.amount_to, comparison.privacy_cost() * 100.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn compare_quotes(&self, req: &QuoteRequest) -> Result<QuoteComparison> {
+        let standard = QuoteRequest { tx_type: Some(TxType::Standard), ..req.clone() };
+        let private = QuoteRequest { tx_type: Some(TxType::Private), ..req.clone() };
+        let (standard, private) = tokio::try_join!(self.get_quote_with(&standard), self.get_quote_with(&private))?;
+        Ok(QuoteComparison { standard, private })
+    }
+
+    /// Find the route paying out the most for `amount` of `from`, directly or through
+    /// intermediate assets when allowed by `options`
+    ///
+    /// The direct pair and every path through the whitelist, up to `max_hops` swaps,
+    /// are quoted concurrently; paths whose quotes fail are skipped.
+    /// # Errors
+    /// * `FogswapSdkError::NoRoute` - If no path could be quoted
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{Asset, FogswapSdk, RouteOptions};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let plan = sdk.plan_route(&Asset::new("sol", "BONK"), &Asset::native("xmr"), 1_000_000.0, RouteOptions::default()).await?;
+    /// println!("{} hops, receive {} XMR", plan.hops.len(), plan.amount_to());
+    /// let txs = sdk.execute_route(&plan, "YOUR_XMR_ADDRESS").await?;
+    /// println!("Deposit to {}", txs[0].payin_address);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn plan_route(&self, from: &Asset, to: &Asset, amount: f64, options: RouteOptions) -> Result<RoutePlan> {
+        let paths = route_paths(from, to, &options);
+        let plans = futures::future::join_all(paths.iter().map(|path| self.quote_path(path, amount))).await;
+        plans
+            .into_iter()
+            .filter_map(Result::ok)
+            .max_by(|a, b| a.amount_to().total_cmp(&b.amount_to()))
+            .ok_or_else(|| {
+                let e = format!("no route from {} {} to {} {}", from.network, from.contract_address, to.network, to.contract_address);
+                FogswapSdkError::NoRoute(e).into()
+            })
+    }
+
+    /// Quote the swaps along `path` one after the other, each with the output of the previous
+    async fn quote_path(&self, path: &[Asset], amount: f64) -> Result<RoutePlan> {
+        let mut hops: Vec<QuoteResponse> = Vec::new();
+        for leg in path.windows(2) {
+            let amount_from = hops.last().map_or(amount, |hop| hop.amount_to);
+            hops.push(self.get_quote_with(&QuoteRequest::new(&leg[0], &leg[1], amount_from)).await?);
+        }
+        Ok(RoutePlan { hops })
+    }
+
+    /// Create the transactions of a route, last hop first, so every hop pays out to
+    /// the deposit address of the next one
+    ///
+    /// Returns the transactions in hop order; the user deposits into the first one.
+    /// Later hops expect the quoted amounts, so a hop paying out less than quoted
+    /// is handled by the API like any short deposit.
+    /// # Errors
+    /// * `FogswapSdkError::Cancelled` - If the token of `with_cancellation` is cancelled; no hop is created after that
+    /// * Any error of `create_transaction_with`; transactions created before it stay unfunded and expire
+    pub async fn execute_route(&self, plan: &RoutePlan, payout_address: &str) -> Result<Vec<TransactionInfo>> {
+        let mut txs: Vec<TransactionInfo> = Vec::with_capacity(plan.hops.len());
+        for hop in plan.hops.iter().rev() {
+            let (payout_address, payout_extra_id) = match txs.last() {
+                Some(next) => (next.payin_address.as_str(), next.payin_extra_id.clone()),
+                None => (payout_address, None),
+            };
+            let tx_info = self.create_transaction_with(&CreateTransactionRequest {
+                network_from: hop.network_from.clone(),
+                contract_address_from: hop.contract_address_from.clone(),
+                network_to: hop.network_to.clone(),
+                contract_address_to: hop.contract_address_to.clone(),
+                amount_from: hop.amount_from,
+                payout_address: payout_address.to_string(),
+                payout_extra_id,
+                tx_type: Some(hop.tx_type.clone()),
+                ..Default::default()
+            }).await?;
+            txs.push(tx_info);
+        }
+        txs.reverse();
+        Ok(txs)
+    }
+}
+
+/// Every path from `from` to `to` of at most `max_hops` swaps, through distinct whitelisted assets
+fn route_paths(from: &Asset, to: &Asset, options: &RouteOptions) -> Vec<Vec<Asset>> {
+    let mut paths = Vec::new();
+    let mut stack = vec![vec![from.clone()]];
+    while let Some(path) = stack.pop() {
+        let hops = path.len() - 1;
+        if hops >= options.max_hops {
+            continue;
+        }
+        let mut direct = path.clone();
+        direct.push(to.clone());
+        paths.push(direct);
+        for mid in &options.intermediate_whitelist {
+            if mid != to && !path.contains(mid) {
+                let mut longer = path.clone();
+                longer.push(mid.clone());
+                stack.push(longer);
+            }
+        }
+    }
+    paths
+}
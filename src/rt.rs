@@ -0,0 +1,42 @@
+#[cfg(target_arch = "wasm32")]
+use std::future::Future;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+pub use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Complete after `duration` on the tokio timer, or the browser timer on wasm
+#[cfg(not(target_arch = "wasm32"))]
+pub fn sleep(duration: Duration) -> BoxFuture<'static, ()> {
+    Box::pin(tokio::time::sleep(duration))
+}
+
+/// Complete after `duration` on the tokio timer, or the browser timer on wasm
+///
+/// Browser timers are not `Send`, so the timer runs on the local task queue and
+/// signals through a channel.
+#[cfg(target_arch = "wasm32")]
+pub fn sleep(duration: Duration) -> BoxFuture<'static, ()> {
+    let (done, wait) = tokio::sync::oneshot::channel();
+    wasm_bindgen_futures::spawn_local(async move {
+        gloo_timers::future::sleep(duration).await;
+        let _ = done.send(());
+    });
+    Box::pin(async move {
+        let _ = wait.await;
+    })
+}
+
+/// Run a `!Send` future to completion on the local task queue and await its output from any task
+#[cfg(target_arch = "wasm32")]
+pub fn run_local<T: Send + 'static>(fut: impl Future<Output = T> + 'static) -> impl Future<Output = Option<T>> + Send {
+    let (done, wait) = tokio::sync::oneshot::channel();
+    wasm_bindgen_futures::spawn_local(async move {
+        let _ = done.send(fut.await);
+    });
+    async move { wait.await.ok() }
+}
@@ -0,0 +1,82 @@
+use anyhow::Result;
+use serde_json::json;
+
+use crate::endpoint::parse_result;
+use crate::error::FogswapSdkError;
+use crate::resp_structs::TransactionInfo;
+use crate::status::{TransactionStatus, LIFECYCLE};
+use crate::FogswapSdk;
+
+impl FogswapSdk {
+
+    /// Move a sandbox transaction to `status`, for integration tests
+    ///
+    /// Only the sandbox environment serves this endpoint; build the client with
+    /// `FogswapSdk::builder().environment(Environment::Sandbox)`. A production client
+    /// refuses the call without sending it.
+    /// # Errors
+    /// * `FogswapSdkError::InvalidConfig` - If the client targets production
+    /// * `FogswapSdkError::Api` - If the sandbox refuses the transition
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, TransactionStatus};
+    ///
+    /// # async fn example(sdk: FogswapSdk) -> Result<(), Box<dyn std::error::Error>> {
+    /// let tx_info = sdk.sandbox_advance_status("S7ZulO3j16", TransactionStatus::Confirming).await?;
+    /// assert_eq!(tx_info.status, TransactionStatus::Confirming);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sandbox_advance_status(&self, tx_id: &str, status: TransactionStatus) -> Result<TransactionInfo> {
+        self.check_not_production()?;
+        let endpoint = "/sandbox/transaction/status";
+
+        let resp = self.send_request(
+            reqwest::Method::POST,
+            endpoint,
+            Some(json!({
+                "tx_id": tx_id,
+                "status": status,
+            }))
+        ).await?;
+
+        let tx_info = parse_result(endpoint, resp)?;
+        self.track(&tx_info);
+        Ok(tx_info)
+    }
+
+    /// Advance a sandbox transaction through every lifecycle step up to `target`
+    ///
+    /// Steps already passed are skipped, so monitors see the same sequence as in production.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, TransactionStatus};
+    ///
+    /// # async fn example(sdk: FogswapSdk) -> Result<(), Box<dyn std::error::Error>> {
+    /// let tx_info = sdk.sandbox_drive_to("S7ZulO3j16", TransactionStatus::Finished).await?;
+    /// assert!(tx_info.status.is_success());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sandbox_drive_to(&self, tx_id: &str, target: TransactionStatus) -> Result<TransactionInfo> {
+        self.check_not_production()?;
+        let mut tx_info = self.get_transaction_info(tx_id).await?;
+        let passed = tx_info.status.rank().map_or(0, |rank| rank + 1);
+        let steps = LIFECYCLE.iter().take(target.implied_steps()).skip(passed).cloned().chain([target]);
+        for status in steps {
+            if tx_info.status == status {
+                continue;
+            }
+            tx_info = self.sandbox_advance_status(tx_id, status).await?;
+        }
+        Ok(tx_info)
+    }
+
+    fn check_not_production(&self) -> Result<()> {
+        if self.environment().is_production() {
+            let e = "sandbox helpers are refused against production".to_string();
+            return Err(FogswapSdkError::InvalidConfig(e).into());
+        }
+        Ok(())
+    }
+}
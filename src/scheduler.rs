@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+
+use crate::clock::Clock;
+use crate::rt::Instant;
+use crate::FogswapSdk;
+
+/// FIFO queue releasing one outgoing request per interval
+///
+/// Tokio's mutex wakes waiters in the order they arrived, so holding it while
+/// waiting for the next slot gives strict first-in first-out dispatch.
+#[derive(Debug)]
+pub(crate) struct RequestQueue {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RequestQueue {
+
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        Self { interval, next_slot: Mutex::new(now) }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Wait until this caller's turn to send
+    pub async fn acquire(&self, clock: &dyn Clock) {
+        let mut next_slot = self.next_slot.lock().await;
+        clock.sleep_until(*next_slot).await;
+        *next_slot = clock.now() + self.interval;
+    }
+}
+
+/// Token bucket admitting `rate` requests per second on average and `burst` at once
+///
+/// Waiters are served in arrival order, like `RequestQueue`.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    /// Available tokens, as of the instant
+    bucket: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+
+    pub fn new(rate: f64, burst: u32, now: Instant) -> Self {
+        let burst = burst.max(1) as f64;
+        Self { rate, burst, bucket: Mutex::new((burst, now)) }
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst as u32
+    }
+
+    /// Wait for a token and take it
+    pub async fn acquire(&self, clock: &dyn Clock) {
+        let mut bucket = self.bucket.lock().await;
+        let (tokens, refilled_at) = &mut *bucket;
+        let now = clock.now();
+        *tokens = (*tokens + now.saturating_duration_since(*refilled_at).as_secs_f64() * self.rate).min(self.burst);
+        *refilled_at = now;
+        if *tokens < 1.0 {
+            clock.sleep(Duration::from_secs_f64((1.0 - *tokens) / self.rate)).await;
+            *tokens = 1.0;
+            *refilled_at = clock.now();
+        }
+        *tokens -= 1.0;
+    }
+}
+
+impl FogswapSdk {
+
+    /// Queue every outgoing request and dispatch them one at a time, in call order,
+    /// at most one per `interval`
+    ///
+    /// The queue is shared by all clones of this client. Retries and credential
+    /// replays take a new place at the end of the queue.
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// // at most 5 requests per second
+    /// let sdk = FogswapSdk::new().with_request_queue(Duration::from_millis(200));
+    /// ```
+    pub fn with_request_queue(mut self, interval: Duration) -> Self {
+        self.queue = Some(std::sync::Arc::new(RequestQueue::new(interval, self.clock.now())));
+        self
+    }
+
+    /// Limit outgoing requests to `rps` per second on average, allowing bursts of `burst`
+    ///
+    /// The limit is shared by all clones of this client and also applies to retries.
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// let sdk = FogswapSdk::new().with_rate_limit(5.0, 10);
+    /// ```
+    /// # Panics
+    /// * If `rps` is not positive
+    pub fn with_rate_limit(mut self, rps: f64, burst: u32) -> Self {
+        assert!(rps > 0.0, "rate limit must be positive");
+        self.limiter = Some(std::sync::Arc::new(RateLimiter::new(rps, burst, self.clock.now())));
+        self
+    }
+}
@@ -0,0 +1,276 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::req_structs::{CreateTransactionRequest, QuoteRequest};
+use crate::resp_structs::{QuoteResponse, TransactionInfo};
+use crate::status::TransactionStatus;
+use crate::FogswapSdk;
+
+/// Step of a swap, from the quote to its outcome
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SwapStage {
+    Quoted,
+    /// Created, before its first status poll
+    Created,
+    AwaitingDeposit,
+    /// Deposit seen, `seen` of `required` confirmations when the API reports them
+    Confirming { seen: Option<u32>, required: Option<u32> },
+    Exchanging,
+    Sending,
+    Finished,
+    /// Failed, refunded or expired
+    Failed { status: TransactionStatus },
+}
+
+impl SwapStage {
+
+    /// Stage of a transaction in `status`; `None` for statuses this SDK version does not know
+    pub fn of(tx_info: &TransactionInfo) -> Option<Self> {
+        Some(match &tx_info.status {
+            TransactionStatus::Waiting => SwapStage::AwaitingDeposit,
+            TransactionStatus::Confirming => SwapStage::Confirming {
+                seen: tx_info.confirmations,
+                required: tx_info.confirmations_required,
+            },
+            TransactionStatus::Exchanging => SwapStage::Exchanging,
+            TransactionStatus::Sending => SwapStage::Sending,
+            TransactionStatus::Finished => SwapStage::Finished,
+            status @ (TransactionStatus::Failed | TransactionStatus::Refunded | TransactionStatus::Expired) => {
+                SwapStage::Failed { status: status.clone() }
+            }
+            TransactionStatus::Unknown(_) => return None,
+        })
+    }
+
+    /// Whether the swap no longer changes
+    pub fn is_final(&self) -> bool {
+        matches!(self, SwapStage::Finished | SwapStage::Failed { .. })
+    }
+}
+
+/// Change of stage of a [`SwapSession`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapTransition {
+    /// `None` for the first stage of a session
+    pub from: Option<SwapStage>,
+    pub to: SwapStage,
+}
+
+/// Lifecycle of one swap: quote, creation, deposit, exchange and outcome
+///
+/// `start` quotes and creates the swap, so the payin instructions are available right
+/// away. `advance` then polls until the next stage and returns the transition, or
+/// `None` once the swap is final. The transaction id is all the state there is:
+/// persist `id()` and continue after a restart with `resume`.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{Asset, FogswapSdk, QuoteRequest, SwapSession, SwapStage};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let sdk = FogswapSdk::new();
+/// let request = QuoteRequest::new(&Asset::native("btc"), &Asset::native("xmr"), 0.1);
+/// let mut session = SwapSession::start(sdk.clone(), &request, "YOUR_RECEIVE_ADDRESS_HERE").await?;
+/// let tx_info = session.transaction();
+/// println!("Send {} {} to {}", tx_info.amount_from, tx_info.contract_address_from, tx_info.payin_address);
+/// save_session_id(session.id());
+///
+/// // ... after a restart
+/// let mut session = SwapSession::resume(sdk, &load_session_id()).await?;
+/// while let Some(transition) = session.advance().await? {
+///     match transition.to {
+///         SwapStage::Confirming { seen: Some(seen), required: Some(required) } => println!("{}/{} confirmations", seen, required),
+///         stage => println!("{:?}", stage),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// # fn save_session_id(_: &str) {}
+/// # fn load_session_id() -> String { String::new() }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SwapSession {
+    sdk: FogswapSdk,
+    quote: Option<QuoteResponse>,
+    tx_info: TransactionInfo,
+    stage: SwapStage,
+    history: Vec<SwapTransition>,
+    poll_interval: Duration,
+}
+
+impl SwapSession {
+
+    /// Quote `request` and create the swap paying out to `payout_address`
+    /// # Errors
+    /// * Any error of `get_quote_with` or `create_transaction_with`
+    pub async fn start(sdk: FogswapSdk, request: &QuoteRequest, payout_address: &str) -> Result<Self> {
+        let create = CreateTransactionRequest { payout_address: payout_address.to_string(), ..Default::default() };
+        Self::start_with(sdk, request, create).await
+    }
+
+    /// Quote `request` and create the swap with the payout, refund and `min_amount_to` of `create`
+    ///
+    /// The route and amount of `create` are replaced by the quote's; its `is_use_xmr`,
+    /// promo code, partner id and fee default to those of `request`.
+    /// # Errors
+    /// * Any error of `get_quote_with` or `create_transaction_with`
+    pub async fn start_with(sdk: FogswapSdk, request: &QuoteRequest, create: CreateTransactionRequest) -> Result<Self> {
+        let quote = sdk.get_quote_with(request).await?;
+        let tx_info = sdk.create_transaction_with(&CreateTransactionRequest {
+            network_from: quote.network_from.clone(),
+            contract_address_from: quote.contract_address_from.clone(),
+            network_to: quote.network_to.clone(),
+            contract_address_to: quote.contract_address_to.clone(),
+            amount_from: quote.amount_from,
+            tx_type: Some(quote.tx_type.clone()),
+            is_use_xmr: create.is_use_xmr.or(request.is_use_xmr),
+            promo_code: create.promo_code.or_else(|| request.promo_code.clone()),
+            partner_id: create.partner_id.or_else(|| request.partner_id.clone()),
+            fee_bps: create.fee_bps.or(request.fee_bps),
+            ..create
+        }).await?;
+        let history = vec![
+            SwapTransition { from: None, to: SwapStage::Quoted },
+            SwapTransition { from: Some(SwapStage::Quoted), to: SwapStage::Created },
+        ];
+        Ok(Self { sdk, quote: Some(quote), tx_info, stage: SwapStage::Created, history, poll_interval: Duration::from_secs(10) })
+    }
+
+    /// Continue the session of transaction `tx_id`, at its current stage
+    /// # Errors
+    /// * Any error of `get_transaction_info`
+    pub async fn resume(sdk: FogswapSdk, tx_id: &str) -> Result<Self> {
+        let tx_info = sdk.get_transaction_info(tx_id).await?;
+        let stage = SwapStage::of(&tx_info).unwrap_or(SwapStage::Created);
+        let history = vec![SwapTransition { from: None, to: stage.clone() }];
+        Ok(Self { sdk, quote: None, tx_info, stage, history, poll_interval: Duration::from_secs(10) })
+    }
+
+    /// Delay between two polls of `advance`, 10 seconds by default
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Wait for the next stage and return the transition, or `None` once the swap is final
+    ///
+    /// New confirmation counts are transitions from `Confirming` to `Confirming`.
+    /// # Errors
+    /// * `FogswapSdkError::Shutdown` - If the client is shut down while waiting
+    /// * `FogswapSdkError::Cancelled` - If the token of `with_cancellation` is cancelled while waiting
+    /// * Any error of `get_transaction_info`; the session is unchanged and `advance` can be called again
+    pub async fn advance(&mut self) -> Result<Option<SwapTransition>> {
+        if self.stage.is_final() {
+            return Ok(None);
+        }
+        // A fresh session learns its first polled stage without waiting
+        let mut wait = self.stage != SwapStage::Created;
+        loop {
+            if wait {
+                self.sdk.until_shutdown(async { self.sdk.clock.sleep(self.poll_interval).await; Ok(()) }).await?;
+            }
+            wait = true;
+            let tx_info = self.sdk.get_transaction_info(&self.tx_info.id).await?;
+            let stage = SwapStage::of(&tx_info);
+            self.tx_info = tx_info;
+            if let Some(stage) = stage && stage != self.stage {
+                let transition = SwapTransition { from: Some(std::mem::replace(&mut self.stage, stage.clone())), to: stage };
+                self.history.push(transition.clone());
+                return Ok(Some(transition));
+            }
+        }
+    }
+
+    /// Transaction id, enough to `resume` the session
+    pub fn id(&self) -> &str {
+        &self.tx_info.id
+    }
+
+    pub fn stage(&self) -> &SwapStage {
+        &self.stage
+    }
+
+    /// Transaction as last seen, with the payin address, extra id and amount to deposit
+    pub fn transaction(&self) -> &TransactionInfo {
+        &self.tx_info
+    }
+
+    /// Quote the swap was created from; `None` for a resumed session
+    pub fn quote(&self) -> Option<&QuoteResponse> {
+        self.quote.as_ref()
+    }
+
+    /// Every transition of this session, oldest first
+    pub fn history(&self) -> &[SwapTransition] {
+        &self.history
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use crate::session::{SwapSession, SwapStage, SwapTransition};
+    use crate::testing::MockTransport;
+    use crate::{Asset, CreateTransactionRequest, FogswapSdk, QuoteRequest, TransactionStatus, TransportResponse};
+
+    fn info_in(status: &str) -> TransportResponse {
+        let mut tx_info = MockTransport::sample_transaction_info();
+        tx_info["status"] = json!(status);
+        TransportResponse::result(tx_info)
+    }
+
+    #[tokio::test]
+    async fn started_session_resumes_from_its_id_to_the_final_status() {
+        let mock = Arc::new(MockTransport::new());
+        let sdk = FogswapSdk::new().with_transport(mock.clone());
+        let request = QuoteRequest::new(&Asset::native("sol"), &Asset::native("eth"), 1.0);
+
+        let session = SwapSession::start(sdk.clone(), &request, "0xpayout").await.unwrap();
+        assert_eq!(session.stage(), &SwapStage::Created);
+        assert!(session.quote().is_some());
+        let saved = session.id().to_string();
+        drop(session);
+
+        mock.once("/transaction/info", info_in("waiting"));
+        mock.once("/transaction/info", info_in("exchanging"));
+        mock.on("/transaction/info", info_in("finished"));
+        let mut session = SwapSession::resume(sdk, &saved).await.unwrap().with_poll_interval(Duration::from_millis(1));
+        assert_eq!(session.stage(), &SwapStage::AwaitingDeposit);
+        assert!(session.quote().is_none());
+
+        let mut stages = Vec::new();
+        while let Some(transition) = session.advance().await.unwrap() {
+            stages.push(transition.to);
+        }
+        assert_eq!(stages, vec![SwapStage::Exchanging, SwapStage::Finished]);
+        assert_eq!(session.transaction().status, TransactionStatus::Finished);
+        assert_eq!(session.history().last(), Some(&SwapTransition { from: Some(SwapStage::Exchanging), to: SwapStage::Finished }));
+        assert!(session.advance().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn start_with_forwards_refund_and_minimum_received() {
+        let mock = Arc::new(MockTransport::new());
+        let sdk = FogswapSdk::new().with_transport(mock.clone());
+        let request = QuoteRequest::new(&Asset::native("sol"), &Asset::native("eth"), 1.0);
+        let create = CreateTransactionRequest {
+            payout_address: "0xpayout".to_string(),
+            refund_address: Some("refund_addr".to_string()),
+            min_amount_to: Some(0.04),
+            ..Default::default()
+        };
+
+        SwapSession::start_with(sdk, &request, create).await.unwrap();
+        let created = mock.requests().into_iter().find(|r| r.endpoint == "/transaction/create").unwrap();
+        let body = created.body.unwrap();
+        assert_eq!(body["payout_address"], "0xpayout");
+        assert_eq!(body["refund_address"], "refund_addr");
+        assert_eq!(body["min_amount_to"], 0.04);
+        assert_eq!(body["network_from"], "sol");
+    }
+}
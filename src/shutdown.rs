@@ -0,0 +1,259 @@
+use std::future::Future;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Mutex;
+
+use anyhow::Result;
+use tokio::sync::watch;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::task::{AbortHandle, JoinHandle};
+use tokio_util::sync::CancellationToken;
+
+use crate::error::FogswapSdkError;
+use crate::rt::SystemTime;
+use crate::FogswapSdk;
+
+/// A task spawned by the SDK
+#[derive(Debug, Clone)]
+pub struct TaskInfo {
+    /// Stable name, also given to the tokio task when built with `tokio_unstable`
+    pub name: &'static str,
+    pub started_at: SystemTime,
+}
+
+/// Shutdown signal and background tasks shared by every clone of a client
+#[derive(Debug)]
+pub(crate) struct ShutdownState {
+    signal: watch::Sender<bool>,
+    #[cfg(not(target_arch = "wasm32"))]
+    tasks: Mutex<Vec<(TaskInfo, AbortHandle)>>,
+}
+
+impl Default for ShutdownState {
+    fn default() -> Self {
+        Self {
+            signal: watch::Sender::new(false),
+            #[cfg(not(target_arch = "wasm32"))]
+            tasks: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl ShutdownState {
+
+    pub fn is_shut_down(&self) -> bool {
+        *self.signal.borrow()
+    }
+
+    /// Tasks still running, forgetting the finished ones
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn running_tasks(&self) -> Vec<TaskInfo> {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        tasks.retain(|(_, handle)| !handle.is_finished());
+        tasks.iter().map(|(info, _)| info.clone()).collect()
+    }
+
+    /// Browser tasks cannot be observed
+    #[cfg(target_arch = "wasm32")]
+    pub fn running_tasks(&self) -> Vec<TaskInfo> {
+        Vec::new()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn track(&self, name: &'static str, handle: AbortHandle) {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        tasks.retain(|(_, handle)| !handle.is_finished());
+        tasks.push((TaskInfo { name, started_at: SystemTime::now() }, handle));
+    }
+
+    /// Resolve once `shutdown()` has been called
+    pub async fn cancelled(&self) {
+        let mut rx = self.signal.subscribe();
+        // The sender lives as long as `self`, so this cannot fail while awaited
+        let _ = rx.wait_for(|shut_down| *shut_down).await;
+    }
+}
+
+/// Handle of a background task that is aborted when dropped
+///
+/// Call `detach()` to keep the task running without holding the handle; it then
+/// stops on `FogswapSdk::shutdown()`.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct BackgroundTask {
+    handle: Option<JoinHandle<()>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BackgroundTask {
+
+    pub(crate) fn new(handle: JoinHandle<()>) -> Self {
+        Self { handle: Some(handle) }
+    }
+
+    pub fn abort(&self) {
+        if let Some(handle) = &self.handle {
+            handle.abort();
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.as_ref().is_none_or(|handle| handle.is_finished())
+    }
+
+    /// Let the task run on without this handle
+    pub fn detach(mut self) -> JoinHandle<()> {
+        self.handle.take().expect("handle is only taken by detach")
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for BackgroundTask {
+    fn drop(&mut self) {
+        self.abort();
+    }
+}
+
+impl FogswapSdk {
+
+    /// Cancel every in-flight request and background task of this client and its clones
+    ///
+    /// Pending calls, including orchestrator polls, return `FogswapSdkError::Shutdown`,
+    /// and so does every later call. Monitors and probes stop.
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() {
+    /// let sdk = FogswapSdk::new();
+    /// let monitor = sdk.spawn_health_monitor(Duration::from_secs(30));
+    /// tokio::signal::ctrl_c().await.ok();
+    /// sdk.shutdown();
+    /// assert!(sdk.is_shut_down());
+    /// # }
+    /// ```
+    pub fn shutdown(&self) {
+        self.shutdown.signal.send_replace(true);
+    }
+
+    pub fn is_shut_down(&self) -> bool {
+        self.shutdown.is_shut_down()
+    }
+
+    /// Clone of this client whose calls end when `token` is cancelled
+    ///
+    /// Requests, polls (`watch_transaction`, `wait_for_status`, `SwapSession::advance`,
+    /// orchestrator waits), route execution and WebSocket subscriptions of the returned
+    /// client, and of its clones, fail with `FogswapSdkError::Cancelled` once the token
+    /// is cancelled, including those already in flight. Unlike `shutdown()`, other
+    /// clients sharing the connection pool keep working, so one token can be scoped to
+    /// a single watch with `token.child_token()`.
+    /// # Examples
+    /// ```
+    /// use tokio_util::sync::CancellationToken;
+    /// use fogswap_sdk_rust::{FogswapSdk, FogswapSdkError, PollOptions, TransactionStatus};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let token = CancellationToken::new();
+    /// let watcher = sdk.clone().with_cancellation(token.child_token());
+    /// let watch = tokio::spawn(async move {
+    ///     watcher.wait_for_status("S7ZulO3j16", &[TransactionStatus::Finished], PollOptions::default()).await
+    /// });
+    ///
+    /// tokio::signal::ctrl_c().await?;
+    /// token.cancel();
+    /// match watch.await? {
+    ///     Err(e) if matches!(e.downcast_ref::<FogswapSdkError>(), Some(FogswapSdkError::Cancelled)) => println!("watch cancelled"),
+    ///     result => println!("{:?}", result?.status),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Whether the cancellation token of this client, if any, has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Fail fast when the client is shut down or cancelled
+    pub(crate) fn ensure_active(&self) -> Result<()> {
+        if self.is_shut_down() {
+            return Err(FogswapSdkError::Shutdown.into());
+        }
+        if self.is_cancelled() {
+            return Err(FogswapSdkError::Cancelled.into());
+        }
+        Ok(())
+    }
+
+    /// Run `fut` unless the client is, or gets, shut down or cancelled
+    pub(crate) async fn until_shutdown<T>(&self, fut: impl Future<Output = Result<T>>) -> Result<T> {
+        self.ensure_active()?;
+        tokio::select! {
+            result = fut => result,
+            _ = self.shutdown.cancelled() => Err(FogswapSdkError::Shutdown.into()),
+            _ = cancelled(self.cancel.clone()) => Err(FogswapSdkError::Cancelled.into()),
+        }
+    }
+
+    /// Background tasks of this client and its clones that are still running
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() {
+    /// let sdk = FogswapSdk::new();
+    /// let monitor = sdk.spawn_health_monitor(Duration::from_secs(30));
+    /// for task in sdk.background_tasks() {
+    ///     println!("{} running since {:?}", task.name, task.started_at);
+    /// }
+    /// # }
+    /// ```
+    pub fn background_tasks(&self) -> Vec<TaskInfo> {
+        self.shutdown.running_tasks()
+    }
+
+    /// Spawn `fut` as a named task that ends on shutdown
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn spawn_until_shutdown(&self, name: &'static str, fut: impl Future<Output = ()> + Send + 'static) -> JoinHandle<()> {
+        let shutdown = self.shutdown.clone();
+        let task = async move {
+            tokio::select! {
+                _ = fut => {}
+                _ = shutdown.cancelled() => {}
+            }
+        };
+        #[cfg(tokio_unstable)]
+        let handle = tokio::task::Builder::new().name(name).spawn(task).expect("spawn background task");
+        #[cfg(not(tokio_unstable))]
+        let handle = tokio::spawn(task);
+        self.shutdown.track(name, handle.abort_handle());
+        handle
+    }
+
+    /// Spawn `fut` on the browser's task queue; it ends on shutdown
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn spawn_until_shutdown(&self, _name: &'static str, fut: impl Future<Output = ()> + 'static) {
+        let shutdown = self.shutdown.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            tokio::select! {
+                _ = fut => {}
+                _ = shutdown.cancelled() => {}
+            }
+        });
+    }
+}
+
+/// Resolve once `token` is cancelled; never without a token
+pub(crate) async fn cancelled(token: Option<CancellationToken>) {
+    match token {
+        Some(token) => token.cancelled_owned().await,
+        None => std::future::pending().await,
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::json;
+
+use crate::endpoint::parse_result;
+use crate::FogswapSdk;
+
+/// Lifecycle status of a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TransactionStatus {
+    Waiting,
+    Confirming,
+    Exchanging,
+    Sending,
+    Finished,
+    Failed,
+    Refunded,
+    Expired,
+    /// A status this SDK version does not know yet
+    Unknown(String),
+}
+
+impl TransactionStatus {
+
+    /// User-presentable English description of the status
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::TransactionStatus;
+    ///
+    /// let status: TransactionStatus = "waiting".parse().unwrap();
+    /// assert_eq!(status.description(), "Waiting for your deposit");
+    /// ```
+    pub fn description(&self) -> &'static str {
+        match self {
+            TransactionStatus::Waiting => "Waiting for your deposit",
+            TransactionStatus::Confirming => "Deposit received, waiting for confirmations",
+            TransactionStatus::Exchanging => "Exchanging — usually takes 5–30 minutes",
+            TransactionStatus::Sending => "Sending funds to your address",
+            TransactionStatus::Finished => "Swap completed",
+            TransactionStatus::Failed => "Swap failed, please contact support",
+            TransactionStatus::Refunded => "Your deposit was refunded",
+            TransactionStatus::Expired => "Expired, no deposit was received in time",
+            TransactionStatus::Unknown(_) => "Processing your swap",
+        }
+    }
+
+    /// Statuses after which a transaction no longer changes
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, TransactionStatus::Finished | TransactionStatus::Failed | TransactionStatus::Refunded | TransactionStatus::Expired)
+    }
+
+    /// Whether the payout was sent
+    pub fn is_success(&self) -> bool {
+        *self == TransactionStatus::Finished
+    }
+
+    /// Whether the swap ended without a payout
+    pub fn is_failure(&self) -> bool {
+        self.is_terminal() && !self.is_success()
+    }
+
+    /// Whether a deposit is still expected
+    pub fn is_awaiting_deposit(&self) -> bool {
+        *self == TransactionStatus::Waiting
+    }
+
+    /// Position in the lifecycle, `None` for unknown statuses
+    pub(crate) fn rank(&self) -> Option<usize> {
+        match self {
+            TransactionStatus::Waiting => Some(0),
+            TransactionStatus::Confirming => Some(1),
+            TransactionStatus::Exchanging => Some(2),
+            TransactionStatus::Sending => Some(3),
+            TransactionStatus::Unknown(_) => None,
+            _ => Some(4),
+        }
+    }
+
+    /// Whether a transaction in this status has reached `target` or gone past it
+    pub(crate) fn is_at_or_past(&self, target: &TransactionStatus) -> bool {
+        self == target || self.rank().zip(target.rank()).is_some_and(|(rank, target)| rank >= target)
+    }
+
+    /// Number of `LIFECYCLE` steps a transaction must have passed to reach this status
+    pub(crate) fn implied_steps(&self) -> usize {
+        match self {
+            TransactionStatus::Finished => LIFECYCLE.len(),
+            // A refund needs a confirmed deposit
+            TransactionStatus::Refunded => 2,
+            TransactionStatus::Failed | TransactionStatus::Expired | TransactionStatus::Unknown(_) => 0,
+            _ => self.rank().unwrap_or(0),
+        }
+    }
+}
+
+/// Non-terminal statuses in lifecycle order
+pub(crate) const LIFECYCLE: [TransactionStatus; 4] = [
+    TransactionStatus::Waiting,
+    TransactionStatus::Confirming,
+    TransactionStatus::Exchanging,
+    TransactionStatus::Sending,
+];
+
+impl fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionStatus::Waiting => write!(f, "waiting"),
+            TransactionStatus::Confirming => write!(f, "confirming"),
+            TransactionStatus::Exchanging => write!(f, "exchanging"),
+            TransactionStatus::Sending => write!(f, "sending"),
+            TransactionStatus::Finished => write!(f, "finished"),
+            TransactionStatus::Failed => write!(f, "failed"),
+            TransactionStatus::Refunded => write!(f, "refunded"),
+            TransactionStatus::Expired => write!(f, "expired"),
+            TransactionStatus::Unknown(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl FromStr for TransactionStatus {
+    type Err = Infallible;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "waiting" => TransactionStatus::Waiting,
+            "confirming" => TransactionStatus::Confirming,
+            "exchanging" => TransactionStatus::Exchanging,
+            "sending" => TransactionStatus::Sending,
+            "finished" => TransactionStatus::Finished,
+            "failed" => TransactionStatus::Failed,
+            "refunded" => TransactionStatus::Refunded,
+            "expired" => TransactionStatus::Expired,
+            _ => TransactionStatus::Unknown(s.to_string()),
+        })
+    }
+}
+
+impl Serialize for TransactionStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TransactionStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let Ok(status) = s.parse();
+        Ok(status)
+    }
+}
+
+/// Status descriptions with per-status overrides, e.g. translations
+///
+/// Statuses without an override fall back to `TransactionStatus::description()`.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{StatusDescriptions, TransactionStatus};
+///
+/// let de = StatusDescriptions::default()
+///     .with(TransactionStatus::Waiting, "Warte auf deine Einzahlung")
+///     .with(TransactionStatus::Finished, "Tausch abgeschlossen");
+/// assert_eq!(de.describe(&TransactionStatus::Waiting), "Warte auf deine Einzahlung");
+/// assert_eq!(de.describe(&TransactionStatus::Sending), "Sending funds to your address");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StatusDescriptions {
+    overrides: HashMap<TransactionStatus, String>,
+}
+
+impl StatusDescriptions {
+
+    pub fn with(mut self, status: TransactionStatus, description: &str) -> Self {
+        self.overrides.insert(status, description.to_string());
+        self
+    }
+
+    pub fn describe(&self, status: &TransactionStatus) -> &str {
+        self.overrides.get(status).map(String::as_str).unwrap_or_else(|| status.description())
+    }
+}
+
+/// A status change of one transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusEvent {
+    pub tx_id: String,
+    pub status: TransactionStatus,
+    /// Never observed, but implied by a later status
+    pub inferred: bool,
+}
+
+/// Turns statuses observed from racing sources (polling, webhooks) into an ordered event sequence
+///
+/// Per transaction, statuses only move forward: duplicates and regressions are dropped,
+/// nothing follows a final status, and lifecycle steps skipped between two observations
+/// are emitted as inferred events. Unknown statuses pass through unless repeated, and
+/// do not change which later statuses count as a regression.
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::{StatusSequencer, TransactionStatus};
+///
+/// let sequencer = StatusSequencer::default();
+/// sequencer.observe("tx1", TransactionStatus::Waiting);
+/// // webhook and poll deliver the same status
+/// assert_eq!(sequencer.observe("tx1", TransactionStatus::Exchanging).len(), 2);
+/// assert!(sequencer.observe("tx1", TransactionStatus::Exchanging).is_empty());
+/// // a late poll result is out of date
+/// assert!(sequencer.observe("tx1", TransactionStatus::Confirming).is_empty());
+///
+/// let events = sequencer.observe("tx1", TransactionStatus::Finished);
+/// assert_eq!(events[0].status, TransactionStatus::Sending);
+/// assert!(events[0].inferred);
+/// assert!(!events[1].inferred);
+/// ```
+#[derive(Debug, Default)]
+pub struct StatusSequencer {
+    last: Mutex<HashMap<String, Tracked>>,
+}
+
+/// Statuses recorded for one transaction
+#[derive(Debug)]
+struct Tracked {
+    /// Last lifecycle status, against which regressions are judged
+    ranked: Option<TransactionStatus>,
+    /// Last status emitted, of any kind
+    last: TransactionStatus,
+}
+
+impl StatusSequencer {
+
+    /// Record an observed status and get the events to publish, in order
+    pub fn observe(&self, tx_id: &str, status: TransactionStatus) -> Vec<StatusEvent> {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        let event = |status: TransactionStatus, inferred: bool| StatusEvent { tx_id: tx_id.to_string(), status, inferred };
+
+        let rank = status.rank();
+        let Some(tracked) = last.get_mut(tx_id) else {
+            let ranked = rank.is_some().then(|| status.clone());
+            last.insert(tx_id.to_string(), Tracked { ranked, last: status.clone() });
+            return vec![event(status, false)];
+        };
+        if tracked.last == status || tracked.ranked.as_ref().is_some_and(TransactionStatus::is_terminal) {
+            return Vec::new();
+        }
+        let Some(rank) = rank else {
+            tracked.last = status.clone();
+            return vec![event(status, false)];
+        };
+        let Some(previous_rank) = tracked.ranked.as_ref().and_then(TransactionStatus::rank) else {
+            // Only unknown statuses so far: nothing to infer from
+            tracked.ranked = Some(status.clone());
+            tracked.last = status.clone();
+            return vec![event(status, false)];
+        };
+        if rank <= previous_rank {
+            return Vec::new();
+        }
+
+        let skipped = previous_rank + 1..status.implied_steps().max(previous_rank + 1);
+        let mut events: Vec<StatusEvent> = LIFECYCLE[skipped].iter().map(|s| event(s.clone(), true)).collect();
+        events.push(event(status.clone(), false));
+        tracked.ranked = Some(status.clone());
+        tracked.last = status;
+        events
+    }
+
+    /// Last recorded status of a transaction
+    pub fn last_status(&self, tx_id: &str) -> Option<TransactionStatus> {
+        let last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        last.get(tx_id).map(|tracked| tracked.last.clone())
+    }
+
+    /// Stop tracking a transaction
+    pub fn forget(&self, tx_id: &str) {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        last.remove(tx_id);
+    }
+}
+
+/// Status and hashes of a transaction, without the rest of `TransactionInfo`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransactionStatusInfo {
+    pub id: String,
+    pub status: TransactionStatus,
+    pub payin_hash: Option<String>,
+    pub payout_hash: Option<String>,
+}
+
+impl FogswapSdk {
+
+    /// Get only the status and hashes of a transaction, for high-frequency polling
+    ///
+    /// Asks the API for a minimal field set, keeping responses small.
+    /// # Arguments
+    /// * `id` - The id of the transaction
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the transaction is not found
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let status = sdk.get_transaction_status("S7ZulO3j16").await?;
+    /// println!("{}: {}", status.status, status.status.description());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all, fields(tx_id = %id)))]
+    pub async fn get_transaction_status(&self, id: &str) -> Result<TransactionStatusInfo> {
+        let endpoint = "/transaction/info";
+
+        let resp = self.send_request(
+            reqwest::Method::GET,
+            endpoint,
+            Some(json!({
+                "tx_id": id,
+                "fields": "id,status,payin_hash,payout_hash"
+            }))
+        ).await?;
+
+        parse_result(endpoint, resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StatusSequencer, TransactionStatus};
+
+    #[test]
+    fn repeated_unknown_status_is_emitted_once() {
+        let sequencer = StatusSequencer::default();
+        let on_hold = || TransactionStatus::Unknown("on_hold".to_string());
+
+        assert_eq!(sequencer.observe("tx1", TransactionStatus::Waiting).len(), 1);
+        assert_eq!(sequencer.observe("tx1", on_hold()).len(), 1);
+        assert!(sequencer.observe("tx1", on_hold()).is_empty());
+        assert_eq!(sequencer.last_status("tx1"), Some(on_hold()));
+        // An unknown status does not reset the lifecycle
+        assert!(sequencer.observe("tx1", TransactionStatus::Waiting).is_empty());
+        assert_eq!(sequencer.observe("tx1", TransactionStatus::Confirming).len(), 1);
+
+        assert_eq!(sequencer.observe("tx2", on_hold()).len(), 1);
+        assert!(sequencer.observe("tx2", on_hold()).is_empty());
+    }
+}
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::client_metadata::ClientMetadata;
+use crate::endpoint::parse_result;
+use crate::error::FogswapSdkError;
+use crate::req_structs::CreateTransactionRequest;
+use crate::resp_structs::TransactionInfo;
+use crate::rt::{SystemTime, UNIX_EPOCH};
+use crate::FogswapSdk;
+
+/// A create call recorded before it was sent to the API
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PendingIntent {
+    /// Unix timestamp (seconds) of when the intent was recorded
+    pub recorded_at: i64,
+    pub request: CreateTransactionRequest,
+}
+
+impl PendingIntent {
+
+    pub fn new(request: CreateTransactionRequest) -> Self {
+        let recorded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        Self { recorded_at, request }
+    }
+
+    pub fn external_id(&self) -> &str {
+        self.request.external_id.as_deref().unwrap_or_default()
+    }
+}
+
+/// Local persistence for create intents and client metadata
+///
+/// Implementations must make `save_intent` durable before returning, since it is
+/// the only record of a swap whose POST was interrupted.
+pub trait TxStore: Debug + Send + Sync {
+    /// Record an intent before the create request is sent
+    fn save_intent(&self, intent: &PendingIntent) -> Result<()>;
+
+    /// Mark an intent as resolved by the created transaction
+    fn complete_intent(&self, external_id: &str, tx_id: &str) -> Result<()>;
+
+    /// Drop an intent the API definitely rejected
+    fn discard_intent(&self, external_id: &str) -> Result<()>;
+
+    /// Intents that were recorded but never completed or discarded
+    fn pending_intents(&self) -> Result<Vec<PendingIntent>>;
+
+    /// Persist the client metadata of a created transaction
+    fn save_metadata(&self, _tx_id: &str, _metadata: &ClientMetadata) -> Result<()> {
+        Ok(())
+    }
+
+    /// Client metadata previously saved for a transaction
+    fn load_metadata(&self, _tx_id: &str) -> Result<Option<ClientMetadata>> {
+        Ok(None)
+    }
+}
+
+/// Non-persistent store, useful for tests
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    intents: Mutex<HashMap<String, PendingIntent>>,
+    metadata: Mutex<HashMap<String, ClientMetadata>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TxStore for MemoryStore {
+
+    fn save_intent(&self, intent: &PendingIntent) -> Result<()> {
+        let mut intents = self.intents.lock().unwrap_or_else(|e| e.into_inner());
+        intents.insert(intent.external_id().to_string(), intent.clone());
+        Ok(())
+    }
+
+    fn complete_intent(&self, external_id: &str, _tx_id: &str) -> Result<()> {
+        self.discard_intent(external_id)
+    }
+
+    fn discard_intent(&self, external_id: &str) -> Result<()> {
+        let mut intents = self.intents.lock().unwrap_or_else(|e| e.into_inner());
+        intents.remove(external_id);
+        Ok(())
+    }
+
+    fn pending_intents(&self) -> Result<Vec<PendingIntent>> {
+        let intents = self.intents.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(intents.values().cloned().collect())
+    }
+
+    fn save_metadata(&self, tx_id: &str, metadata: &ClientMetadata) -> Result<()> {
+        let mut entries = self.metadata.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(tx_id.to_string(), metadata.clone());
+        Ok(())
+    }
+
+    fn load_metadata(&self, tx_id: &str) -> Result<Option<ClientMetadata>> {
+        let entries = self.metadata.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(entries.get(tx_id).cloned())
+    }
+}
+
+/// Contents of a `JsonFileStore` file
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct StoreFile {
+    #[serde(default)]
+    intents: HashMap<String, PendingIntent>,
+    #[serde(default)]
+    metadata: HashMap<String, ClientMetadata>,
+}
+
+/// Store keeping pending intents and client metadata in a JSON file, rewritten atomically on every change
+#[derive(Debug)]
+pub struct JsonFileStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl JsonFileStore {
+
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), lock: Mutex::new(()) }
+    }
+
+    fn read(&self) -> Result<StoreFile> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(StoreFile::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn write(&self, file: &StoreFile) -> Result<()> {
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(file)?)?;
+        std::fs::File::open(&tmp)?.sync_all()?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    fn update(&self, f: impl FnOnce(&mut StoreFile)) -> Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut file = self.read()?;
+        f(&mut file);
+        self.write(&file)
+    }
+}
+
+impl TxStore for JsonFileStore {
+
+    fn save_intent(&self, intent: &PendingIntent) -> Result<()> {
+        self.update(|file| {
+            file.intents.insert(intent.external_id().to_string(), intent.clone());
+        })
+    }
+
+    fn complete_intent(&self, external_id: &str, _tx_id: &str) -> Result<()> {
+        self.discard_intent(external_id)
+    }
+
+    fn discard_intent(&self, external_id: &str) -> Result<()> {
+        self.update(|file| {
+            file.intents.remove(external_id);
+        })
+    }
+
+    fn pending_intents(&self) -> Result<Vec<PendingIntent>> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.read()?.intents.into_values().collect())
+    }
+
+    fn save_metadata(&self, tx_id: &str, metadata: &ClientMetadata) -> Result<()> {
+        self.update(|file| {
+            file.metadata.insert(tx_id.to_string(), metadata.clone());
+        })
+    }
+
+    fn load_metadata(&self, tx_id: &str) -> Result<Option<ClientMetadata>> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.read()?.metadata.remove(tx_id))
+    }
+}
+
+/// Outcome of `FogswapSdk::reconcile`
+#[derive(Debug, Default)]
+pub struct ReconcileReport {
+    /// Intents whose transaction already existed on the API
+    pub recovered: Vec<TransactionInfo>,
+    /// Intents the API answered `not_found` for, created again under the same external id
+    pub resubmitted: Vec<TransactionInfo>,
+    /// Intents that could not be resolved yet, with the reason
+    pub unresolved: Vec<(PendingIntent, String)>,
+}
+
+impl FogswapSdk {
+
+    /// Resolve create intents left pending by a crash
+    ///
+    /// Each pending intent is looked up by its external id. Only an intent the API
+    /// answers `not_found` for is created again, with the same external id. Any other
+    /// failure of the lookup, e.g. a rate limit, a server error or a timeout, does not
+    /// tell whether the swap exists, so the intent is reported as unresolved and left
+    /// pending for the next run.
+    /// # Returns
+    /// * `ReconcileReport` - What happened to each pending intent
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    /// use fogswap_sdk_rust::{FogswapSdk, JsonFileStore};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_store(Arc::new(JsonFileStore::new("fogswap-intents.json")));
+    /// let report = sdk.reconcile().await?;
+    /// println!("{} recovered, {} resubmitted", report.recovered.len(), report.resubmitted.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn reconcile(&self) -> Result<ReconcileReport> {
+        let mut report = ReconcileReport::default();
+        let Some(store) = &self.store else {
+            return Ok(report);
+        };
+
+        for intent in store.pending_intents()? {
+            let external_id = intent.external_id().to_string();
+            match self.find_by_external_id(&external_id).await {
+                Ok(tx_info) => {
+                    store.complete_intent(&external_id, &tx_info.id)?;
+                    report.recovered.push(tx_info);
+                }
+                Err(e) if is_not_found(&e) => {
+                    match self.create_transaction_with(&intent.request).await {
+                        Ok(tx_info) => report.resubmitted.push(tx_info),
+                        Err(e) => report.unresolved.push((intent, e.to_string())),
+                    }
+                }
+                Err(e) => report.unresolved.push((intent, e.to_string())),
+            }
+        }
+        Ok(report)
+    }
+
+    async fn find_by_external_id(&self, external_id: &str) -> Result<TransactionInfo> {
+        let endpoint = "/transaction/info";
+
+        let resp=self.send_request(
+            reqwest::Method::GET,
+            endpoint,
+            Some(json!({
+                "external_id": external_id
+            }))
+        ).await?;
+
+        parse_result(endpoint, resp)
+    }
+}
+
+/// Whether the API answered that the transaction does not exist; any other error,
+/// e.g. `rate_limited`, says nothing about whether it was created
+fn is_not_found(e: &anyhow::Error) -> bool {
+    matches!(e.downcast_ref::<FogswapSdkError>(), Some(FogswapSdkError::Api { code: Some(code), .. }) if code == "not_found")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::MockTransport;
+    use crate::{CreateTransactionRequest, FogswapSdk, MemoryStore, PendingIntent, TransportResponse, TxStore};
+
+    fn intent() -> PendingIntent {
+        PendingIntent::new(CreateTransactionRequest {
+            network_from: "sol".to_string(),
+            contract_address_from: "SOL".to_string(),
+            network_to: "eth".to_string(),
+            contract_address_to: "ETH".to_string(),
+            amount_from: 1.0,
+            payout_address: "0x742d35Cc6634C0532925a3b844Bc454e4438f44e".to_string(),
+            external_id: Some("order-1042".to_string()),
+            ..Default::default()
+        })
+    }
+
+    fn creates(mock: &MockTransport) -> usize {
+        mock.requests().iter().filter(|r| r.endpoint == "/transaction/create").count()
+    }
+
+    #[tokio::test]
+    async fn reconcile_leaves_intent_pending_on_other_api_errors() {
+        let mock = Arc::new(MockTransport::new());
+        let store = Arc::new(MemoryStore::new());
+        store.save_intent(&intent()).unwrap();
+        let sdk = FogswapSdk::new().with_transport(mock.clone()).with_store(store.clone());
+
+        for code in ["rate_limited", "internal_error"] {
+            mock.once("/transaction/info", TransportResponse::api_error(code, "try again later"));
+            let report = sdk.reconcile().await.unwrap();
+            assert!(report.resubmitted.is_empty());
+            assert_eq!(report.unresolved.len(), 1);
+            assert_eq!(store.pending_intents().unwrap().len(), 1);
+        }
+        assert_eq!(creates(&mock), 0);
+    }
+
+    #[tokio::test]
+    async fn reconcile_resubmits_intent_on_not_found() {
+        let mock = Arc::new(MockTransport::new());
+        let store = Arc::new(MemoryStore::new());
+        store.save_intent(&intent()).unwrap();
+        let sdk = FogswapSdk::new().with_transport(mock.clone()).with_store(store.clone());
+
+        mock.once("/transaction/info", TransportResponse::api_error("not_found", "unknown transaction"));
+        let report = sdk.reconcile().await.unwrap();
+        assert_eq!(report.resubmitted.len(), 1);
+        assert_eq!(creates(&mock), 1);
+        assert!(store.pending_intents().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::error::FogswapSdkError;
+use crate::meta::ResponseMeta;
+use crate::metrics::Attempt;
+
+/// Status bucket of a call outcome: `2xx`, `4xx`, `5xx`, `timeout` or `error`
+fn status_bucket(result: &anyhow::Result<(Value, ResponseMeta)>) -> String {
+    let status = match result {
+        Ok((_, meta)) => meta.status,
+        Err(e) => match e.downcast_ref::<FogswapSdkError>() {
+            Some(FogswapSdkError::Http { status, .. }) => *status,
+            Some(FogswapSdkError::RateLimited { .. }) => 429,
+            Some(FogswapSdkError::ServiceUnavailable { .. }) => 503,
+            Some(FogswapSdkError::AuthError(_)) => 401,
+            Some(FogswapSdkError::Timeout { .. }) => return "timeout".to_string(),
+            _ => return "error".to_string(),
+        },
+    };
+    format!("{}xx", status / 100)
+}
+
+/// Count the call, its retries and record its latency, backoff included
+pub(crate) fn call(
+    method: &reqwest::Method,
+    endpoint: &str,
+    result: &anyhow::Result<(Value, ResponseMeta)>,
+    attempts: &[Attempt],
+    elapsed: Duration,
+) {
+    let (method, endpoint) = (method.to_string(), endpoint.to_string());
+    ::metrics::counter!(
+        "fogswap_requests_total",
+        "method" => method.clone(), "endpoint" => endpoint.clone(), "status" => status_bucket(result),
+    ).increment(1);
+    ::metrics::histogram!("fogswap_request_duration_seconds", "method" => method, "endpoint" => endpoint.clone())
+        .record(elapsed.as_secs_f64());
+    let retries = attempts.len().saturating_sub(1) as u64;
+    if retries > 0 {
+        ::metrics::counter!("fogswap_retries_total", "endpoint" => endpoint).increment(retries);
+    }
+}
+
+/// Count a `429 Too Many Requests` answer, retried or not
+pub(crate) fn rate_limited(endpoint: &str) {
+    ::metrics::counter!("fogswap_rate_limited_total", "endpoint" => endpoint.to_string()).increment(1);
+}
+
+/// Count the outcome of a high-level operation such as `create_transaction` and record
+/// its duration, every request it made included
+pub(crate) fn operation<T>(name: &'static str, result: &anyhow::Result<T>, elapsed: Duration) {
+    let outcome = if result.is_ok() { "success" } else { "failure" };
+    ::metrics::counter!("fogswap_operations_total", "operation" => name, "outcome" => outcome).increment(1);
+    ::metrics::histogram!("fogswap_operation_duration_seconds", "operation" => name).record(elapsed.as_secs_f64());
+}
@@ -0,0 +1,212 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use serde_json::{json, Value};
+
+use crate::transport::{Transport, TransportResponse};
+
+/// Request received by a [`MockTransport`]
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: reqwest::Method,
+    /// Endpoint of the matched response, e.g. `/transaction/info`, or the URL path if none matched
+    pub endpoint: String,
+    pub query: HashMap<String, String>,
+    /// JSON body of POST requests
+    pub body: Option<Value>,
+    pub headers: reqwest::header::HeaderMap,
+}
+
+/// In-memory [`Transport`] answering every endpoint with canned responses
+///
+/// `new()` starts with a successful response for every endpoint of the SDK; `empty()`
+/// starts with none. Endpoints are matched on the end of the URL path, so any base URL
+/// works, and unmatched requests get a 404. Every request is recorded for assertions.
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use serde_json::json;
+/// use fogswap_sdk_rust::{FogswapSdk, TransactionStatus, TransportResponse};
+/// use fogswap_sdk_rust::testing::MockTransport;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let mock = Arc::new(MockTransport::new());
+/// let sdk = FogswapSdk::new().with_transport(mock.clone());
+///
+/// let tx_info = sdk.get_transaction_info("tx_1").await?;
+/// assert_eq!(tx_info.status, TransactionStatus::Waiting);
+///
+/// let mut finished = MockTransport::sample_transaction_info();
+/// finished["status"] = json!("finished");
+/// mock.on("/transaction/info", TransportResponse::result(finished));
+/// assert_eq!(sdk.get_transaction_info("tx_1").await?.status, TransactionStatus::Finished);
+///
+/// mock.once("/transaction/info", TransportResponse::api_error("not_found", "unknown transaction"));
+/// assert!(sdk.get_transaction_info("tx_1").await.is_err());
+///
+/// assert_eq!(mock.requests().len(), 3);
+/// assert_eq!(mock.requests()[0].query["tx_id"], "tx_1");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MockTransport {
+    responses: Mutex<HashMap<String, TransportResponse>>,
+    queued: Mutex<HashMap<String, VecDeque<TransportResponse>>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+impl Default for MockTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockTransport {
+
+    /// Mock with a successful canned response for every endpoint
+    pub fn new() -> Self {
+        let mock = Self::empty();
+        mock.on("/market/tokens", TransportResponse::result(json!([Self::sample_token_list()])));
+        mock.on("/market/limits", TransportResponse::result(json!({ "min_amount": 0.01, "max_amount": 1000.0 })));
+        mock.on("/market/network_fee", TransportResponse::result(json!({ "fee": 0.0005 })));
+        mock.on("/market/rates", TransportResponse::result(json!([{
+            "network_from": "sol", "contract_address_from": "SOL", "network_to": "eth", "contract_address_to": "ETH", "rate": 0.05
+        }])));
+        mock.on("/market/price", TransportResponse::result(json!({ "usd": 150.0 })));
+        mock.on("/market/validate_address", TransportResponse::result(json!({ "is_valid": true })));
+        mock.on("/transaction/quote", TransportResponse::result(Self::sample_quote()));
+        mock.on("/transaction/create", TransportResponse::result(Self::sample_transaction_info()));
+        mock.on("/transaction/info", TransportResponse::result(Self::sample_transaction_info()));
+        mock.on("/transaction/list", TransportResponse::result(json!([Self::sample_transaction_info()])));
+        mock.on("/transaction/emergency", TransportResponse::result(Self::sample_transaction_info()));
+        mock.on("/sandbox/transaction/status", TransportResponse::result(Value::Null));
+        mock.on("/status", TransportResponse::result(json!({ "status": "operational", "maintenance": false })));
+        mock
+    }
+
+    /// Mock without any response; every request gets a 404
+    pub fn empty() -> Self {
+        Self { responses: Mutex::default(), queued: Mutex::default(), requests: Mutex::default() }
+    }
+
+    /// Answer every later request to `endpoint` with `response`
+    pub fn on(&self, endpoint: &str, response: TransportResponse) -> &Self {
+        self.responses.lock().unwrap_or_else(|e| e.into_inner()).insert(endpoint.to_string(), response);
+        self
+    }
+
+    /// Answer the next request to `endpoint` with `response`, before falling back to `on`
+    ///
+    /// Responses queued for the same endpoint are used in order.
+    pub fn once(&self, endpoint: &str, response: TransportResponse) -> &Self {
+        self.queued.lock().unwrap_or_else(|e| e.into_inner()).entry(endpoint.to_string()).or_default().push_back(response);
+        self
+    }
+
+    /// Every request received so far, oldest first
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Canned `TokenList` of the `sol` network
+    pub fn sample_token_list() -> Value {
+        json!({
+            "network": "sol",
+            "network_image": "https://example.com/sol.png",
+            "tokens": [{
+                "token": "SOL",
+                "network": "sol",
+                "contract_address": "SOL",
+                "image": "https://example.com/sol.png",
+                "is_native": true,
+                "decimals": 9
+            }]
+        })
+    }
+
+    /// Canned `QuoteResponse` of 1 SOL to ETH
+    pub fn sample_quote() -> Value {
+        json!({
+            "network_from": "sol",
+            "contract_address_from": "SOL",
+            "amount_from": 1.0,
+            "network_to": "eth",
+            "contract_address_to": "ETH",
+            "amount_to": 0.05,
+            "convert_usd": { "from": 150.0, "to": 149.0 },
+            "tx_type": "standard",
+            "promo_discount": null,
+            "service_fee": 0.0001
+        })
+    }
+
+    /// Canned `TransactionInfo` waiting for its deposit
+    pub fn sample_transaction_info() -> Value {
+        json!({
+            "id": "tx_1",
+            "created_at": 1_700_000_000,
+            "tx_type": "standard",
+            "network_from": "sol",
+            "contract_address_from": "SOL",
+            "contract_address_to": "ETH",
+            "network_to": "eth",
+            "amount_from": 1.0,
+            "amount_to": 0.05,
+            "payin_address": "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU",
+            "payin_extra_id": null,
+            "payin_hash": null,
+            "payout_address": "0x742d35Cc6634C0532925a3b844Bc454e4438f44e",
+            "payout_extra_id": null,
+            "payout_hash": null,
+            "convert_usd": 150.0,
+            "status": "waiting",
+            "promo_discount": null,
+            "finished_at": null,
+            "network_fee": null,
+            "service_fee": null
+        })
+    }
+
+    /// Registered endpoint with the longest match on the end of `path`
+    fn endpoint<'a>(keys: impl Iterator<Item = &'a String>, path: &str) -> Option<String> {
+        keys.filter(|endpoint| path.ends_with(endpoint.as_str())).max_by_key(|endpoint| endpoint.len()).cloned()
+    }
+
+    fn respond(&self, req: &reqwest::Request) -> (String, TransportResponse) {
+        let path = req.url().path();
+        let mut queued = self.queued.lock().unwrap_or_else(|e| e.into_inner());
+        let non_empty = queued.iter().filter(|(_, q)| !q.is_empty()).map(|(k, _)| k);
+        if let Some(endpoint) = Self::endpoint(non_empty, path)
+            && let Some(response) = queued.get_mut(&endpoint).and_then(VecDeque::pop_front) {
+            return (endpoint, response);
+        }
+        let responses = self.responses.lock().unwrap_or_else(|e| e.into_inner());
+        match Self::endpoint(responses.keys(), path) {
+            Some(endpoint) => {
+                let response = responses[&endpoint].clone();
+                (endpoint, response)
+            }
+            None => {
+                let message = format!("no mock response for {}", path);
+                (path.to_string(), TransportResponse::json(404, &json!({ "error": { "message": message } })))
+            }
+        }
+    }
+}
+
+impl Transport for MockTransport {
+    fn execute(&self, req: reqwest::Request) -> BoxFuture<'_, Result<TransportResponse>> {
+        let (endpoint, response) = self.respond(&req);
+        self.requests.lock().unwrap_or_else(|e| e.into_inner()).push(RecordedRequest {
+            method: req.method().clone(),
+            endpoint,
+            query: req.url().query_pairs().into_owned().collect(),
+            body: req.body().and_then(|body| body.as_bytes()).and_then(|bytes| serde_json::from_slice(bytes).ok()),
+            headers: req.headers().clone(),
+        });
+        Box::pin(async move { Ok(response) })
+    }
+}
@@ -0,0 +1,145 @@
+use std::sync::RwLock;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::resp_structs::{TokenInfo, TokenList};
+use crate::rt::Instant;
+use crate::FogswapSdk;
+
+/// Last full token list and when it was fetched, shared by clones of a client
+#[derive(Debug, Default)]
+pub(crate) struct TokenListCache {
+    entry: RwLock<Option<(Instant, Vec<TokenList>)>>,
+    /// Held while fetching, so concurrent misses trigger a single request
+    refreshing: tokio::sync::Mutex<()>,
+}
+
+impl TokenListCache {
+
+    pub fn get(&self, ttl: Duration, now: Instant) -> Option<Vec<TokenList>> {
+        let entry = self.entry.read().unwrap_or_else(|e| e.into_inner());
+        entry.as_ref().filter(|(at, _)| now.saturating_duration_since(*at) < ttl).map(|(_, lists)| lists.clone())
+    }
+
+    pub fn put(&self, lists: &[TokenList], now: Instant) {
+        *self.entry.write().unwrap_or_else(|e| e.into_inner()) = Some((now, lists.to_vec()));
+    }
+
+    fn find(&self, network: &str, symbol_or_contract: &str) -> Option<TokenInfo> {
+        let entry = self.entry.read().unwrap_or_else(|e| e.into_inner());
+        entry.as_ref()?.1.iter()
+            .filter(|list| list.network.eq_ignore_ascii_case(network))
+            .find_map(|list| list.find(symbol_or_contract))
+            .cloned()
+    }
+}
+
+/// Age up to which the search helpers reuse the cached token list
+const SEARCH_TTL: Duration = Duration::from_secs(600);
+
+impl FogswapSdk {
+
+    /// Get the list of available tokens, from the cache while it is younger than `ttl`
+    ///
+    /// Every successful `get_token_list` fills the cache as well. Concurrent callers
+    /// that miss the cache share a single request.
+    /// # Arguments
+    /// * `ttl` - Maximum age of a cached list
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the cache is stale and the token list cannot be retrieved
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let tokens = sdk.get_token_list_cached(Duration::from_secs(600)).await?;
+    /// // Served from the cache
+    /// let again = sdk.get_token_list_cached(Duration::from_secs(600)).await?;
+    /// assert_eq!(tokens.len(), again.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_token_list_cached(&self, ttl: Duration) -> Result<Vec<TokenList>> {
+        if let Some(lists) = self.token_cache.get(ttl, self.clock.now()) {
+            return Ok(lists);
+        }
+        let _refreshing = self.token_cache.refreshing.lock().await;
+        // Filled by the caller we waited for
+        if let Some(lists) = self.token_cache.get(ttl, self.clock.now()) {
+            return Ok(lists);
+        }
+        self.get_token_list().await
+    }
+
+    /// Fetch the token list and replace the cached copy
+    /// # Errors
+    /// * `FogswapSdkError::Api` - If the token list cannot be retrieved; the cache is left unchanged
+    pub async fn refresh_token_list(&self) -> Result<Vec<TokenList>> {
+        let _refreshing = self.token_cache.refreshing.lock().await;
+        self.get_token_list().await
+    }
+
+    /// Look up a cached token by network and symbol or contract address, case-insensitively
+    ///
+    /// Never calls the API; returns `None` until the token list was fetched once.
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// sdk.get_token_list_cached(Duration::from_secs(600)).await?;
+    /// if let Some(usdt) = sdk.find_token("eth", "USDT") {
+    ///     println!("USDT contract: {}", usdt.contract_address);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_token(&self, network: &str, symbol_or_contract: &str) -> Option<TokenInfo> {
+        self.token_cache.find(network, symbol_or_contract)
+    }
+
+    /// Tokens whose symbol contains `query` or whose contract address equals it,
+    /// case-insensitively, over every network
+    ///
+    /// Uses the cached token list while it is younger than 10 minutes.
+    /// # Errors
+    /// * Any error of `get_token_list_cached`
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// for token in sdk.search_tokens("usdc").await? {
+    ///     println!("{} on {}: {}", token.token, token.network, token.contract_address);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_tokens(&self, query: &str) -> Result<Vec<TokenInfo>> {
+        self.tokens_where(|token| token.matches(query)).await
+    }
+
+    /// Tokens of `network`, matched case-insensitively
+    pub async fn tokens_on_network(&self, network: &str) -> Result<Vec<TokenInfo>> {
+        self.tokens_where(|token| token.network.eq_ignore_ascii_case(network)).await
+    }
+
+    /// Native coin of every network
+    pub async fn native_tokens(&self) -> Result<Vec<TokenInfo>> {
+        self.tokens_where(|token| token.is_native).await
+    }
+
+    async fn tokens_where(&self, predicate: impl Fn(&TokenInfo) -> bool) -> Result<Vec<TokenInfo>> {
+        Ok(self.get_token_list_cached(SEARCH_TTL).await?
+            .into_iter()
+            .flat_map(|list| list.tokens)
+            .filter(|token| predicate(token))
+            .collect())
+    }
+}
@@ -0,0 +1,37 @@
+use serde_json::Value;
+
+/// Payload and body fields never written to logs
+const REDACTED_FIELDS: &[&str] = &[
+    "api_key", "token", "access_token", "refresh_token", "authorization", "secret",
+    "payout_address", "payout_extra_id", "refund_address", "refund_extra_id",
+];
+
+/// Copy of `value` with secrets and addresses masked, at any depth
+pub(crate) fn redacted(value: &Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(fields.iter().map(|(name, value)| {
+            let masked = REDACTED_FIELDS.iter().any(|field| name.eq_ignore_ascii_case(field)) && !value.is_null();
+            let value = if masked { Value::String("***".to_string()) } else { redacted(value) };
+            (name.clone(), value)
+        }).collect()),
+        Value::Array(items) => Value::Array(items.iter().map(redacted).collect()),
+        value => value.clone(),
+    }
+}
+
+/// Log the redacted request payload at debug level
+pub(crate) fn request(payload: &Option<Value>) {
+    if tracing::enabled!(tracing::Level::DEBUG) {
+        let payload = payload.as_ref().map(redacted).unwrap_or(Value::Null);
+        tracing::debug!(payload = %payload, "request");
+    }
+}
+
+/// Log the outcome of a call, with the redacted body at debug level
+pub(crate) fn response<M>(result: &anyhow::Result<(Value, M)>) {
+    match result {
+        Ok((body, _)) if tracing::enabled!(tracing::Level::DEBUG) => tracing::debug!(body = %redacted(body), "response"),
+        Ok(_) => (),
+        Err(e) => tracing::warn!(error = %e, "request failed"),
+    }
+}
@@ -0,0 +1,467 @@
+use std::fmt::Debug;
+use std::net::IpAddr;
+#[cfg(not(target_arch = "wasm32"))]
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::HeaderMap;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::redirect::Policy;
+use reqwest::{Client, StatusCode, Url};
+use serde_json::{json, Value};
+
+use crate::auth::ApiKeyPlacement;
+use crate::config::redact_url;
+use crate::error::FogswapSdkError;
+use crate::FogswapSdk;
+
+/// Which IP family to use when the API host resolves to both
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IpPreference {
+    /// Keep the order returned by the system resolver
+    #[default]
+    System,
+    /// Try IPv4 addresses first, fall back to IPv6
+    PreferV4,
+    /// Try IPv6 addresses first, fall back to IPv4
+    PreferV6,
+    /// Never connect over IPv6
+    OnlyV4,
+    /// Never connect over IPv4
+    OnlyV6,
+}
+
+/// How HTTP redirects are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RedirectPolicy {
+    /// Follow up to 10 redirects
+    #[default]
+    Default,
+    /// Never follow; the redirect response fails the request
+    None,
+    /// Follow up to this many redirects, then fail
+    Limit(usize),
+}
+
+/// Proxy every API request is sent through
+#[derive(Clone, PartialEq, Eq)]
+pub enum ProxyConfig {
+    /// SOCKS5 proxy; host names are resolved by the proxy (`socks5h`), never locally
+    Socks5 { host: String, port: u16, auth: Option<(String, String)> },
+}
+
+impl ProxyConfig {
+
+    /// Local Tor daemon on its default SOCKS port, 127.0.0.1:9050
+    pub fn tor() -> Self {
+        ProxyConfig::Socks5 { host: "127.0.0.1".to_string(), port: 9050, auth: None }
+    }
+
+    /// Proxy URL; `socks5h` so DNS lookups of the API host do not leak around the proxy
+    pub fn url(&self) -> String {
+        match self {
+            ProxyConfig::Socks5 { host, port, .. } => format!("socks5h://{}:{}", host, port),
+        }
+    }
+
+    /// SOCKS5 credentials travel in the proxy URL, percent-encoded
+    #[cfg(not(target_arch = "wasm32"))]
+    fn to_reqwest(&self) -> Result<reqwest::Proxy> {
+        let ProxyConfig::Socks5 { auth, .. } = self;
+        let mut url = Url::parse(&self.url())?;
+        if let Some((username, password)) = auth {
+            let invalid = || FogswapSdkError::InvalidConfig("proxy credentials".to_string());
+            url.set_username(username).map_err(|_| invalid())?;
+            url.set_password(Some(password)).map_err(|_| invalid())?;
+        }
+        Ok(reqwest::Proxy::all(url)?)
+    }
+}
+
+/// Masks the proxy password, like `ConfigSnapshot` masks URL credentials
+impl std::fmt::Debug for ProxyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProxyConfig::Socks5 { host, port, auth } => f
+                .debug_struct("Socks5")
+                .field("host", host)
+                .field("port", port)
+                .field("auth", &auth.as_ref().map(|(username, _)| (username, "***")))
+                .finish(),
+        }
+    }
+}
+
+/// Low-level connection settings
+#[derive(Debug, Clone, Default)]
+pub struct TransportOptions {
+    pub ip_preference: IpPreference,
+    /// Timeout of a single connection attempt; with a preferred family this is
+    /// also how long an unreachable address delays the fallback
+    pub connect_timeout: Option<Duration>,
+    /// Refuse plaintext `http://` base URLs and redirects, except to localhost
+    pub https_only: bool,
+    pub redirects: RedirectPolicy,
+    /// Send every request through this proxy; the system proxy settings are ignored
+    pub proxy: Option<ProxyConfig>,
+}
+
+impl TransportOptions {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn apply(&self, mut builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder> {
+        if let Some(proxy) = &self.proxy {
+            builder = builder.no_proxy().proxy(proxy.to_reqwest()?);
+        }
+        if self.ip_preference != IpPreference::System {
+            builder = builder.dns_resolver(Arc::new(FamilyResolver(self.ip_preference)));
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if self.redirects != RedirectPolicy::Default || self.https_only {
+            let (redirects, https_only) = (self.redirects, self.https_only);
+            builder = builder.redirect(Policy::custom(move |attempt| {
+                let max_hops = match redirects {
+                    RedirectPolicy::Default => 10,
+                    RedirectPolicy::None => return attempt.stop(),
+                    RedirectPolicy::Limit(max_hops) => max_hops,
+                };
+                if attempt.previous().len() > max_hops {
+                    let e = format!("more than {} redirects", max_hops);
+                    attempt.error(e)
+                } else if https_only && !is_secure_url(attempt.url().as_str()) {
+                    let e = format!("redirect to insecure {}", redact_url(attempt.url().as_str()));
+                    attempt.error(e)
+                } else {
+                    attempt.follow()
+                }
+            }));
+        }
+        Ok(builder)
+    }
+
+    /// The browser owns connections, proxies and redirects; only `https_only` applies
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn apply(&self, builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder> {
+        if self.proxy.is_some()
+            || self.ip_preference != IpPreference::System
+            || self.connect_timeout.is_some()
+            || self.redirects != RedirectPolicy::Default {
+            let e = "proxy, IP preference, connect timeout and redirect policy are not available on wasm".to_string();
+            return Err(FogswapSdkError::InvalidConfig(e).into());
+        }
+        Ok(builder)
+    }
+
+    /// Fail with `FogswapSdkError::InsecureUrl` if `url` is not allowed by `https_only`
+    pub(crate) fn check_url(&self, url: &str) -> Result<()> {
+        if self.https_only && !is_secure_url(url) {
+            return Err(FogswapSdkError::InsecureUrl(redact_url(url)).into());
+        }
+        Ok(())
+    }
+}
+
+/// Response of a [`Transport`], with the body read in full
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl TransportResponse {
+
+    /// Response with `status` and a raw body
+    ///
+    /// # Panics
+    /// If `status` is not a valid HTTP status code
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        let status = StatusCode::from_u16(status).expect("valid HTTP status code");
+        Self { status, headers: HeaderMap::new(), body: body.into() }
+    }
+
+    /// Response with `status` and a JSON body
+    pub fn json(status: u16, body: &Value) -> Self {
+        Self::new(status, body.to_string())
+    }
+
+    /// Successful API response carrying `result` in its envelope
+    pub fn result(result: Value) -> Self {
+        Self::json(200, &json!({ "result": result }))
+    }
+
+    /// API error envelope, answered with status 200 like the API does
+    pub fn api_error(code: &str, message: &str) -> Self {
+        Self::json(200, &json!({ "error": { "code": code, "message": message } }))
+    }
+
+    pub fn with_header(mut self, name: reqwest::header::HeaderName, value: reqwest::header::HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+}
+
+/// Sends the HTTP requests of API calls
+///
+/// Authentication, retries, rate limiting and response decoding stay in the client;
+/// a transport only exchanges one request for one response. Replace the default
+/// `reqwest` transport to run code built on `FogswapSdk` against canned responses,
+/// see [`crate::testing::MockTransport`]. Token image downloads and mirror probes go
+/// through it as well; health and connectivity probes keep using the `reqwest` client.
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use futures::future::BoxFuture;
+/// use fogswap_sdk_rust::{FogswapSdk, Transport, TransportResponse};
+///
+/// #[derive(Debug)]
+/// struct Maintenance;
+///
+/// impl Transport for Maintenance {
+///     fn execute(&self, _req: reqwest::Request) -> BoxFuture<'_, anyhow::Result<TransportResponse>> {
+///         Box::pin(async { Ok(TransportResponse::new(503, "down for maintenance")) })
+///     }
+/// }
+///
+/// let sdk = FogswapSdk::new().with_transport(Arc::new(Maintenance));
+/// ```
+pub trait Transport: Debug + Send + Sync {
+    fn execute(&self, req: reqwest::Request) -> BoxFuture<'_, Result<TransportResponse>>;
+
+    /// Called with the name of the query parameter carrying the API key, so that
+    /// transports storing requests can leave it out
+    fn secret_query_param(&self, _name: &str) {}
+}
+
+/// Send `req` with `client` and read the whole body
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn execute_reqwest(client: &Client, req: reqwest::Request) -> Result<TransportResponse> {
+    fetch(client.clone(), req).await
+}
+
+/// Send `req` with `client` and read the whole body
+///
+/// `fetch` futures are not `Send`, so the request runs on the local task queue.
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn execute_reqwest(client: &Client, req: reqwest::Request) -> Result<TransportResponse> {
+    crate::rt::run_local(fetch(client.clone(), req)).await
+        .unwrap_or_else(|| Err(anyhow::anyhow!("request task dropped")))
+}
+
+/// Errors leave without their URL, which may carry a query-placed API key
+async fn fetch(client: Client, req: reqwest::Request) -> Result<TransportResponse> {
+    let resp = client.execute(req).await.map_err(reqwest::Error::without_url)?;
+    let status = resp.status();
+    let headers = resp.headers().clone();
+    let body = resp.bytes().await.map_err(reqwest::Error::without_url)?.to_vec();
+    Ok(TransportResponse { status, headers, body })
+}
+
+/// Whether `e` means the API could not be reached at all
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn is_connect_error(e: &reqwest::Error) -> bool {
+    e.is_connect()
+}
+
+/// Whether `e` means the API could not be reached at all
+///
+/// `fetch` does not tell connection failures apart, it only rejects the request.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn is_connect_error(e: &reqwest::Error) -> bool {
+    e.is_request() && e.status().is_none()
+}
+
+/// Whether `e` is a refused server certificate
+///
+/// Neither TLS backend exposes a typed error through `reqwest`, so the error chain is
+/// searched for the certificate failures of rustls and native-tls.
+pub(crate) fn is_certificate_error(e: &reqwest::Error) -> bool {
+    let mut source: Option<&dyn std::error::Error> = Some(e);
+    while let Some(e) = source {
+        if e.to_string().to_lowercase().contains("certificate") {
+            return true;
+        }
+        source = e.source();
+    }
+    false
+}
+
+/// Whether `url` uses HTTPS or only reaches the local machine
+pub(crate) fn is_secure_url(url: &str) -> bool {
+    let Ok(url) = Url::parse(url) else {
+        return false;
+    };
+    match url.scheme() {
+        "https" => true,
+        "http" => {
+            let host = url.host_str().unwrap_or_default().trim_start_matches('[').trim_end_matches(']');
+            host.eq_ignore_ascii_case("localhost") || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+        },
+        _ => false,
+    }
+}
+
+/// Resolver ordering or filtering addresses by IP family
+///
+/// The connector races families in the order the addresses are returned
+/// (happy eyeballs), so ordering is enough to express a preference.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+struct FamilyResolver(IpPreference);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Resolve for FamilyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let preference = self.0;
+        Box::pin(async move {
+            let mut addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            match preference {
+                IpPreference::System => {}
+                IpPreference::PreferV4 => addrs.sort_by_key(|a| a.is_ipv6()),
+                IpPreference::PreferV6 => addrs.sort_by_key(|a| a.is_ipv4()),
+                IpPreference::OnlyV4 => addrs.retain(|a| a.is_ipv4()),
+                IpPreference::OnlyV6 => addrs.retain(|a| a.is_ipv6()),
+            }
+            if addrs.is_empty() {
+                return Err(format!("no {:?} address for {}", preference, name.as_str()).into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+impl FogswapSdk {
+
+    /// Rebuild the HTTP client with the given transport options
+    /// # Errors
+    /// * `FogswapSdkError::InvalidConfig` - If the client was passed to `FogswapSdkBuilder::client`,
+    ///   which cannot be rebuilt
+    /// * `FogswapSdkError::InsecureUrl` - If `https_only` is set and the base URL or a mirror is plaintext
+    /// * If the HTTP client cannot be built
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use fogswap_sdk_rust::{FogswapSdk, IpPreference, RedirectPolicy, TransportOptions};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_transport_options(TransportOptions {
+    ///     ip_preference: IpPreference::OnlyV4,
+    ///     connect_timeout: Some(Duration::from_secs(5)),
+    ///     https_only: true,
+    ///     redirects: RedirectPolicy::None,
+    ///     ..Default::default()
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_transport_options(mut self, options: TransportOptions) -> Result<Self> {
+        if self.client_options.external_client {
+            let e = "transport options and proxy cannot be applied to an existing client";
+            return Err(FogswapSdkError::InvalidConfig(e.to_string()).into());
+        }
+        options.check_url(&self.base_url)?;
+        for url in self.mirrors.iter().flat_map(|mirrors| mirrors.urls()) {
+            options.check_url(url)?;
+        }
+        self.client = self.client_options.apply(options.apply(Client::builder())?)?.build()?;
+        self.transport = options;
+        Ok(self)
+    }
+
+    /// Send API requests through `transport` instead of the `reqwest` client
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    /// use fogswap_sdk_rust::testing::MockTransport;
+    ///
+    /// let sdk = FogswapSdk::new().with_transport(Arc::new(MockTransport::new()));
+    /// ```
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.http = Some(transport);
+        self.share_secret_query_param();
+        self
+    }
+
+    /// Tell the transport which query parameter carries the API key
+    pub(crate) fn share_secret_query_param(&self) {
+        if let (Some(transport), Some(api_key)) = (&self.http, &self.api_key)
+            && let ApiKeyPlacement::Query(name) = &api_key.placement {
+            transport.secret_query_param(name);
+        }
+    }
+
+    pub(crate) async fn execute(&self, req: reqwest::Request) -> Result<TransportResponse> {
+        match &self.http {
+            Some(transport) => transport.execute(req).await,
+            None => execute_reqwest(&self.client, req).await,
+        }
+    }
+
+    /// Send `req`, which is not an API call, through the transport once
+    ///
+    /// The URL must pass the transport options, interceptors see the request and its
+    /// response under the URL path, and the timeout of `req` is raced against the clock.
+    pub(crate) async fn execute_direct(&self, req: reqwest::Request) -> Result<TransportResponse> {
+        let url = req.url().to_string();
+        self.transport.check_url(&url)?;
+        let method = req.method().clone();
+        let endpoint = req.url().path().to_string();
+        let timeout = req.timeout().copied();
+        self.intercept_request(&endpoint, &req, None);
+        let sent = crate::rt::Instant::now();
+        let resp = match timeout {
+            Some(after) => tokio::select! {
+                result = self.execute(req) => result?,
+                _ = self.clock.sleep(after) => return Err(FogswapSdkError::Timeout { endpoint, after }.into()),
+            },
+            None => self.execute(req).await?,
+        };
+        self.intercept_response(&method, &endpoint, &url, &resp, sent.elapsed());
+        Ok(resp)
+    }
+
+    /// Send every request through `proxy`, keeping the other transport options
+    /// # Errors
+    /// * `FogswapSdkError::InvalidConfig` - If the client was passed to `FogswapSdkBuilder::client`
+    /// * If the HTTP client cannot be built
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::{FogswapSdk, ProxyConfig};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new().with_proxy(ProxyConfig::tor())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_proxy(self, proxy: ProxyConfig) -> Result<Self> {
+        let options = TransportOptions { proxy: Some(proxy), ..self.transport.clone() };
+        self.with_transport_options(options)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use crate::{ApiKeyPlacement, FogswapSdk, Pair};
+
+    #[tokio::test]
+    async fn connect_error_does_not_reveal_query_api_key() {
+        // Nothing listens on port 1
+        let sdk = FogswapSdk::builder()
+            .base_url("http://127.0.0.1:1")
+            .build()
+            .unwrap()
+            .with_api_key_in("SECRET_KEY_123", ApiKeyPlacement::Query("access".to_string()));
+
+        let e = sdk.get_pair_limits(&Pair::new("sol", "SOL", "eth", "ETH"), None).await.unwrap_err();
+        assert!(!format!("{:#}", e).contains("SECRET_KEY_123"));
+        assert!(!format!("{:?}", e).contains("SECRET_KEY_123"));
+    }
+}
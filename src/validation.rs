@@ -0,0 +1,137 @@
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::endpoint::parse_result;
+use crate::error::FogswapSdkError;
+#[cfg(feature = "validation")]
+use crate::req_structs::CreateTransactionRequest;
+use crate::FogswapSdk;
+
+/// Verdict of the address validation endpoint
+#[derive(Debug, Deserialize)]
+struct AddressValidation {
+    is_valid: bool,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+impl FogswapSdk {
+
+    /// Check that `address` (and `extra_id`) can receive funds on `network`
+    ///
+    /// With the `validation` feature, addresses that fail the local format and checksum
+    /// checks are rejected before any request is sent.
+    /// # Arguments
+    /// * `network` - The network of the address
+    /// * `address` - The address to check
+    /// * `extra_id` - The extra id (memo, destination tag), if any
+    /// # Errors
+    /// * `FogswapSdkError::InvalidAddress` - If the address is rejected locally or by the API
+    /// * `FogswapSdkError::Api` - If the address cannot be checked
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// sdk.validate_address("eth", "0x742d35Cc6634C0532925a3b844Bc454e4438f44e", None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn validate_address(&self, network: &str, address: &str, extra_id: Option<&str>) -> Result<()> {
+        #[cfg(feature = "validation")]
+        self.check_address_locally(network, address, extra_id)?;
+
+        let endpoint = "/market/validate_address";
+        let params = json!({ "network": network, "address": address, "extra_id": extra_id });
+        let resp = self.send_request(reqwest::Method::GET, endpoint, Some(params)).await?;
+        let validation: AddressValidation = parse_result(endpoint, resp)?;
+        if !validation.is_valid {
+            let e = validation.message.unwrap_or_else(|| format!("{} is not a valid {} address", address, network));
+            return Err(FogswapSdkError::InvalidAddress(e).into());
+        }
+        Ok(())
+    }
+
+    /// Check an address against the network registry and its checksum, without calling the API
+    ///
+    /// Networks without a registered format or a known checksum are accepted.
+    /// # Errors
+    /// * `FogswapSdkError::InvalidAddress` - If the format, checksum or required extra id is wrong
+    /// # Examples
+    /// ```
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// let sdk = FogswapSdk::new();
+    /// assert!(sdk.check_address_locally("eth", "0x742d35Cc6634C0532925a3b844Bc454e4438f44e", None).is_ok());
+    /// // One letter with the wrong case breaks the EIP-55 checksum
+    /// assert!(sdk.check_address_locally("eth", "0x742d35cc6634C0532925a3b844Bc454e4438f44e", None).is_err());
+    /// ```
+    #[cfg(feature = "validation")]
+    pub fn check_address_locally(&self, network: &str, address: &str, extra_id: Option<&str>) -> Result<()> {
+        self.networks.validate_address(network, address, extra_id)?;
+        if !checksum::is_valid(network, address) {
+            let e = format!("{} has an invalid {} checksum", address, network);
+            return Err(FogswapSdkError::InvalidAddress(e).into());
+        }
+        Ok(())
+    }
+
+    /// Check the payout and refund addresses of a create request locally
+    #[cfg(feature = "validation")]
+    pub(crate) fn check_request_addresses(&self, req: &CreateTransactionRequest) -> Result<()> {
+        self.check_address_locally(&req.network_to, &req.payout_address, req.payout_extra_id.as_deref())?;
+        if let Some(refund_address) = &req.refund_address {
+            self.check_address_locally(&req.network_from, refund_address, req.refund_extra_id.as_deref())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "validation")]
+mod checksum {
+    use sha3::{Digest, Keccak256};
+
+    use crate::networks::Network;
+
+    /// Whether the checksum of `address` holds, for networks whose encoding is known
+    pub fn is_valid(network: &str, address: &str) -> bool {
+        match Network::from(network) {
+            network if network.is_evm() => evm(address),
+            Network::Btc => segwit(address, "bc") || base58check(address, &[0x00, 0x05], 21),
+            Network::Ltc => segwit(address, "ltc") || base58check(address, &[0x30, 0x32, 0x05], 21),
+            Network::Doge => base58check(address, &[0x1e, 0x16], 21),
+            Network::Tron => base58check(address, &[0x41], 21),
+            Network::Sol => bs58::decode(address).into_vec().is_ok_and(|key| key.len() == 32),
+            _ => true,
+        }
+    }
+
+    /// EIP-55 mixed-case checksum; all-lowercase and all-uppercase addresses carry none
+    fn evm(address: &str) -> bool {
+        let Some(hex) = address.strip_prefix("0x") else {
+            return false;
+        };
+        if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return false;
+        }
+        if hex == hex.to_ascii_lowercase() || hex == hex.to_ascii_uppercase() {
+            return true;
+        }
+        let hash = Keccak256::digest(hex.to_ascii_lowercase().as_bytes());
+        hex.bytes().enumerate().all(|(i, b)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            !b.is_ascii_alphabetic() || b.is_ascii_uppercase() == (nibble >= 8)
+        })
+    }
+
+    fn segwit(address: &str, hrp: &str) -> bool {
+        bech32::segwit::decode(address).is_ok_and(|(decoded, _, _)| decoded.as_str().eq_ignore_ascii_case(hrp))
+    }
+
+    fn base58check(address: &str, versions: &[u8], len: usize) -> bool {
+        bs58::decode(address).with_check(None).into_vec()
+            .is_ok_and(|payload| payload.len() == len && versions.contains(&payload[0]))
+    }
+}
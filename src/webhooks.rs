@@ -0,0 +1,181 @@
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::error::FogswapSdkError;
+use crate::resp_structs::TransactionInfo;
+
+/// Header carrying the hex HMAC-SHA256 of the raw body, optionally prefixed with `sha256=`
+pub const SIGNATURE_HEADER: &str = "x-fogswap-signature";
+
+/// Event sent by Fogswap to a partner webhook
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data")]
+pub enum WebhookEvent {
+    #[serde(rename = "transaction.created")]
+    TransactionCreated(TransactionInfo),
+    /// The status changed, see `TransactionInfo::status`
+    #[serde(rename = "transaction.updated")]
+    TransactionUpdated(TransactionInfo),
+    /// The transaction is on hold until `set_emergency_action()` is called
+    #[serde(rename = "transaction.emergency")]
+    EmergencyRequired(TransactionInfo),
+    /// An event type added to the API after this SDK version; acknowledge it so that
+    /// it is not redelivered. Never serialized.
+    #[serde(skip_serializing)]
+    Unknown,
+}
+
+impl<'de> Deserialize<'de> for WebhookEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Tagged {
+            event: String,
+            #[serde(default)]
+            data: Value,
+        }
+        let tagged = Tagged::deserialize(deserializer)?;
+        let tx = |data: Value| serde_json::from_value::<TransactionInfo>(data).map_err(serde::de::Error::custom);
+        Ok(match tagged.event.as_str() {
+            "transaction.created" => WebhookEvent::TransactionCreated(tx(tagged.data)?),
+            "transaction.updated" => WebhookEvent::TransactionUpdated(tx(tagged.data)?),
+            "transaction.emergency" => WebhookEvent::EmergencyRequired(tx(tagged.data)?),
+            _ => WebhookEvent::Unknown,
+        })
+    }
+}
+
+impl WebhookEvent {
+
+    /// Transaction of the event, `None` for an `Unknown` one
+    pub fn transaction(&self) -> Option<&TransactionInfo> {
+        match self {
+            WebhookEvent::TransactionCreated(tx)
+            | WebhookEvent::TransactionUpdated(tx)
+            | WebhookEvent::EmergencyRequired(tx) => Some(tx),
+            WebhookEvent::Unknown => None,
+        }
+    }
+}
+
+/// Delivery of one webhook event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEnvelope {
+    /// Unique per event, the same across redeliveries
+    pub id: String,
+    /// Unix timestamp of the event in seconds
+    pub created_at: u64,
+    #[serde(flatten)]
+    pub event: WebhookEvent,
+}
+
+/// Check that `body` was signed by Fogswap with the webhook `secret`
+///
+/// Pass the body exactly as received, before any JSON parsing. The comparison
+/// is constant-time.
+/// # Errors
+/// * `FogswapSdkError::InvalidSignature` - If the header is missing, malformed or does not match
+/// # Examples
+/// ```
+/// use reqwest::header::HeaderMap;
+/// use fogswap_sdk_rust::webhooks::verify_signature;
+///
+/// let headers = HeaderMap::new();
+/// assert!(verify_signature("whsec", &headers, b"{}").is_err());
+/// ```
+pub fn verify_signature(secret: &str, headers: &HeaderMap, body: &[u8]) -> Result<()> {
+    let invalid = |reason: &str| FogswapSdkError::InvalidSignature(reason.to_string());
+    let header = headers
+        .get(SIGNATURE_HEADER)
+        .ok_or_else(|| invalid("missing signature header"))?
+        .to_str()
+        .map_err(|_| invalid("signature header is not ASCII"))?;
+    let hex = header.trim().strip_prefix("sha256=").unwrap_or(header.trim());
+    let signature = decode_hex(hex).ok_or_else(|| invalid("signature is not hex"))?;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).map_err(|_| invalid("unusable secret"))?;
+    mac.update(body);
+    mac.verify_slice(&signature).map_err(|_| invalid("signature mismatch"))?;
+    Ok(())
+}
+
+/// Verify the signature of a webhook delivery and decode it
+/// # Errors
+/// * `FogswapSdkError::InvalidSignature` - If the signature does not match
+/// * `FogswapSdkError::Decode` - If the body is not a webhook delivery; event types
+///   unknown to this SDK version decode as `WebhookEvent::Unknown`
+/// # Examples
+/// ```
+/// use fogswap_sdk_rust::webhooks::{parse_webhook, WebhookEvent};
+///
+/// # fn handle(headers: &reqwest::header::HeaderMap, body: &[u8]) -> anyhow::Result<()> {
+/// let envelope = parse_webhook("whsec", headers, body)?;
+/// if let WebhookEvent::TransactionUpdated(tx) = &envelope.event {
+///     println!("{} is now {}", tx.id, tx.status);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_webhook(secret: &str, headers: &HeaderMap, body: &[u8]) -> Result<WebhookEnvelope> {
+    verify_signature(secret, headers, body)?;
+    serde_json::from_slice(body).map_err(|e| {
+        FogswapSdkError::Decode {
+            endpoint: "webhook".to_string(),
+            reason: e.to_string(),
+            body: serde_json::from_slice(body).unwrap_or(Value::Null),
+        }
+        .into()
+    })
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use hmac::{Hmac, Mac};
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use sha2::Sha256;
+
+    use super::{parse_webhook, verify_signature, WebhookEvent, SIGNATURE_HEADER};
+
+    fn signed(secret: &str, body: &[u8]) -> HeaderMap {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let hex: String = mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, HeaderValue::from_str(&format!("sha256={}", hex)).unwrap());
+        headers
+    }
+
+    #[test]
+    fn signature_of_the_body_is_accepted_and_tampering_rejected() {
+        let body = br#"{"id":"evt_1","created_at":1700000000,"event":"transaction.updated","data":{}}"#;
+        let headers = signed("whsec_test", body);
+
+        assert!(verify_signature("whsec_test", &headers, body).is_ok());
+        assert!(verify_signature("whsec_other", &headers, body).is_err());
+        let tampered = String::from_utf8_lossy(body).replace("evt_1", "evt_2");
+        assert!(verify_signature("whsec_test", &headers, tampered.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn unknown_event_type_decodes_as_unknown() {
+        let body = br#"{"id":"evt_2","created_at":1700000000,"event":"payout.batched","data":{"batch":"b_1"}}"#;
+        let envelope = parse_webhook("whsec_test", &signed("whsec_test", body), body).unwrap();
+
+        assert_eq!(envelope.id, "evt_2");
+        assert!(matches!(envelope.event, WebhookEvent::Unknown));
+        assert!(envelope.event.transaction().is_none());
+    }
+}
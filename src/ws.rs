@@ -0,0 +1,196 @@
+use anyhow::Result;
+use futures::{SinkExt, Stream, StreamExt};
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::auth::ApiKeyPlacement;
+use crate::endpoint::parse_result;
+use crate::error::FogswapSdkError;
+use crate::req_structs::Pair;
+use crate::shutdown::cancelled;
+use crate::status::{StatusSequencer, TransactionStatus};
+use crate::FogswapSdk;
+
+/// Status change pushed for a subscribed transaction
+#[derive(Debug, Clone, Deserialize)]
+pub struct TxStatusEvent {
+    pub id: String,
+    pub status: TransactionStatus,
+}
+
+/// Rate update pushed for a subscribed pair
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateTickEvent {
+    #[serde(flatten)]
+    pub pair: Pair,
+    /// Destination units received per source unit
+    pub rate: f64,
+}
+
+impl FogswapSdk {
+
+    /// Stream the status changes of a transaction over the WebSocket API, without polling
+    ///
+    /// Duplicate, out-of-order and repeated unknown statuses are dropped, as by
+    /// `StatusSequencer`. The stream ends after a final status, when the server closes
+    /// the connection, or on `shutdown()`. Cancelling the token of `with_cancellation`
+    /// ends it with a `FogswapSdkError::Cancelled` item.
+    /// # Errors
+    /// * `FogswapSdkError::InsecureUrl` - If `https_only` is set and the API is not HTTPS
+    /// * `FogswapSdkError::InvalidConfig` - If a proxy or pinned certificates are configured
+    /// * `tungstenite::Error` - If the connection cannot be opened
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use fogswap_sdk_rust::FogswapSdk;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let mut events = std::pin::pin!(sdk.subscribe_transaction("S7ZulO3j16").await?);
+    /// while let Some(event) = events.try_next().await? {
+    ///     println!("{}", event.status.description());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_transaction(&self, tx_id: &str) -> Result<impl Stream<Item = Result<TxStatusEvent>> + use<>> {
+        let events = self.subscribe::<TxStatusEvent>("transaction", json!({ "id": tx_id })).await?;
+        let sequencer = StatusSequencer::default();
+        let events = events.filter(move |event| {
+            let new = match event {
+                Ok(event) => !sequencer.observe(&event.id, event.status.clone()).is_empty(),
+                Err(_) => true,
+            };
+            std::future::ready(new)
+        });
+        let mut done = false;
+        Ok(events.take_while(move |event| {
+            let more = !done;
+            done = done || event.as_ref().is_ok_and(|event| event.status.is_terminal());
+            std::future::ready(more)
+        }))
+    }
+
+    /// Stream rate updates of `pairs` over the WebSocket API
+    ///
+    /// The stream ends when the server closes the connection or on `shutdown()`, and
+    /// with a `FogswapSdkError::Cancelled` item when the token of `with_cancellation` is cancelled.
+    /// # Errors
+    /// * `FogswapSdkError::InsecureUrl` - If `https_only` is set and the API is not HTTPS
+    /// * `FogswapSdkError::InvalidConfig` - If a proxy or pinned certificates are configured
+    /// * `tungstenite::Error` - If the connection cannot be opened
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use fogswap_sdk_rust::{FogswapSdk, Pair};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sdk = FogswapSdk::new();
+    /// let pairs = [Pair::new("sol", "SOL", "eth", "ETH"), Pair::new("btc", "BTC", "eth", "ETH")];
+    /// let mut ticks = std::pin::pin!(sdk.subscribe_rates(&pairs).await?);
+    /// while let Some(tick) = ticks.try_next().await? {
+    ///     println!("{} -> {}: {}", tick.pair.contract_address_from, tick.pair.contract_address_to, tick.rate);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_rates(&self, pairs: &[Pair]) -> Result<impl Stream<Item = Result<RateTickEvent>> + use<>> {
+        self.subscribe::<RateTickEvent>("rates", json!({ "pairs": pairs })).await
+    }
+
+    /// Open a connection, subscribe to `channel` and decode the `data` of its messages
+    ///
+    /// Messages are `{"channel": ..., "data": ...}`, or `{"channel": ..., "error": {"message": ...}}`.
+    async fn subscribe<T: DeserializeOwned>(
+        &self,
+        channel: &'static str,
+        params: Value
+    ) -> Result<impl Stream<Item = Result<T>> + use<T>> {
+        self.ensure_active()?;
+        // The socket would connect directly, bypassing the proxy
+        if self.transport.proxy.is_some() {
+            let e = "WebSocket subscriptions cannot be sent through a proxy";
+            return Err(FogswapSdkError::InvalidConfig(e.to_string()).into());
+        }
+        // The socket trusts the webpki roots of its own TLS config, not the pinned certificates
+        if self.client_options.has_pinned_certificates() {
+            let e = "WebSocket subscriptions cannot use pinned certificates";
+            return Err(FogswapSdkError::InvalidConfig(e.to_string()).into());
+        }
+        let base_url = self.current_base_url();
+        self.transport.check_url(&base_url)?;
+
+        let mut url = Url::parse(&format!("{}{}", base_url.trim_end_matches('/'), WS_ENDPOINT))?;
+        let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+        // Both schemes are special, so the switch cannot fail
+        let _ = url.set_scheme(scheme);
+        if let Some(api_key) = &self.api_key
+            && let ApiKeyPlacement::Query(name) = &api_key.placement {
+            url.query_pairs_mut().append_pair(name, &api_key.key);
+        }
+
+        let mut request = url.as_str().into_client_request()?;
+        let headers = request.headers_mut();
+        if let Some(api_key) = &self.api_key
+            && let ApiKeyPlacement::Header(name) = &api_key.placement {
+            headers.insert(reqwest::header::HeaderName::from_bytes(name.as_bytes())?, HeaderValue::from_str(&api_key.key)?);
+        }
+        if let Some(partner_id) = &self.partner_id {
+            headers.insert("x-partner-id", HeaderValue::from_str(partner_id)?);
+        }
+        if let Some(auth) = &self.auth {
+            headers.insert("authorization", HeaderValue::from_str(&format!("Bearer {}", auth.token().await?))?);
+        }
+
+        let (mut socket, _) = tokio_tungstenite::connect_async(request).await?;
+        let mut subscribe = json!({ "op": "subscribe", "channel": channel });
+        if let (Some(fields), Some(params)) = (subscribe.as_object_mut(), params.as_object()) {
+            fields.extend(params.clone());
+        }
+        socket.send(Message::Text(subscribe.to_string().into())).await?;
+
+        let shutdown = self.shutdown.clone();
+        let events = socket.filter_map(move |message| std::future::ready(match message {
+            Ok(Message::Text(text)) => decode_event(channel, &text),
+            // Pings are answered by tungstenite, the close frame ends the stream
+            Ok(_) => None,
+            Err(e) => Some(Err(e.into())),
+        }));
+        let events = Box::pin(events.take_until(async move { shutdown.cancelled().await }));
+        // Cancellation is reported as a last `Cancelled` item, unlike shutdown
+        let cancel = self.cancel.clone();
+        Ok(futures::stream::unfold(Some(events), move |events| {
+            let cancel = cancel.clone();
+            async move {
+                let mut events = events?;
+                tokio::select! {
+                    event = events.next() => event.map(|event| (event, Some(events))),
+                    _ = cancelled(cancel) => Some((Err(FogswapSdkError::Cancelled.into()), None)),
+                }
+            }
+        }))
+    }
+}
+
+/// Endpoint errors of subscriptions are reported for
+const WS_ENDPOINT: &str = "/ws";
+
+pub(crate) fn decode_event<T: DeserializeOwned>(channel: &str, text: &str) -> Option<Result<T>> {
+    let Ok(message) = serde_json::from_str::<Value>(text) else {
+        return None;
+    };
+    if message.get("channel").and_then(Value::as_str) != Some(channel) {
+        return None;
+    }
+    if message.get("error").is_some_and(|e| !e.is_null()) {
+        let data = json!({ "error": message["error"].clone() });
+        return Some(parse_result(WS_ENDPOINT, data));
+    }
+    let data = message.get("data")?.clone();
+    Some(parse_result(WS_ENDPOINT, json!({ "result": data })))
+}